@@ -0,0 +1,126 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! WinZip AES encryption (AE-1 & AE-2).
+//!
+//! https://www.winzip.com/en/support/aes-encryption/
+
+use crate::error::{Result, ZipError};
+use crate::spec::header::AesExtraField;
+
+use aes::cipher::{generic_array::GenericArray, BlockEncrypt, KeyInit};
+use aes::{Aes128, Aes192, Aes256};
+use futures_util::io::{AsyncRead, AsyncReadExt};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+/// The length of the password verification value which follows the salt.
+const PASSWORD_VERIFICATION_LENGTH: u64 = 2;
+/// The length of the (truncated) HMAC-SHA1 authentication code which follows an entry's encrypted data.
+pub(crate) const AUTHENTICATION_CODE_LENGTH: u64 = 10;
+/// The number of PBKDF2 iterations used to derive the keys from the password.
+const KEY_DERIVATION_ITERATIONS: u32 = 1000;
+
+/// An AES block cipher of any of the supported key strengths.
+enum AesCipher {
+    Aes128(Aes128),
+    Aes192(Aes192),
+    Aes256(Aes256),
+}
+
+impl AesCipher {
+    fn encrypt_block(&self, block: &mut [u8; 16]) {
+        let block = GenericArray::from_mut_slice(block);
+
+        match self {
+            AesCipher::Aes128(cipher) => cipher.encrypt_block(block),
+            AesCipher::Aes192(cipher) => cipher.encrypt_block(block),
+            AesCipher::Aes256(cipher) => cipher.encrypt_block(block),
+        }
+    }
+}
+
+/// The state of AES-CTR decryption & HMAC-SHA1 authentication for a single entry.
+pub(crate) struct AesKeys {
+    cipher: AesCipher,
+    mac: Hmac<Sha1>,
+    counter: u128,
+    keystream: [u8; 16],
+    position: usize,
+}
+
+impl AesKeys {
+    /// Decrypts the provided data in place, authenticating the encrypted data beforehand.
+    pub(crate) fn decrypt(&mut self, data: &mut [u8]) {
+        self.mac.update(data);
+
+        for byte in data {
+            if self.position == self.keystream.len() {
+                // WinZip's CTR mode uses a little-endian counter starting at one.
+                self.counter = self.counter.wrapping_add(1);
+                self.keystream = self.counter.to_le_bytes();
+                self.cipher.encrypt_block(&mut self.keystream);
+                self.position = 0;
+            }
+
+            *byte ^= self.keystream[self.position];
+            self.position += 1;
+        }
+    }
+
+    /// Returns whether or not the provided authentication code matches that of the data decrypted so far.
+    pub(crate) fn authenticate(&self, code: &[u8]) -> bool {
+        let computed = self.mac.clone().finalize().into_bytes();
+        let computed = &computed[..AUTHENTICATION_CODE_LENGTH as usize];
+
+        // Avoid short-circuiting to keep the comparison constant-time.
+        code.len() == computed.len() && code.iter().zip(computed).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+    }
+}
+
+/// Returns the length of the salt which precedes an entry's data for the given key strength.
+fn salt_length(strength: u8) -> Result<usize> {
+    match strength {
+        1 => Ok(8),
+        2 => Ok(12),
+        3 => Ok(16),
+        _ => Err(ZipError::FeatureNotSupported("AES key strengths other than 128, 192, or 256 bits")),
+    }
+}
+
+/// Returns the combined length of the data which surrounds an entry's encrypted data.
+pub(crate) fn overhead(aes: &AesExtraField) -> Result<u64> {
+    Ok(salt_length(aes.strength)? as u64 + PASSWORD_VERIFICATION_LENGTH + AUTHENTICATION_CODE_LENGTH)
+}
+
+/// Reads the salt & password verification value which precede an entry's data, deriving the keys from the password
+/// and verifying them against that value.
+pub(crate) async fn read_header<R>(mut reader: R, password: &[u8], aes: &AesExtraField) -> Result<AesKeys>
+where
+    R: AsyncRead + Unpin,
+{
+    let salt_length = salt_length(aes.strength)?;
+    let key_length = salt_length * 2;
+
+    let mut header = vec![0; salt_length + PASSWORD_VERIFICATION_LENGTH as usize];
+    reader.read_exact(&mut header).await?;
+    let (salt, verification) = header.split_at(salt_length);
+
+    let mut derived = vec![0; key_length * 2 + PASSWORD_VERIFICATION_LENGTH as usize];
+    pbkdf2::pbkdf2::<Hmac<Sha1>>(password, salt, KEY_DERIVATION_ITERATIONS, &mut derived);
+    let (encryption_key, rest) = derived.split_at(key_length);
+    let (authentication_key, derived_verification) = rest.split_at(key_length);
+
+    if derived_verification != verification {
+        return Err(ZipError::WrongPassword);
+    }
+
+    let cipher = match aes.strength {
+        1 => AesCipher::Aes128(Aes128::new(GenericArray::from_slice(encryption_key))),
+        2 => AesCipher::Aes192(Aes192::new(GenericArray::from_slice(encryption_key))),
+        _ => AesCipher::Aes256(Aes256::new(GenericArray::from_slice(encryption_key))),
+    };
+    let mac = <Hmac<Sha1> as Mac>::new_from_slice(authentication_key).expect("HMAC accepts keys of any length");
+
+    Ok(AesKeys { cipher, mac, counter: 0, keystream: [0; 16], position: 16 })
+}
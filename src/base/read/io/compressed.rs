@@ -1,10 +1,12 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "lzma")]
+use crate::base::read::io::lzma::LzmaHeaderReader;
 use crate::spec::Compression;
 
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::task::{ready, Context, Poll};
 
 #[cfg(any(
     feature = "deflate",
@@ -22,14 +24,15 @@ use pin_project::pin_project;
 #[pin_project(project = CompressedReaderProj)]
 pub(crate) enum CompressedReader<R> {
     Stored(#[pin] R),
+    Empty(#[pin] R),
     #[cfg(feature = "deflate")]
-    Deflate(#[pin] bufread::DeflateDecoder<R>),
+    Deflate(DeflateReader<R>),
     #[cfg(feature = "deflate64")]
     Deflate64(#[pin] bufread::Deflate64Decoder<R>),
     #[cfg(feature = "bzip2")]
     Bz(#[pin] bufread::BzDecoder<R>),
     #[cfg(feature = "lzma")]
-    Lzma(#[pin] bufread::LzmaDecoder<R>),
+    Lzma(#[pin] bufread::LzmaDecoder<LzmaHeaderReader<R>>),
     #[cfg(feature = "zstd")]
     Zstd(#[pin] bufread::ZstdDecoder<R>),
     #[cfg(feature = "xz")]
@@ -45,13 +48,13 @@ where
         match compression {
             Compression::Stored => CompressedReader::Stored(reader),
             #[cfg(feature = "deflate")]
-            Compression::Deflate => CompressedReader::Deflate(bufread::DeflateDecoder::new(reader)),
+            Compression::Deflate => CompressedReader::Deflate(DeflateReader::new(reader)),
             #[cfg(feature = "deflate64")]
             Compression::Deflate64 => CompressedReader::Deflate64(bufread::Deflate64Decoder::new(reader)),
             #[cfg(feature = "bzip2")]
             Compression::Bz => CompressedReader::Bz(bufread::BzDecoder::new(reader)),
             #[cfg(feature = "lzma")]
-            Compression::Lzma => CompressedReader::Lzma(bufread::LzmaDecoder::new(reader)),
+            Compression::Lzma => CompressedReader::Lzma(bufread::LzmaDecoder::new(LzmaHeaderReader::new(reader))),
             #[cfg(feature = "zstd")]
             Compression::Zstd => CompressedReader::Zstd(bufread::ZstdDecoder::new(reader)),
             #[cfg(feature = "xz")]
@@ -59,10 +62,63 @@ where
         }
     }
 
+    /// Constructs a new wrapping reader for an entry known to be empty, which yields no data regardless of the entry's
+    /// compression method.
+    ///
+    /// Any data the inner reader holds (eg. an empty Deflate stream, or none at all) is consumed and discarded so that
+    /// the inner reader still ends up at the end of the entry.
+    pub(crate) fn empty(reader: R) -> Self {
+        CompressedReader::Empty(reader)
+    }
+
+    /// Sets the declared uncompressed size of the data, which must only be done if the inner reader is bounded to the
+    /// entry's compressed data.
+    ///
+    /// Some writers store Deflate data as multiple concatenated Deflate streams. Once the size is known, decoding
+    /// continues onto subsequent streams until either the inner reader is exhausted or the declared number of bytes
+    /// has been produced. This has no effect for other compression methods.
+    #[cfg_attr(not(feature = "deflate"), allow(unused_variables))]
+    pub(crate) fn set_uncompressed_size(&mut self, size: u64) {
+        #[cfg(feature = "deflate")]
+        if let CompressedReader::Deflate(inner) = self {
+            inner.remaining = Some(size);
+        }
+    }
+
+    /// Returns a mutable reference to the inner value if the data isn't compressed.
+    pub(crate) fn stored_mut(&mut self) -> Option<&mut R> {
+        match self {
+            CompressedReader::Stored(inner) => Some(inner),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the inner value.
+    pub(crate) fn get_ref(&self) -> &R {
+        match self {
+            CompressedReader::Stored(inner) => inner,
+            CompressedReader::Empty(inner) => inner,
+            #[cfg(feature = "deflate")]
+            CompressedReader::Deflate(inner) => inner.get_ref(),
+            #[cfg(feature = "deflate64")]
+            CompressedReader::Deflate64(inner) => inner.get_ref(),
+            #[cfg(feature = "bzip2")]
+            CompressedReader::Bz(inner) => inner.get_ref(),
+            #[cfg(feature = "lzma")]
+            CompressedReader::Lzma(inner) => inner.get_ref().get_ref(),
+            #[cfg(feature = "zstd")]
+            CompressedReader::Zstd(inner) => inner.get_ref(),
+            #[cfg(feature = "xz")]
+            CompressedReader::Xz(inner) => inner.get_ref(),
+        }
+    }
+
     /// Consumes this reader and returns the inner value.
     pub(crate) fn into_inner(self) -> R {
         match self {
             CompressedReader::Stored(inner) => inner,
+            CompressedReader::Empty(inner) => inner,
             #[cfg(feature = "deflate")]
             CompressedReader::Deflate(inner) => inner.into_inner(),
             #[cfg(feature = "deflate64")]
@@ -70,7 +126,7 @@ where
             #[cfg(feature = "bzip2")]
             CompressedReader::Bz(inner) => inner.into_inner(),
             #[cfg(feature = "lzma")]
-            CompressedReader::Lzma(inner) => inner.into_inner(),
+            CompressedReader::Lzma(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "zstd")]
             CompressedReader::Zstd(inner) => inner.into_inner(),
             #[cfg(feature = "xz")]
@@ -86,8 +142,15 @@ where
     fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
         match self.project() {
             CompressedReaderProj::Stored(inner) => inner.poll_read(c, b),
+            CompressedReaderProj::Empty(mut inner) => loop {
+                let consumed = ready!(inner.as_mut().poll_fill_buf(c))?.len();
+                if consumed == 0 {
+                    return Poll::Ready(Ok(0));
+                }
+                inner.as_mut().consume(consumed);
+            },
             #[cfg(feature = "deflate")]
-            CompressedReaderProj::Deflate(inner) => inner.poll_read(c, b),
+            CompressedReaderProj::Deflate(inner) => Pin::new(inner).poll_read(c, b),
             #[cfg(feature = "deflate64")]
             CompressedReaderProj::Deflate64(inner) => inner.poll_read(c, b),
             #[cfg(feature = "bzip2")]
@@ -101,3 +164,66 @@ where
         }
     }
 }
+
+/// A Deflate decoder which may continue onto further Deflate streams concatenated after the first.
+///
+/// See [`CompressedReader::set_uncompressed_size()`] for when this occurs.
+#[cfg(feature = "deflate")]
+pub(crate) struct DeflateReader<R> {
+    // Only ever taken whilst being replaced by a decoder for the next stream.
+    decoder: Option<bufread::DeflateDecoder<R>>,
+    remaining: Option<u64>,
+}
+
+#[cfg(feature = "deflate")]
+impl<R> DeflateReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn new(reader: R) -> Self {
+        Self { decoder: Some(bufread::DeflateDecoder::new(reader)), remaining: None }
+    }
+
+    fn decoder(&self) -> &bufread::DeflateDecoder<R> {
+        self.decoder.as_ref().expect("decoder is only taken whilst being replaced")
+    }
+
+    fn get_ref(&self) -> &R {
+        self.decoder().get_ref()
+    }
+
+    fn into_inner(mut self) -> R {
+        self.decoder.take().expect("decoder is only taken whilst being replaced").into_inner()
+    }
+}
+
+#[cfg(feature = "deflate")]
+impl<R> AsyncRead for DeflateReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let this = &mut *self;
+
+        loop {
+            let decoder = this.decoder.as_mut().expect("decoder is only taken whilst being replaced");
+            let read = ready!(Pin::new(&mut *decoder).poll_read(c, b))?;
+
+            if read != 0 || b.is_empty() {
+                this.remaining = this.remaining.map(|remaining| remaining.saturating_sub(read as u64));
+                return Poll::Ready(Ok(read));
+            }
+
+            // The current stream has ended, so only continue if more data was declared and more input remains.
+            if this.remaining.unwrap_or(0) == 0 {
+                return Poll::Ready(Ok(0));
+            }
+            if ready!(Pin::new(decoder.get_mut()).poll_fill_buf(c))?.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            let reader = this.decoder.take().expect("decoder is only taken whilst being replaced").into_inner();
+            this.decoder = Some(bufread::DeflateDecoder::new(reader));
+        }
+    }
+}
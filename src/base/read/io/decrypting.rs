@@ -0,0 +1,256 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+#[cfg(feature = "aes")]
+use crate::base::read::io::aes::{AesKeys, AUTHENTICATION_CODE_LENGTH};
+use crate::base::read::io::zipcrypto::ZipCryptoKeys;
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_util::io::{AsyncBufRead, AsyncRead};
+use pin_project::pin_project;
+
+/// A wrapping reader which decrypts the data of encrypted entries (or passes through that of unencrypted entries).
+#[pin_project(project = DecryptingReaderProj)]
+pub(crate) enum DecryptingReader<R> {
+    None(#[pin] R),
+    ZipCrypto(#[pin] ZipCryptoReader<R>),
+    #[cfg(feature = "aes")]
+    Aes(#[pin] AesReader<R>),
+}
+
+/// The cipher (and its state) with which an entry's data should be decrypted.
+pub(crate) enum Decryption {
+    None,
+    ZipCrypto(ZipCryptoKeys),
+    /// The keys alongside the length of the encrypted data (excluding the trailing authentication code).
+    #[cfg(feature = "aes")]
+    Aes(Box<AesKeys>, u64),
+}
+
+impl<R> DecryptingReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Constructs a new wrapping reader from a generic [`AsyncBufRead`] implementer.
+    pub(crate) fn new(reader: R, decryption: Decryption) -> Self {
+        match decryption {
+            Decryption::None => DecryptingReader::None(reader),
+            Decryption::ZipCrypto(keys) => {
+                DecryptingReader::ZipCrypto(ZipCryptoReader { reader, keys, buffer: Vec::new(), position: 0 })
+            }
+            #[cfg(feature = "aes")]
+            Decryption::Aes(keys, remaining) => DecryptingReader::Aes(AesReader {
+                reader,
+                keys,
+                remaining,
+                buffer: Vec::new(),
+                position: 0,
+                code: Vec::with_capacity(AUTHENTICATION_CODE_LENGTH as usize),
+            }),
+        }
+    }
+
+    /// Returns a mutable reference to the inner value if the data isn't encrypted.
+    pub(crate) fn unencrypted_mut(&mut self) -> Option<&mut R> {
+        match self {
+            DecryptingReader::None(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the inner value.
+    pub(crate) fn get_ref(&self) -> &R {
+        match self {
+            DecryptingReader::None(inner) => inner,
+            DecryptingReader::ZipCrypto(inner) => &inner.reader,
+            #[cfg(feature = "aes")]
+            DecryptingReader::Aes(inner) => &inner.reader,
+        }
+    }
+
+    /// Consumes this reader and returns the inner value.
+    pub(crate) fn into_inner(self) -> R {
+        match self {
+            DecryptingReader::None(inner) => inner,
+            DecryptingReader::ZipCrypto(inner) => inner.reader,
+            #[cfg(feature = "aes")]
+            DecryptingReader::Aes(inner) => inner.reader,
+        }
+    }
+}
+
+impl<R> AsyncBufRead for DecryptingReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        match self.project() {
+            DecryptingReaderProj::None(inner) => inner.poll_fill_buf(cx),
+            DecryptingReaderProj::ZipCrypto(inner) => inner.poll_fill_buf(cx),
+            #[cfg(feature = "aes")]
+            DecryptingReaderProj::Aes(inner) => inner.poll_fill_buf(cx),
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        match self.project() {
+            DecryptingReaderProj::None(inner) => inner.consume(amt),
+            DecryptingReaderProj::ZipCrypto(inner) => inner.consume(amt),
+            #[cfg(feature = "aes")]
+            DecryptingReaderProj::Aes(inner) => inner.consume(amt),
+        }
+    }
+}
+
+impl<R> AsyncRead for DecryptingReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        match self.project() {
+            DecryptingReaderProj::None(inner) => inner.poll_read(c, b),
+            DecryptingReaderProj::ZipCrypto(inner) => inner.poll_read(c, b),
+            #[cfg(feature = "aes")]
+            DecryptingReaderProj::Aes(inner) => inner.poll_read(c, b),
+        }
+    }
+}
+
+/// A wrapping reader which decrypts data encrypted with traditional PKWARE encryption.
+#[pin_project]
+pub(crate) struct ZipCryptoReader<R> {
+    #[pin]
+    reader: R,
+    keys: ZipCryptoKeys,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl<R> AsyncBufRead for ZipCryptoReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let mut project = self.project();
+
+        if *project.position >= project.buffer.len() {
+            let encrypted = ready!(project.reader.as_mut().poll_fill_buf(cx))?;
+            let length = encrypted.len();
+
+            project.buffer.clear();
+            project.buffer.extend(encrypted.iter().map(|&byte| project.keys.decrypt(byte)));
+            *project.position = 0;
+            project.reader.consume(length);
+        }
+
+        Poll::Ready(Ok(&project.buffer[*project.position..]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let project = self.project();
+        *project.position = (*project.position + amt).min(project.buffer.len());
+    }
+}
+
+impl<R> AsyncRead for ZipCryptoReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let available = ready!(self.as_mut().poll_fill_buf(c))?;
+        let length = available.len().min(b.len());
+
+        b[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Poll::Ready(Ok(length))
+    }
+}
+
+/// A wrapping reader which decrypts data encrypted with WinZip AES encryption, verifying the trailing authentication
+/// code once all of the encrypted data has been read.
+#[cfg(feature = "aes")]
+#[pin_project]
+pub(crate) struct AesReader<R> {
+    #[pin]
+    reader: R,
+    keys: Box<AesKeys>,
+    remaining: u64,
+    buffer: Vec<u8>,
+    position: usize,
+    code: Vec<u8>,
+}
+
+#[cfg(feature = "aes")]
+impl<R> AsyncBufRead for AesReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let mut project = self.project();
+
+        if *project.position < project.buffer.len() {
+            return Poll::Ready(Ok(&project.buffer[*project.position..]));
+        }
+
+        if *project.remaining > 0 {
+            let encrypted = ready!(project.reader.as_mut().poll_fill_buf(cx))?;
+            let length = encrypted.len().min(*project.remaining as usize);
+
+            if length == 0 {
+                return Poll::Ready(Err(std::io::ErrorKind::UnexpectedEof.into()));
+            }
+
+            project.buffer.clear();
+            project.buffer.extend_from_slice(&encrypted[..length]);
+            project.keys.decrypt(project.buffer);
+            *project.position = 0;
+            *project.remaining -= length as u64;
+            project.reader.consume(length);
+
+            return Poll::Ready(Ok(&project.buffer[..]));
+        }
+
+        while project.code.len() < AUTHENTICATION_CODE_LENGTH as usize {
+            let available = ready!(project.reader.as_mut().poll_fill_buf(cx))?;
+            let length = available.len().min(AUTHENTICATION_CODE_LENGTH as usize - project.code.len());
+
+            if length == 0 {
+                return Poll::Ready(Err(std::io::ErrorKind::UnexpectedEof.into()));
+            }
+
+            project.code.extend_from_slice(&available[..length]);
+            project.reader.as_mut().consume(length);
+        }
+
+        if !project.keys.authenticate(project.code) {
+            let error = std::io::Error::new(std::io::ErrorKind::InvalidData, "AES authentication code mismatch");
+            return Poll::Ready(Err(error));
+        }
+
+        Poll::Ready(Ok(&[]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let project = self.project();
+        *project.position = (*project.position + amt).min(project.buffer.len());
+    }
+}
+
+#[cfg(feature = "aes")]
+impl<R> AsyncRead for AesReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let available = ready!(self.as_mut().poll_fill_buf(c))?;
+        let length = available.len().min(b.len());
+
+        b[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Poll::Ready(Ok(length))
+    }
+}
@@ -1,17 +1,24 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
-use crate::base::read::io::{compressed::CompressedReader, hashed::HashedReader, owned::OwnedReader};
-use crate::entry::ZipEntry;
+use crate::base::read::get_aes_extra_field;
+#[cfg(feature = "aes")]
+use crate::base::read::io::aes;
+use crate::base::read::io::decrypting::{DecryptingReader, Decryption};
+use crate::base::read::io::{compressed::CompressedReader, hashed::HashedReader, owned::OwnedReader, zipcrypto};
+use crate::entry::{StoredZipEntry, ZipEntry};
 use crate::error::{Result, ZipError};
 use crate::spec::Compression;
 
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::task::{ready, Context, Poll};
 
-use futures_util::io::{AsyncRead, AsyncReadExt, BufReader, Take};
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncWrite, BufReader, SeekFrom, Take};
 use pin_project::pin_project;
 
+/// The default number of bytes an entry reader yields before returning control to the runtime, equal to 1MiB.
+pub(crate) const DEFAULT_YIELD_BUDGET: u64 = 1024 * 1024;
+
 /// A type which encodes that [`ZipEntryReader`] has associated entry data.
 pub struct WithEntry<'a>(OwnedEntry<'a>);
 
@@ -19,11 +26,34 @@ pub struct WithEntry<'a>(OwnedEntry<'a>);
 pub struct WithoutEntry;
 
 /// A ZIP entry reader which may implement decompression.
+///
+/// # Cancellation safety
+/// Reads are cancellation safe. All decompression, decryption, and hashing state is held within the reader itself and
+/// only advanced once bytes are returned, so if a read future is dropped before completing (eg. as another branch of a
+/// `tokio::select!` completed first), no data is lost or duplicated and the next read continues where the last one
+/// left off.
 #[pin_project]
 pub struct ZipEntryReader<'a, R, E> {
     #[pin]
-    reader: HashedReader<CompressedReader<Take<OwnedReader<'a, R>>>>,
+    reader: HashedReader<CompressedReader<DecryptingReader<Take<OwnedReader<'a, R>>>>>,
     entry: E,
+    compression: Compression,
+    peeked: Option<u8>,
+    size: u64,
+    yield_budget: u64,
+    since_yield: u64,
+    expansion_limit: Option<ExpansionLimit>,
+    produced: u64,
+    #[cfg(feature = "tokio-fs")]
+    permit: Option<tokio::sync::OwnedSemaphorePermit>,
+}
+
+/// A limit on the ratio of bytes produced by decompression to compressed bytes consumed, which is only enforced once
+/// more than a threshold number of bytes have been produced.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ExpansionLimit {
+    pub(crate) ratio: u64,
+    pub(crate) threshold: u64,
 }
 
 impl<'a, R> ZipEntryReader<'a, R, WithoutEntry>
@@ -31,23 +61,136 @@ where
     R: AsyncRead + Unpin,
 {
     /// Constructs a new entry reader from its required parameters (incl. an owned R).
-    pub(crate) fn new_with_owned(reader: BufReader<R>, compression: Compression, size: u64) -> Self {
-        let reader = HashedReader::new(CompressedReader::new(OwnedReader::Owned(reader).take(size), compression));
-        Self { reader, entry: WithoutEntry }
+    ///
+    /// If the entry is known to be empty, the reader yields no data regardless of its compression method (see
+    /// [`CompressedReader::empty()`]).
+    pub(crate) fn new_with_owned(reader: BufReader<R>, compression: Compression, size: u64, empty: bool) -> Self {
+        Self::new_with_decryption(OwnedReader::Owned(reader), compression, size, empty, Decryption::None)
     }
 
     /// Constructs a new entry reader from its required parameters (incl. a mutable borrow of an R).
-    pub(crate) fn new_with_borrow(reader: BufReader<&'a mut R>, compression: Compression, size: u64) -> Self {
-        let reader = HashedReader::new(CompressedReader::new(OwnedReader::Borrow(reader).take(size), compression));
-        Self { reader, entry: WithoutEntry }
+    ///
+    /// If the entry is known to be empty, the reader yields no data regardless of its compression method (see
+    /// [`CompressedReader::empty()`]).
+    pub(crate) fn new_with_borrow(
+        reader: BufReader<&'a mut R>,
+        compression: Compression,
+        size: u64,
+        empty: bool,
+    ) -> Self {
+        Self::new_with_decryption(OwnedReader::Borrow(reader), compression, size, empty, Decryption::None)
+    }
+
+    /// Constructs a new entry reader which decrypts the entry's data with the provided password (incl. an owned R).
+    ///
+    /// The reader must be positioned at the start of the entry's data. If the entry isn't encrypted, the password is
+    /// ignored.
+    pub(crate) async fn new_with_owned_password(
+        mut reader: BufReader<R>,
+        entry: &StoredZipEntry,
+        password: &[u8],
+    ) -> Result<Self> {
+        let (decryption, size) = read_decryption(&mut reader, entry, password).await?;
+        Ok(Self::new_with_decryption(
+            OwnedReader::Owned(reader),
+            entry.compression(),
+            size,
+            entry.is_empty(),
+            decryption,
+        )
+        .with_uncompressed_size(entry.uncompressed_size()))
+    }
+
+    /// Constructs a new entry reader which decrypts the entry's data with the provided password (incl. a mutable
+    /// borrow of an R).
+    ///
+    /// The reader must be positioned at the start of the entry's data. If the entry isn't encrypted, the password is
+    /// ignored.
+    pub(crate) async fn new_with_borrow_password(
+        mut reader: BufReader<&'a mut R>,
+        entry: &StoredZipEntry,
+        password: &[u8],
+    ) -> Result<Self> {
+        let (decryption, size) = read_decryption(&mut reader, entry, password).await?;
+        Ok(Self::new_with_decryption(
+            OwnedReader::Borrow(reader),
+            entry.compression(),
+            size,
+            entry.is_empty(),
+            decryption,
+        )
+        .with_uncompressed_size(entry.uncompressed_size()))
+    }
+
+    fn new_with_decryption(
+        reader: OwnedReader<'a, R>,
+        compression: Compression,
+        size: u64,
+        empty: bool,
+        decryption: Decryption,
+    ) -> Self {
+        let reader = DecryptingReader::new(reader.take(size), decryption);
+        let reader = match empty {
+            true => CompressedReader::empty(reader),
+            false => CompressedReader::new(reader, compression),
+        };
+
+        Self {
+            reader: HashedReader::new(reader),
+            entry: WithoutEntry,
+            compression,
+            peeked: None,
+            size,
+            yield_budget: DEFAULT_YIELD_BUDGET,
+            since_yield: 0,
+            expansion_limit: None,
+            produced: 0,
+            #[cfg(feature = "tokio-fs")]
+            permit: None,
+        }
+    }
+
+    /// Sets the entry's declared uncompressed size, so that Deflate data made up of multiple concatenated streams is read
+    /// in full (see [`CompressedReader::set_uncompressed_size()`]).
+    pub(crate) fn with_uncompressed_size(mut self, size: u64) -> Self {
+        // Without a bound on the compressed data (ie. when stream reading an entry with a data descriptor), whatever
+        // follows the first stream can't be told apart from another stream.
+        if self.size != u64::MAX {
+            self.reader.reader.set_uncompressed_size(size);
+        }
+        self
     }
 
     pub(crate) fn into_with_entry(self, entry: &'a ZipEntry) -> ZipEntryReader<'a, R, WithEntry<'a>> {
-        ZipEntryReader { reader: self.reader, entry: WithEntry(OwnedEntry::Borrow(entry)) }
+        ZipEntryReader {
+            reader: self.reader,
+            entry: WithEntry(OwnedEntry::Borrow(entry)),
+            compression: self.compression,
+            peeked: self.peeked,
+            size: self.size,
+            yield_budget: self.yield_budget,
+            since_yield: self.since_yield,
+            expansion_limit: self.expansion_limit,
+            produced: self.produced,
+            #[cfg(feature = "tokio-fs")]
+            permit: self.permit,
+        }
     }
 
     pub(crate) fn into_with_entry_owned(self, entry: ZipEntry) -> ZipEntryReader<'a, R, WithEntry<'a>> {
-        ZipEntryReader { reader: self.reader, entry: WithEntry(OwnedEntry::Owned(entry)) }
+        ZipEntryReader {
+            reader: self.reader,
+            entry: WithEntry(OwnedEntry::Owned(Box::new(entry))),
+            compression: self.compression,
+            peeked: self.peeked,
+            size: self.size,
+            yield_budget: self.yield_budget,
+            since_yield: self.since_yield,
+            expansion_limit: self.expansion_limit,
+            produced: self.produced,
+            #[cfg(feature = "tokio-fs")]
+            permit: self.permit,
+        }
     }
 }
 
@@ -56,7 +199,90 @@ where
     R: AsyncRead + Unpin,
 {
     fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
-        self.project().reader.poll_read(c, b)
+        let mut this = self.project();
+        let mut b = b;
+
+        // A byte read whilst checking for EOF (see `ZipEntryReader::verify()`) has already been hashed and accounted for.
+        if let Some(byte) = this.peeked.take() {
+            let Some(first) = b.first_mut() else {
+                *this.peeked = Some(byte);
+                return Poll::Ready(Ok(0));
+            };
+            *first = byte;
+            return Poll::Ready(Ok(1));
+        }
+
+        // Cooperatively yield once the budget has been read, so that decompressing a large entry from a source which is
+        // always ready doesn't starve other tasks on the runtime.
+        if *this.yield_budget != 0 {
+            if *this.since_yield >= *this.yield_budget {
+                *this.since_yield = 0;
+                c.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+
+            let remaining = (*this.yield_budget - *this.since_yield).min(b.len() as u64) as usize;
+            b = &mut b[..remaining];
+        }
+
+        let read = ready!(this.reader.as_mut().poll_read(c, b))?;
+        *this.since_yield += read as u64;
+        *this.produced += read as u64;
+
+        if let Some(limit) = this.expansion_limit {
+            let consumed = *this.size - this.reader.as_ref().get_ref().reader.get_ref().get_ref().limit();
+            if *this.produced > limit.threshold && *this.produced > consumed.saturating_mul(limit.ratio) {
+                let error = ZipError::SuspiciousExpansion(limit.ratio);
+                return Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error)));
+            }
+        }
+
+        Poll::Ready(Ok(read))
+    }
+}
+
+/// Seeking is only supported within entries which are neither compressed nor encrypted, with positions being relative
+/// to the start of the entry's data and clamped to its size. As the CRC32 hash is computed over the bytes read, it
+/// won't match the stored value once a seek has occurred.
+impl<'a, R, E> AsyncSeek for ZipEntryReader<'a, R, E>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    fn poll_seek(self: Pin<&mut Self>, c: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        let this = self.project();
+        let size = *this.size;
+
+        let Some(reader) = this.reader.get_mut().reader.stored_mut().and_then(|reader| reader.unencrypted_mut()) else {
+            let message = "seeking is only supported within Stored entries which aren't encrypted";
+            return Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::Unsupported, message)));
+        };
+
+        // A byte peeked whilst checking for EOF has been read from the inner reader, but not yet returned.
+        let inner_position = size - reader.limit();
+        let position = inner_position - u64::from(this.peeked.is_some());
+        let target = match pos {
+            SeekFrom::Start(offset) => i128::from(offset),
+            SeekFrom::Current(offset) => i128::from(position) + i128::from(offset),
+            SeekFrom::End(offset) => i128::from(size) + i128::from(offset),
+        };
+
+        if target < 0 {
+            let message = "invalid seek to a negative position";
+            return Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, message)));
+        }
+
+        let target = target.min(i128::from(size)) as u64;
+        let relative = target as i64 - inner_position as i64;
+
+        match Pin::new(reader.get_mut()).poll_seek(c, SeekFrom::Current(relative)) {
+            Poll::Ready(Ok(_)) => {
+                reader.set_limit(size - target);
+                *this.peeked = None;
+                Poll::Ready(Ok(target))
+            }
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        }
     }
 }
 
@@ -64,6 +290,14 @@ impl<'a, R, E> ZipEntryReader<'a, R, E>
 where
     R: AsyncRead + Unpin,
 {
+    /// Returns the compression method with which this reader decompresses the entry's data.
+    ///
+    /// For AES-encrypted entries, this is the actual method stored within the AES extra field rather than the
+    /// placeholder method (99) stored within the entry's headers.
+    pub fn compression_method(&self) -> Compression {
+        self.compression
+    }
+
     /// Computes and returns the CRC32 hash of bytes read by this reader so far.
     ///
     /// This hash should only be computed once EOF has been reached.
@@ -71,9 +305,53 @@ where
         self.reader.swap_and_compute_hash()
     }
 
-    /// Consumes this reader and returns the inner value.
-    pub(crate) fn into_inner(self) -> R {
-        self.reader.into_inner().into_inner().into_inner().owned_into_inner()
+    /// Disables computing the CRC32 hash of bytes read by this reader, so that it isn't verified.
+    ///
+    /// This trades safety for throughput, so should only be used for trusted data (or where partial/corrupt data is
+    /// acceptable). Once disabled, [`ZipEntryReader::compute_hash()`] no longer represents the data read in.
+    pub fn without_crc_check(mut self) -> Self {
+        self.reader.enabled = false;
+        self
+    }
+
+    /// Sets the number of bytes this reader yields before returning control to the runtime (defaults to 1 MiB).
+    ///
+    /// Once the budget has been read, the next read returns [`Poll::Pending`] (after waking the task) so that other
+    /// tasks on a single-threaded runtime can make progress. Individual reads are also bounded by the budget. A budget
+    /// of zero disables yielding.
+    pub fn with_yield_budget(mut self, budget: u64) -> Self {
+        self.yield_budget = budget;
+        self.since_yield = 0;
+        self
+    }
+
+    /// Limits the ratio of decompressed bytes this reader produces to compressed bytes it consumes (eg. a ratio of 1000
+    /// allows each compressed byte to expand into up to 1000 bytes), guarding against ZIP bombs.
+    ///
+    /// The limit is only enforced once more than the threshold number of bytes have been produced, so that small but
+    /// highly-compressible entries can still be read. Once exceeded, reads fail with an error which converts into
+    /// [`ZipError::SuspiciousExpansion`]. No limit is enforced by default.
+    pub fn with_expansion_limit(mut self, ratio: u64, threshold: u64) -> Self {
+        self.expansion_limit = Some(ExpansionLimit { ratio, threshold });
+        self
+    }
+
+    /// Sets (or clears) the expansion limit of this reader (see [`ZipEntryReader::with_expansion_limit()`]).
+    pub(crate) fn with_expansion_limit_opt(mut self, limit: Option<ExpansionLimit>) -> Self {
+        self.expansion_limit = limit;
+        self
+    }
+
+    /// Ties a semaphore permit to this reader, so that it's only released once the reader is dropped.
+    #[cfg(feature = "tokio-fs")]
+    pub(crate) fn with_permit(mut self, permit: Option<tokio::sync::OwnedSemaphorePermit>) -> Self {
+        self.permit = permit;
+        self
+    }
+
+    /// Consumes this reader and returns the inner buffered value.
+    pub(crate) fn into_inner(self) -> BufReader<R> {
+        self.reader.into_inner().into_inner().into_inner().into_inner().owned_into_inner()
     }
 }
 
@@ -86,17 +364,102 @@ where
         self.entry.0.entry()
     }
 
+    /// Verifies that the CRC32 hash of the bytes read so far matches the value stored for the associated entry.
+    ///
+    /// This should only be called once EOF has been reached as the internal hasher is reset by the check (see
+    /// [`ZipEntryReader::compute_hash()`]). If any data remains to be read, [`ZipError::EOFNotReached`] is returned
+    /// and no data is lost, as the next read still begins where the last one left off.
+    ///
+    /// If the check was disabled via [`ZipEntryReader::without_crc_check()`], only reaching EOF is verified.
+    pub async fn verify(&mut self) -> Result<()> {
+        // Reaching EOF can't otherwise be determined for compressed data without decompressing it, so a single byte is
+        // read and held back for the next read if one remains.
+        let mut byte = [0; 1];
+        if self.read(&mut byte).await? != 0 {
+            self.peeked = Some(byte[0]);
+            return Err(ZipError::EOFNotReached);
+        }
+
+        if !self.reader.enabled {
+            return Ok(());
+        }
+
+        // AE-2 entries don't store a CRC32 value as their data is instead verified via the AES authentication code.
+        if get_aes_extra_field(self.entry.0.entry().extra_fields()).is_some_and(|aes| aes.version == 2) {
+            return Ok(());
+        }
+
+        let (expected, actual) = (self.entry.0.entry().crc32(), self.compute_hash());
+        match expected == actual {
+            true => Ok(()),
+            false => Err(ZipError::CRC32CheckError { expected, actual }),
+        }
+    }
+
+    /// Copies all bytes until EOF has been reached into the provided writer, and verifies the CRC32 values.
+    ///
+    /// This is a helper function synonymous to [`futures_util::io::copy()`], returning the number of bytes written.
+    pub async fn copy_to_checked<W: AsyncWrite + Unpin>(&mut self, mut writer: W) -> Result<u64> {
+        let written = futures_util::io::copy(&mut *self, &mut writer).await?;
+        self.verify().await?;
+
+        Ok(written)
+    }
+
+    /// Reads and discards all bytes until EOF has been reached, and verifies the CRC32 values.
+    pub(crate) async fn consume_checked(&mut self) -> Result<()> {
+        futures_util::io::copy(&mut *self, &mut futures_util::io::sink()).await?;
+        self.verify().await
+    }
+
     /// Reads all bytes until EOF has been reached, appending them to buf, and verifies the CRC32 values.
     ///
     /// This is a helper function synonymous to [`AsyncReadExt::read_to_end()`].
     pub async fn read_to_end_checked(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
         let read = self.read_to_end(buf).await?;
+        self.verify().await?;
+
+        Ok(read)
+    }
 
-        if self.compute_hash() == self.entry.0.entry().crc32() {
-            Ok(read)
-        } else {
-            Err(ZipError::CRC32CheckError)
+    /// Reads all bytes until EOF has been reached, appending them to buf, and verifies the CRC32 values.
+    ///
+    /// [`ZipError::EntryTooLarge`] is returned if the entry's declared uncompressed size exceeds the limit, or if
+    /// decompressing its data produces more bytes than the limit (as the declared size may not be accurate). At most
+    /// one byte beyond the limit is read.
+    pub(crate) async fn read_to_end_limited(&mut self, buf: &mut Vec<u8>, limit: u64) -> Result<usize> {
+        let declared = self.entry.0.entry().uncompressed_size();
+        if declared > limit {
+            return Err(ZipError::EntryTooLarge(limit));
         }
+
+        buf.reserve(declared as usize);
+        let read = (&mut *self).take(limit.saturating_add(1)).read_to_end(buf).await?;
+        if read as u64 > limit {
+            return Err(ZipError::EntryTooLarge(limit));
+        }
+
+        self.verify().await?;
+        Ok(read)
+    }
+
+    /// Reads all bytes until EOF has been reached, returning their SHA-256 digest, and verifies the CRC32 values.
+    #[cfg(feature = "sha256")]
+    pub(crate) async fn sha256_checked(&mut self) -> Result<[u8; 32]> {
+        use sha2::Digest;
+
+        let mut hasher = sha2::Sha256::new();
+        let mut buffer = vec![0; crate::base::read::DEFAULT_BUFFER_CAPACITY];
+
+        loop {
+            match self.read(&mut buffer).await? {
+                0 => break,
+                read => hasher.update(&buffer[..read]),
+            }
+        }
+
+        self.verify().await?;
+        Ok(hasher.finalize().into())
     }
 
     /// Reads all bytes until EOF has been reached, placing them into buf, and verifies the CRC32 values.
@@ -104,17 +467,51 @@ where
     /// This is a helper function synonymous to [`AsyncReadExt::read_to_string()`].
     pub async fn read_to_string_checked(&mut self, buf: &mut String) -> Result<usize> {
         let read = self.read_to_string(buf).await?;
+        self.verify().await?;
 
-        if self.compute_hash() == self.entry.0.entry().crc32() {
-            Ok(read)
-        } else {
-            Err(ZipError::CRC32CheckError)
+        Ok(read)
+    }
+}
+
+/// Reads any encryption header at the start of an entry's data, returning the decryption required by the entry and the
+/// remaining size of its (encrypted) compressed data.
+async fn read_decryption<R>(reader: R, entry: &StoredZipEntry, password: &[u8]) -> Result<(Decryption, u64)>
+where
+    R: AsyncRead + Unpin,
+{
+    if !entry.general_purpose_flag.encrypted {
+        return Ok((Decryption::None, entry.data_size()));
+    }
+
+    if let Some(aes) = get_aes_extra_field(entry.extra_fields()) {
+        #[cfg(feature = "aes")]
+        {
+            let overhead = aes::overhead(&aes)?;
+            let keys = aes::read_header(reader, password, &aes).await?;
+            let size = entry.compressed_size().saturating_sub(overhead);
+
+            // The authentication code follows the encrypted data, so is included within the reader's size.
+            return Ok((Decryption::Aes(Box::new(keys), size), size + aes::AUTHENTICATION_CODE_LENGTH));
+        }
+
+        #[cfg(not(feature = "aes"))]
+        {
+            let _ = aes;
+            return Err(ZipError::FeatureNotSupported("AES decryption (requires the `aes` feature)"));
         }
     }
+
+    let check_byte = match entry.general_purpose_flag.data_descriptor {
+        true => (entry.last_modification_date().time >> 8) as u8,
+        false => (entry.crc32() >> 24) as u8,
+    };
+
+    let keys = zipcrypto::read_header(reader, password, check_byte).await?;
+    Ok((Decryption::ZipCrypto(keys), entry.data_size().saturating_sub(zipcrypto::HEADER_LENGTH)))
 }
 
 enum OwnedEntry<'a> {
-    Owned(ZipEntry),
+    Owned(Box<ZipEntry>),
     Borrow(&'a ZipEntry),
 }
 
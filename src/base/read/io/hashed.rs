@@ -16,6 +16,7 @@ pub(crate) struct HashedReader<R> {
     #[pin]
     pub(crate) reader: R,
     pub(crate) hasher: Hasher,
+    pub(crate) enabled: bool,
 }
 
 impl<R> HashedReader<R>
@@ -24,7 +25,7 @@ where
 {
     /// Constructs a new wrapping reader from a generic [`AsyncRead`] implementer.
     pub(crate) fn new(reader: R) -> Self {
-        Self { reader, hasher: Hasher::default() }
+        Self { reader, hasher: Hasher::default(), enabled: true }
     }
 
     /// Swaps the internal hasher and returns the computed CRC32 hash.
@@ -49,7 +50,9 @@ where
     fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
         let project = self.project();
         let written = poll_result_ok!(ready!(project.reader.poll_read(c, b)));
-        project.hasher.update(&b[..written]);
+        if *project.enabled {
+            project.hasher.update(&b[..written]);
+        }
 
         Poll::Ready(Ok(written))
     }
@@ -32,18 +32,22 @@ const BUFFER_SIZE: usize = 2048;
 const EOCDR_UPPER_BOUND: u64 = EOCDR_LENGTH as u64;
 
 /// The lower bound of where the EOCDR signature cannot be located.
-const EOCDR_LOWER_BOUND: u64 = EOCDR_UPPER_BOUND + SIGNATURE_LENGTH as u64 + u16::MAX as u64;
+///
+/// This is used as the default distance from the end of the data within which the EOCDR signature is searched for.
+pub(crate) const EOCDR_LOWER_BOUND: u64 = EOCDR_UPPER_BOUND + SIGNATURE_LENGTH as u64 + u16::MAX as u64;
 
 /// Locate the `end of central directory record` offset, if one exists.
 /// The returned offset excludes the signature (4 bytes)
 ///
+/// Only signatures starting within `max_search` bytes of the end of the data are considered.
+///
 /// This method involves buffered reading in reverse and reverse linear searching along those buffers for the EOCDR
 /// signature. As a result of this buffered approach, we reduce seeks when compared to `zip-rs`'s method by a factor
 /// of the buffer size. We also then don't have to do individual u32 reads against the upstream reader.
 ///
 /// Whilst I haven't done any in-depth benchmarks, when reading a ZIP file with the maximum length comment, this method
 /// saw a reduction in location time by a factor of 500 when compared with the `zip-rs` method.
-pub async fn eocdr<R>(mut reader: R) -> ZipResult<u64>
+pub async fn eocdr<R>(mut reader: R, max_search: u64) -> ZipResult<u64>
 where
     R: AsyncRead + AsyncSeek + Unpin,
 {
@@ -51,29 +55,59 @@ where
     let signature = &EOCDR_SIGNATURE.to_le_bytes();
     let mut buffer: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
 
-    let mut position = length.saturating_sub((EOCDR_LENGTH + BUFFER_SIZE) as u64);
+    let floor = length.saturating_sub(max_search);
+    let mut position = length.saturating_sub((EOCDR_LENGTH + BUFFER_SIZE) as u64).max(floor);
+    let mut fallback = None;
     reader.seek(SeekFrom::Start(position)).await?;
 
     loop {
         let read = reader.read(&mut buffer).await?;
+        let mut searchable = read;
 
-        if let Some(match_index) = reverse_search_buffer(&buffer[..read], signature) {
-            return Ok(position + (match_index + 1) as u64);
+        // The archive comment may itself contain the EOCDR signature, so we only accept a match whose comment length
+        // extends exactly to the end of the data. The last match found is kept as a fallback for data with trailing
+        // bytes after the comment.
+        while let Some(match_index) = reverse_search_buffer(&buffer[..searchable], signature) {
+            let offset = position + (match_index + 1) as u64;
+
+            if comment_reaches_end(&mut reader, offset, length).await? {
+                return Ok(offset);
+            }
+
+            fallback.get_or_insert(offset);
+            searchable = match_index;
         }
 
         // If we hit the start of the data or the lower bound, we're unable to locate the EOCDR.
-        if position == 0 || position <= length.saturating_sub(EOCDR_LOWER_BOUND) {
-            return Err(ZipError::UnableToLocateEOCDR);
+        if position <= floor {
+            return fallback.ok_or(ZipError::NotAZipFile);
         }
 
         // To handle the case where the EOCDR signature crosses buffer boundaries, we simply overlap reads by the
         // signature length. This significantly reduces the complexity of handling partial matches with very little
         // overhead.
-        position = position.saturating_sub((BUFFER_SIZE - SIGNATURE_LENGTH) as u64);
+        position = position.saturating_sub((BUFFER_SIZE - SIGNATURE_LENGTH) as u64).max(floor);
         reader.seek(SeekFrom::Start(position)).await?;
     }
 }
 
+/// Returns whether the comment length of a candidate EOCDR (whose offset excludes the signature) extends exactly to
+/// the end of the data.
+async fn comment_reaches_end<R>(mut reader: R, offset: u64, length: u64) -> ZipResult<bool>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    if offset + EOCDR_LENGTH as u64 > length {
+        return Ok(false);
+    }
+
+    let mut buffer = [0; 2];
+    reader.seek(SeekFrom::Start(offset + EOCDR_LENGTH as u64 - 2)).await?;
+    reader.read_exact(&mut buffer).await?;
+
+    Ok(offset + EOCDR_LENGTH as u64 + u16::from_le_bytes(buffer) as u64 == length)
+}
+
 /// A naive reverse linear search along the buffer for the specified signature bytes.
 ///
 /// This is already surprisingly performant. For instance, using memchr::memchr() to match for the first byte of the
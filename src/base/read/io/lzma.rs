@@ -0,0 +1,124 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::consts::{LZMA_HEADER_LENGTH, LZMA_PROPERTIES_LENGTH};
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_util::io::{AsyncBufRead, AsyncRead};
+use pin_project::pin_project;
+
+/// The length of the header which precedes an LZMA stream within a ZIP entry's data.
+const ZIP_HEADER_LENGTH: usize = LZMA_HEADER_LENGTH + LZMA_PROPERTIES_LENGTH;
+
+/// The length of the header which precedes an LZMA stream in the legacy `.lzma` format (and exceeds the above).
+const ALONE_HEADER_LENGTH: usize = LZMA_PROPERTIES_LENGTH + 8;
+
+/// A wrapping reader which converts the LZMA properties header of a ZIP entry's data into the legacy `.lzma` header
+/// expected by the LZMA decoder.
+///
+/// The ZIP format stores a version and properties size before the LZMA properties, but omits the uncompressed size
+/// which follows them in the legacy format (5.8.8). The uncompressed size is declared as unknown, so the stream must
+/// be terminated by an end-of-stream marker (as written by this crate, 7-Zip, and Python's `zipfile` module).
+#[pin_project]
+pub(crate) struct LzmaHeaderReader<R> {
+    #[pin]
+    reader: R,
+    header: [u8; ALONE_HEADER_LENGTH],
+    state: State,
+}
+
+enum State {
+    /// The ZIP header is being read, with the number of bytes read so far.
+    Reading(usize),
+    /// The legacy header is being provided, with the number of bytes consumed so far.
+    Providing(usize),
+    /// The compressed data is being passed through.
+    Data,
+}
+
+impl<R> LzmaHeaderReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Constructs a new wrapping reader from a generic [`AsyncBufRead`] implementer.
+    pub(crate) fn new(reader: R) -> Self {
+        Self { reader, header: [0; ALONE_HEADER_LENGTH], state: State::Reading(0) }
+    }
+
+    /// Returns a reference to the inner value.
+    pub(crate) fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Consumes this reader and returns the inner value.
+    pub(crate) fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> AsyncBufRead for LzmaHeaderReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let mut this = self.project();
+
+        while let State::Reading(read) = *this.state {
+            if read == ZIP_HEADER_LENGTH {
+                let properties_length = u16::from_le_bytes([this.header[2], this.header[3]]);
+                if properties_length as usize != LZMA_PROPERTIES_LENGTH {
+                    let message = format!("unsupported LZMA properties length: {properties_length}");
+                    return Poll::Ready(Err(Error::new(ErrorKind::InvalidData, message)));
+                }
+
+                this.header.copy_within(LZMA_HEADER_LENGTH..ZIP_HEADER_LENGTH, 0);
+                this.header[LZMA_PROPERTIES_LENGTH..ALONE_HEADER_LENGTH].copy_from_slice(&u64::MAX.to_le_bytes());
+                *this.state = State::Providing(0);
+                break;
+            }
+
+            let buffer = ready!(this.reader.as_mut().poll_fill_buf(c))?;
+            if buffer.is_empty() {
+                return Poll::Ready(Err(ErrorKind::UnexpectedEof.into()));
+            }
+
+            let length = buffer.len().min(ZIP_HEADER_LENGTH - read);
+            this.header[read..read + length].copy_from_slice(&buffer[..length]);
+            this.reader.as_mut().consume(length);
+            *this.state = State::Reading(read + length);
+        }
+
+        match *this.state {
+            State::Providing(consumed) => Poll::Ready(Ok(&this.header[consumed..ALONE_HEADER_LENGTH])),
+            _ => this.reader.poll_fill_buf(c),
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.project();
+
+        match *this.state {
+            State::Providing(consumed) if consumed + amt < ALONE_HEADER_LENGTH => {
+                *this.state = State::Providing(consumed + amt);
+            }
+            State::Providing(_) => *this.state = State::Data,
+            _ => this.reader.consume(amt),
+        }
+    }
+}
+
+impl<R> AsyncRead for LzmaHeaderReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let buffer = ready!(self.as_mut().poll_fill_buf(c))?;
+        let length = buffer.len().min(b.len());
+        b[..length].copy_from_slice(&buffer[..length]);
+        self.consume(length);
+        Poll::Ready(Ok(length))
+    }
+}
@@ -1,15 +1,22 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "aes")]
+pub(crate) mod aes;
 pub(crate) mod combined_record;
 pub(crate) mod compressed;
+pub(crate) mod decrypting;
 pub(crate) mod entry;
 pub(crate) mod hashed;
 pub(crate) mod locator;
+#[cfg(feature = "lzma")]
+pub(crate) mod lzma;
 pub(crate) mod owned;
+pub(crate) mod zipcrypto;
 
 pub use combined_record::CombinedCentralDirectoryRecord;
 
+use crate::spec::header::GeneralPurposeFlag;
 use crate::string::{StringEncoding, ZipString};
 use futures_util::io::{AsyncRead, AsyncReadExt};
 
@@ -21,6 +28,25 @@ where
     Ok(ZipString::new(read_bytes(reader, length).await?, encoding))
 }
 
+/// Read and return a dynamic length string whose encoding is determined by the language encoding flag (bit 11).
+///
+/// When the flag isn't set, the string is decoded from IBM Code Page 437 as per the specification (APPENDIX D).
+pub(crate) async fn read_flagged_string<R>(
+    reader: R,
+    length: usize,
+    flags: GeneralPurposeFlag,
+) -> std::io::Result<ZipString>
+where
+    R: AsyncRead + Unpin,
+{
+    let raw = read_bytes(reader, length).await?;
+
+    match flags.filename_unicode {
+        true => Ok(ZipString::new(raw, StringEncoding::Utf8)),
+        false => Ok(ZipString::from_cp437(raw)),
+    }
+}
+
 /// Read and return a dynamic length vector of bytes from a reader which impls AsyncRead.
 pub(crate) async fn read_bytes<R>(reader: R, length: usize) -> std::io::Result<Vec<u8>>
 where
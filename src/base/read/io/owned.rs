@@ -4,7 +4,7 @@
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use futures_util::io::{AsyncBufRead, AsyncRead, BufReader};
+use futures_util::io::{AsyncBufRead, AsyncRead, AsyncSeek, BufReader, SeekFrom};
 use pin_project::pin_project;
 
 /// A wrapping reader which holds an owned R or a mutable borrow to R.
@@ -21,10 +21,10 @@ impl<'a, R> OwnedReader<'a, R>
 where
     R: AsyncRead + Unpin,
 {
-    /// Consumes an owned reader and returns the inner value.
-    pub(crate) fn owned_into_inner(self) -> R {
+    /// Consumes an owned reader and returns the inner buffered value.
+    pub(crate) fn owned_into_inner(self) -> BufReader<R> {
         match self {
-            OwnedReader::Owned(inner) => inner.into_inner(),
+            OwnedReader::Owned(inner) => inner,
             OwnedReader::Borrow(_) => panic!("not OwnedReader::Owned value"),
         }
     }
@@ -60,3 +60,15 @@ where
         }
     }
 }
+
+impl<'a, R> AsyncSeek for OwnedReader<'a, R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    fn poll_seek(self: Pin<&mut Self>, c: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        match self.project() {
+            OwnedReaderProj::Owned(inner) => inner.poll_seek(c, pos),
+            OwnedReaderProj::Borrow(inner) => inner.poll_seek(c, pos),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Traditional PKWARE encryption (ZipCrypto).
+//!
+//! https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#61-traditional-pkware-decryption
+
+use crate::error::{Result, ZipError};
+
+use futures_util::io::{AsyncRead, AsyncReadExt};
+
+/// The length of the encryption header which precedes an entry's (encrypted) data.
+pub(crate) const HEADER_LENGTH: u64 = 12;
+
+/// The standard CRC32 lookup table, as used to update the cipher's keys.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut index = 0;
+
+    while index < 256 {
+        let mut value = index as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            value = if value & 1 == 1 { (value >> 1) ^ 0xEDB88320 } else { value >> 1 };
+            bit += 1;
+        }
+
+        table[index] = value;
+        index += 1;
+    }
+
+    table
+};
+
+fn crc32(crc: u32, byte: u8) -> u32 {
+    (crc >> 8) ^ CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize]
+}
+
+/// The three keys which make up the state of the cipher.
+#[derive(Clone)]
+pub(crate) struct ZipCryptoKeys(u32, u32, u32);
+
+impl ZipCryptoKeys {
+    /// Constructs the initial keys from the provided password.
+    pub(crate) fn new(password: &[u8]) -> Self {
+        let mut keys = Self(0x12345678, 0x23456789, 0x34567890);
+        password.iter().for_each(|&byte| keys.update(byte));
+        keys
+    }
+
+    fn update(&mut self, byte: u8) {
+        self.0 = crc32(self.0, byte);
+        self.1 = self.1.wrapping_add(self.0 & 0xFF).wrapping_mul(134775813).wrapping_add(1);
+        self.2 = crc32(self.2, (self.1 >> 24) as u8);
+    }
+
+    fn stream_byte(&self) -> u8 {
+        let temp = (self.2 | 2) as u16;
+        (temp.wrapping_mul(temp ^ 1) >> 8) as u8
+    }
+
+    /// Decrypts a single byte and advances the cipher's state.
+    pub(crate) fn decrypt(&mut self, byte: u8) -> u8 {
+        let plain = byte ^ self.stream_byte();
+        self.update(plain);
+        plain
+    }
+}
+
+/// Reads & decrypts the encryption header which precedes an entry's data, verifying the password against the header's
+/// final byte.
+///
+/// The check byte is the high-order byte of the entry's CRC32 or, if the entry was written with a data descriptor, the
+/// high-order byte of its last modification time. As this is only a single byte, a wrong password has a 1 in 256
+/// chance of passing this check, in which case the entry's CRC32 check will fail once its data has been read.
+pub(crate) async fn read_header<R>(mut reader: R, password: &[u8], check_byte: u8) -> Result<ZipCryptoKeys>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut header = [0; HEADER_LENGTH as usize];
+    reader.read_exact(&mut header).await?;
+
+    let mut keys = ZipCryptoKeys::new(password);
+    let header = header.map(|byte| keys.decrypt(byte));
+
+    match header[HEADER_LENGTH as usize - 1] == check_byte {
+        true => Ok(keys),
+        false => Err(ZipError::WrongPassword),
+    }
+}
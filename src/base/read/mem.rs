@@ -70,13 +70,16 @@
 #[cfg(doc)]
 use crate::base::read::seek;
 
-use crate::base::read::io::entry::ZipEntryReader;
+use crate::base::read::io::entry::{ExpansionLimit, ZipEntryReader};
+use crate::base::read::ReadOptions;
+use crate::entry::StoredZipEntry;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 
+use std::ops::Index;
 use std::sync::Arc;
 
-use futures_util::io::{BufReader, Cursor};
+use futures_util::io::{AsyncReadExt, AsyncWrite, BufReader, Cursor, Take};
 
 use super::io::entry::{WithEntry, WithoutEntry};
 
@@ -89,20 +92,48 @@ struct Inner {
 #[derive(Clone)]
 pub struct ZipFileReader {
     inner: Arc<Inner>,
+    expansion_limit: Option<ExpansionLimit>,
 }
 
 impl ZipFileReader {
     /// Constructs a new ZIP reader from an owned vector of bytes.
+    ///
+    /// Only the central directory is read, in a single pass. Each entry's local file header is read (and validated)
+    /// when its data is first accessed, so opening a ZIP file holding many entries doesn't require IO per entry.
     pub async fn new(data: Vec<u8>) -> Result<ZipFileReader> {
-        let file = crate::base::read::file(Cursor::new(&data)).await?;
+        ZipFileReader::new_with_options(data, ReadOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from an owned vector of bytes, locating and parsing its central directory in
+    /// accordance with the provided options.
+    pub async fn new_with_options(data: Vec<u8>, options: ReadOptions) -> Result<ZipFileReader> {
+        let file = crate::base::read::file(Cursor::new(&data), options).await?;
         Ok(ZipFileReader::from_raw_parts(data, file))
     }
 
+    /// Constructs a new ZIP reader from an owned vector of bytes, tolerating non-fatal inconsistencies within its
+    /// central directory.
+    ///
+    /// See [`seek::ZipFileReader::new_lenient()`] for more information.
+    pub async fn new_lenient(data: Vec<u8>) -> Result<ZipFileReader> {
+        ZipFileReader::new_with_options(data, ReadOptions { lenient: true, ..Default::default() }).await
+    }
+
     /// Constructs a ZIP reader from an owned vector of bytes and ZIP file information derived from those bytes.
     ///
     /// Providing a [`ZipFile`] that wasn't derived from those bytes may lead to inaccurate parsing.
     pub fn from_raw_parts(data: Vec<u8>, file: ZipFile) -> ZipFileReader {
-        ZipFileReader { inner: Arc::new(Inner { data, file }) }
+        ZipFileReader { inner: Arc::new(Inner { data, file }), expansion_limit: None }
+    }
+
+    /// Limits the ratio of decompressed bytes to compressed bytes for entry readers opened by this reader, guarding
+    /// against ZIP bombs (see [`ZipEntryReader::with_expansion_limit()`]).
+    ///
+    /// The limit is only enforced once an entry reader has produced more than `threshold` bytes. Reads which exceed it
+    /// fail with [`ZipError::SuspiciousExpansion`]. No limit is enforced by default.
+    pub fn with_expansion_limit(mut self, ratio: u64, threshold: u64) -> Self {
+        self.expansion_limit = Some(ExpansionLimit { ratio, threshold });
+        self
     }
 
     /// Returns this ZIP file's information.
@@ -110,13 +141,43 @@ impl ZipFileReader {
         &self.inner.file
     }
 
+    crate::file::zip_file_accessors!();
+
+    /// Returns the offset in bytes to where the data of an entry starts, if the provided index is valid.
+    ///
+    /// The offset is determined from the entry's local file header (rather than the central directory), which is read
+    /// on the first call and cached thereafter.
+    pub async fn data_offset(&self, index: usize) -> Result<u64> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        stored_entry.data_offset(&mut Cursor::new(&self.inner.data[..])).await
+    }
+
+    /// Returns the offsets in bytes to where the compressed data of an entry starts and ends (exclusive), if the
+    /// provided index is valid.
+    ///
+    /// The start is determined as with [`ZipFileReader::data_offset()`], and the end from the compressed size recorded
+    /// within the central directory.
+    pub async fn compressed_data_range(&self, index: usize) -> Result<(u64, u64)> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        stored_entry.compressed_data_range(&mut Cursor::new(&self.inner.data[..])).await
+    }
+
+    /// Returns the offsets in bytes to where the data descriptor following an entry's data starts and ends (exclusive),
+    /// if the provided index is valid and the entry was written with one.
+    ///
+    /// The end of the descriptor is where the next local file header (or the central directory) is expected to start.
+    pub async fn data_descriptor_range(&self, index: usize) -> Result<Option<(u64, u64)>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        stored_entry.data_descriptor_range(&mut Cursor::new(&self.inner.data[..])).await
+    }
+
     /// Returns the raw bytes provided to the reader during construction.
     pub fn data(&self) -> &[u8] {
         &self.inner.data
     }
 
     /// Returns a new entry reader if the provided index is valid.
-    pub async fn reader_without_entry(&self, index: usize) -> Result<ZipEntryReader<Cursor<&[u8]>, WithoutEntry>> {
+    pub async fn reader_without_entry(&self, index: usize) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithoutEntry>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         let mut cursor = BufReader::new(Cursor::new(&self.inner.data[..]));
 
@@ -125,12 +186,15 @@ impl ZipFileReader {
         Ok(ZipEntryReader::new_with_owned(
             cursor,
             stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+            stored_entry.data_size(),
+            stored_entry.is_empty(),
+        )
+        .with_uncompressed_size(stored_entry.entry.uncompressed_size())
+        .with_expansion_limit_opt(self.expansion_limit))
     }
 
     /// Returns a new entry reader if the provided index is valid.
-    pub async fn reader_with_entry(&self, index: usize) -> Result<ZipEntryReader<Cursor<&[u8]>, WithEntry<'_>>> {
+    pub async fn reader_with_entry(&self, index: usize) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithEntry<'_>>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         let mut cursor = BufReader::new(Cursor::new(&self.inner.data[..]));
 
@@ -139,9 +203,159 @@ impl ZipFileReader {
         let reader = ZipEntryReader::new_with_owned(
             cursor,
             stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        );
+            stored_entry.data_size(),
+            stored_entry.is_empty(),
+        )
+        .with_uncompressed_size(stored_entry.entry.uncompressed_size())
+        .with_expansion_limit_opt(self.expansion_limit);
+
+        Ok(reader.into_with_entry(stored_entry))
+    }
+
+    /// Returns a new entry reader for the provided entry, if it belongs to this ZIP file.
+    ///
+    /// This allows an entry found via [`ZipFile::entry()`] to be read without threading its index back through. See
+    /// [`ZipFile::index_of()`] for how the entry is matched; [`ZipError::ForeignEntry`] is returned if it doesn't
+    /// belong to this ZIP file.
+    pub async fn reader_for(&self, entry: &StoredZipEntry) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithEntry<'_>>> {
+        let index = self.file().index_of(entry).ok_or(ZipError::ForeignEntry)?;
+        self.reader_with_entry(index).await
+    }
 
+    /// Returns a new entry reader which decrypts the entry's data with the provided password, if the provided index is
+    /// valid.
+    ///
+    /// If the entry isn't encrypted, the password is ignored. [`ZipError::WrongPassword`] is returned if the password
+    /// fails the check against the entry's encryption header.
+    pub async fn reader_with_password(
+        &self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithEntry<'_>>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut cursor = BufReader::new(Cursor::new(&self.inner.data[..]));
+
+        stored_entry.seek_to_data_offset(&mut cursor).await?;
+
+        let reader = ZipEntryReader::new_with_owned_password(cursor, stored_entry, password)
+            .await?
+            .with_expansion_limit_opt(self.expansion_limit);
         Ok(reader.into_with_entry(stored_entry))
     }
+
+    /// Returns a reader over the raw data of an entry (ie. still compressed and, if applicable, encrypted), if the
+    /// provided index is valid.
+    ///
+    /// The reader yields exactly the entry's compressed size in bytes. Alongside the entry's compression method, CRC32
+    /// value, and sizes, this allows an entry to be copied into another ZIP file without decompressing it.
+    pub async fn raw_reader(&self, index: usize) -> Result<Take<BufReader<Cursor<&[u8]>>>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut reader = BufReader::new(Cursor::new(&self.inner.data[..]));
+
+        stored_entry.seek_to_data_offset(&mut reader).await?;
+
+        Ok(reader.take(stored_entry.compressed_size()))
+    }
+
+    /// Reads the entire decompressed contents of an entry, if the provided index is valid.
+    ///
+    /// The entry's CRC32 value is verified once all of its data has been read.
+    pub async fn read_entry(&self, index: usize) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.reader_with_entry(index).await?.read_to_end_checked(&mut data).await?;
+        Ok(data)
+    }
+
+    /// Computes the SHA-256 digest of the decompressed contents of an entry, if the provided index is valid.
+    ///
+    /// See [`seek::ZipFileReader::entry_sha256()`] for more information.
+    #[cfg(feature = "sha256")]
+    pub async fn entry_sha256(&self, index: usize) -> Result<[u8; 32]> {
+        self.reader_with_entry(index).await?.sha256_checked().await
+    }
+
+    /// Reads the entire decompressed contents of an entry, if the provided index is valid and it doesn't exceed the
+    /// provided limit (in bytes).
+    ///
+    /// This guards against entries whose data decompresses to far more than is expected (eg. ZIP bombs), as
+    /// decompression stops once the limit has been exceeded, returning [`ZipError::EntryTooLarge`]. The same error is
+    /// returned upfront if the entry's declared uncompressed size exceeds the limit.
+    pub async fn read_entry_limited(&self, index: usize, limit: u64) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.reader_with_entry(index).await?.read_to_end_limited(&mut data, limit).await?;
+        Ok(data)
+    }
+
+    /// Reads up to the first `length` decompressed bytes of an entry, if the provided index is valid.
+    ///
+    /// Fewer bytes are returned if the entry holds less data. As the entry isn't read in full, its CRC32 value isn't
+    /// verified.
+    pub async fn read_entry_prefix(&self, index: usize, length: usize) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.reader_without_entry(index).await?.take(length as u64).read_to_end(&mut data).await?;
+        Ok(data)
+    }
+
+    /// Copies the entire decompressed contents of an entry into the provided writer, if the provided index is valid.
+    ///
+    /// The number of bytes written is returned, and the entry's CRC32 value is verified once all of its data has been
+    /// read.
+    pub async fn copy_entry_to<W: AsyncWrite + Unpin>(&self, index: usize, writer: W) -> Result<u64> {
+        self.reader_with_entry(index).await?.copy_to_checked(writer).await
+    }
+
+    /// Reads the entire decompressed contents of the first entry with the provided filename, if one exists.
+    ///
+    /// See [`ZipFile::entry()`] for how filenames are matched.
+    pub async fn read_entry_by_name(&self, filename: impl AsRef<[u8]>) -> Result<Vec<u8>> {
+        let (index, _) = self.file().entry(filename).ok_or(ZipError::EntryNotFound)?;
+        self.read_entry(index).await
+    }
+
+    /// Reads every entry to completion (without buffering their data), verifying each entry's CRC32 value.
+    ///
+    /// The first failure is returned as [`ZipError::EntryValidationFailed`], which names the failing entry. Encrypted
+    /// entries can't be validated without a password (see [`ZipFileReader::validate_with_password()`]).
+    pub async fn validate(&self) -> Result<()> {
+        self.validate_entries(None).await
+    }
+
+    /// Reads every entry to completion (without buffering their data), decrypting any encrypted entries with the
+    /// provided password, and verifying each entry's CRC32 value (or authentication code for AE-2 entries).
+    ///
+    /// The first failure is returned as [`ZipError::EntryValidationFailed`], which names the failing entry.
+    pub async fn validate_with_password(&self, password: &[u8]) -> Result<()> {
+        self.validate_entries(Some(password)).await
+    }
+
+    async fn validate_entries(&self, password: Option<&[u8]>) -> Result<()> {
+        for index in 0..self.file().entries().len() {
+            let reader = match (password, self.file().entries()[index].is_encrypted()) {
+                (Some(password), _) => self.reader_with_password(index, password).await,
+                (None, true) => Err(ZipError::FeatureNotSupported("validating encrypted entries without a password")),
+                (None, false) => self.reader_with_entry(index).await,
+            };
+
+            let result = match reader {
+                Ok(mut reader) => reader.consume_checked().await,
+                Err(err) => Err(err),
+            };
+
+            result.map_err(|err| crate::base::read::validation_error(self.file(), index, err))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Index<usize> for ZipFileReader {
+    type Output = StoredZipEntry;
+
+    /// Returns the entry at the provided index.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds. See [`ZipFileReader::get()`] for a non-panicking alternative.
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.inner.file.entries[index]
+    }
 }
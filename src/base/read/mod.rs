@@ -17,31 +17,85 @@ pub use crate::base::read::io::entry::ZipEntryReader;
 use crate::date::ZipDateTime;
 use crate::entry::{StoredZipEntry, ZipEntry};
 use crate::error::{Result, ZipError};
-use crate::file::ZipFile;
+use crate::file::{ZipFile, ZipFileMetadata};
 use crate::spec::attribute::AttributeCompatibility;
-use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH};
+use crate::spec::consts::{
+    CDH_SIGNATURE, LFH_SIGNATURE, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH, ZIP64_EOCDR_SIGNATURE,
+};
 use crate::spec::header::{
-    CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, LocalFileHeader,
+    AesExtraField, CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, HeaderId, LocalFileHeader,
     Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord, Zip64ExtendedInformationExtraField,
 };
 use crate::spec::Compression;
-use crate::string::StringEncoding;
 
 use crate::base::read::io::CombinedCentralDirectoryRecord;
 use crate::spec::parse::parse_extra_fields;
 
+use std::sync::OnceLock;
+
 use futures_util::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, BufReader, SeekFrom};
 
+/// The default capacity of the buffer used by entry readers, equal to 8KiB.
+pub(crate) const DEFAULT_BUFFER_CAPACITY: usize = 8 * 1024;
+
 /// The max buffer size used when parsing the central directory, equal to 20MiB.
 const MAX_CD_BUFFER_SIZE: usize = 20 * 1024 * 1024;
 
-pub(crate) async fn file<R>(mut reader: R) -> Result<ZipFile>
+/// Options which control how a ZIP file's central directory is located and parsed.
+///
+/// # Example
+/// ```no_run
+/// # use async_zip::base::read::ReadOptions;
+/// #
+/// // Search the last 1 MiB of the data for the end of central directory record.
+/// let options = ReadOptions { max_eocd_search: 1024 * 1024, ..Default::default() };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// The distance in bytes from the end of the data within which the end of central directory record is searched
+    /// for (defaults to its maximum length, 64 KiB + 21 bytes).
+    ///
+    /// Raising this allows ZIP files with trailing garbage (ie. data following the record's comment) to be opened,
+    /// whilst lowering it bounds the IO spent on data which isn't a ZIP file.
+    pub max_eocd_search: u64,
+    /// Whether non-fatal inconsistencies within the central directory are tolerated (defaults to false).
+    ///
+    /// See [`seek::ZipFileReader::new_lenient()`] for more information.
+    pub lenient: bool,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        ReadOptions { max_eocd_search: crate::base::read::io::locator::EOCDR_LOWER_BOUND, lenient: false }
+    }
+}
+
+/// Parses a ZIP file's central directory in accordance with the provided options.
+pub(crate) async fn file<R>(mut reader: R, options: ReadOptions) -> Result<ZipFile>
 where
     R: AsyncRead + AsyncSeek + Unpin,
 {
-    // First find and parse the EOCDR.
-    let eocdr_offset = crate::base::read::io::locator::eocdr(&mut reader).await?;
+    // First find the EOCDR. If there isn't one but the data starts with a local file header, it's likely a ZIP file
+    // which has been truncated rather than not being a ZIP file at all.
+    let eocdr_offset = match crate::base::read::io::locator::eocdr(&mut reader, options.max_eocd_search).await {
+        Err(ZipError::NotAZipFile) if signature_at(&mut reader, 0, LFH_SIGNATURE).await? => {
+            return Err(ZipError::UnexpectedEof)
+        }
+        result => result?,
+    };
+
+    // Any records which are declared but end prematurely are also indicative of truncation.
+    directory(reader, eocdr_offset, options.lenient).await.map_err(|err| match err {
+        ZipError::UpstreamReadError(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => ZipError::UnexpectedEof,
+        err => err,
+    })
+}
 
+/// Parses the EOCDR at the given offset (which excludes its signature), followed by the central directory it refers to.
+async fn directory<R>(mut reader: R, eocdr_offset: u64, lenient: bool) -> Result<ZipFile>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
     reader.seek(SeekFrom::Start(eocdr_offset)).await?;
     let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut reader).await?;
 
@@ -49,43 +103,115 @@ where
 
     // Check the 20 bytes before the EOCDR for the Zip64 EOCDL, plus an extra 4 bytes because the offset
     // does not include the signature. If the ECODL exists we are dealing with a Zip64 file.
-    let (eocdr, zip64) = match eocdr_offset.checked_sub(ZIP64_EOCDL_LENGTH + SIGNATURE_LENGTH as u64) {
-        None => (CombinedCentralDirectoryRecord::from(&eocdr), false),
+    //
+    // We also track where the central directory ends (ie. where the record following it begins) so that any data
+    // prepended to the ZIP file (eg. a self-extracting executable stub) can be accounted for.
+    let eocdr_signature_offset = eocdr_offset - SIGNATURE_LENGTH as u64;
+    let (eocdr, zip64, cd_end) = match eocdr_offset.checked_sub(ZIP64_EOCDL_LENGTH + SIGNATURE_LENGTH as u64) {
+        None => (CombinedCentralDirectoryRecord::from(&eocdr), false, eocdr_signature_offset),
         Some(offset) => {
             reader.seek(SeekFrom::Start(offset)).await?;
             let zip64_locator = Zip64EndOfCentralDirectoryLocator::try_from_reader(&mut reader).await?;
 
             match zip64_locator {
+                Some(locator) if locator.total_number_of_disks > 1 => return Err(ZipError::SpannedArchiveUnsupported),
                 Some(locator) => {
-                    reader.seek(SeekFrom::Start(locator.relative_offset + SIGNATURE_LENGTH as u64)).await?;
+                    let zip64_eocdr_offset = zip64_eocdr(&mut reader, locator.relative_offset, offset).await?;
+                    reader.seek(SeekFrom::Start(zip64_eocdr_offset + SIGNATURE_LENGTH as u64)).await?;
                     let zip64_eocdr = Zip64EndOfCentralDirectoryRecord::from_reader(&mut reader).await?;
-                    (CombinedCentralDirectoryRecord::combine(eocdr, zip64_eocdr), true)
+                    (CombinedCentralDirectoryRecord::combine(eocdr, zip64_eocdr), true, zip64_eocdr_offset)
                 }
-                None => (CombinedCentralDirectoryRecord::from(&eocdr), false),
+                None => (CombinedCentralDirectoryRecord::from(&eocdr), false, eocdr_signature_offset),
             }
         }
     };
 
-    // Outdated feature so unlikely to ever make it into this crate.
-    if eocdr.disk_number != eocdr.disk_number_start_of_cd
-        || eocdr.num_entries_in_directory != eocdr.num_entries_in_directory_on_disk
+    // Outdated feature so unlikely to ever make it into this crate. The EOCDR is always stored on the last disk, so any
+    // non-zero disk number indicates that the central directory (and entries' data) may span multiple disks. Some
+    // writers record differing entry counts regardless, so that alone is tolerated when lenient.
+    if eocdr.disk_number != 0
+        || eocdr.disk_number_start_of_cd != 0
+        || (eocdr.num_entries_in_directory != eocdr.num_entries_in_directory_on_disk && !lenient)
+    {
+        return Err(ZipError::SpannedArchiveUnsupported);
+    }
+
+    // Offsets within the ZIP file are relative to its start, which only differs from the start of the reader when
+    // data has been prepended. In that case, the central directory is found at a greater offset than was recorded.
+    // Only the lack of a central directory at the recorded offset is taken as evidence of that.
+    let mut prepended = cd_end
+        .checked_sub(eocdr.directory_size)
+        .and_then(|cd_start| cd_start.checked_sub(eocdr.offset_of_start_of_directory))
+        .unwrap_or(0);
+
+    if prepended != 0
+        && (signature_at(&mut reader, eocdr.offset_of_start_of_directory, CDH_SIGNATURE).await?
+            || !signature_at(&mut reader, eocdr.offset_of_start_of_directory + prepended, CDH_SIGNATURE).await?)
     {
-        return Err(ZipError::FeatureNotSupported("Spanned/split files"));
+        prepended = 0;
     }
 
     // Find and parse the central directory.
-    reader.seek(SeekFrom::Start(eocdr.offset_of_start_of_directory)).await?;
+    reader.seek(SeekFrom::Start(eocdr.offset_of_start_of_directory + prepended)).await?;
 
     // To avoid lots of small reads to `reader` when parsing the central directory, we use a BufReader that can read the whole central directory at once.
     // Because `eocdr.offset_of_start_of_directory` is a u64, we use MAX_CD_BUFFER_SIZE to prevent very large buffer sizes.
     let buf =
         BufReader::with_capacity(std::cmp::min(eocdr.offset_of_start_of_directory as _, MAX_CD_BUFFER_SIZE), reader);
-    let entries = crate::base::read::cd(buf, eocdr.num_entries_in_directory, zip64).await?;
+    let mut entries = crate::base::read::cd(buf, eocdr.num_entries_in_directory, zip64, lenient).await?;
+    entries.iter_mut().for_each(|entry| entry.file_offset += prepended);
+
+    let mut file = ZipFile::new(entries, zip64, comment);
+    file.metadata = ZipFileMetadata {
+        disk_number: eocdr.disk_number,
+        entries_on_disk: eocdr.num_entries_in_directory_on_disk,
+        total_entries: eocdr.num_entries_in_directory,
+        directory_size: eocdr.directory_size,
+        directory_offset: eocdr.offset_of_start_of_directory,
+    };
+    file.directory_start = Some(eocdr.offset_of_start_of_directory + prepended);
+
+    Ok(file)
+}
+
+/// The length of a zip64 EOCDR without any extensible data (incl. its signature).
+const ZIP64_EOCDR_LENGTH: u64 = 56;
+
+/// Returns the offset of the zip64 EOCDR's signature.
+///
+/// The recorded offset is checked first, falling back to the offset immediately preceding the zip64 EOCDL (which
+/// differs from the recorded offset when data has been prepended to the ZIP file).
+async fn zip64_eocdr<R>(mut reader: R, recorded_offset: u64, locator_offset: u64) -> Result<u64>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    if let Some(preceding_offset) = locator_offset.checked_sub(ZIP64_EOCDR_LENGTH) {
+        if !signature_at(&mut reader, recorded_offset, ZIP64_EOCDR_SIGNATURE).await?
+            && signature_at(&mut reader, preceding_offset, ZIP64_EOCDR_SIGNATURE).await?
+        {
+            return Ok(preceding_offset);
+        }
+    }
+
+    Ok(recorded_offset)
+}
+
+/// Returns whether or not the given signature is stored at the given offset.
+async fn signature_at<R>(mut reader: R, offset: u64, signature: u32) -> Result<bool>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let mut buffer = [0; SIGNATURE_LENGTH];
+    reader.seek(SeekFrom::Start(offset)).await?;
 
-    Ok(ZipFile { entries, comment, zip64 })
+    match reader.read_exact(&mut buffer).await {
+        Ok(()) => Ok(u32::from_le_bytes(buffer) == signature),
+        Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(err) => Err(err.into()),
+    }
 }
 
-pub(crate) async fn cd<R>(mut reader: R, num_of_entries: u64, zip64: bool) -> Result<Vec<StoredZipEntry>>
+pub(crate) async fn cd<R>(mut reader: R, num_of_entries: u64, zip64: bool, lenient: bool) -> Result<Vec<StoredZipEntry>>
 where
     R: AsyncRead + Unpin,
 {
@@ -93,13 +219,19 @@ where
     let mut entries = Vec::with_capacity(num_of_entries);
 
     for _ in 0..num_of_entries {
-        let entry = cd_record(&mut reader, zip64).await?;
+        let entry = cd_record(&mut reader, zip64, lenient).await?;
         entries.push(entry);
     }
 
     Ok(entries)
 }
 
+/// Wraps an error encountered whilst validating an entry with the entry's index and filename.
+pub(crate) fn validation_error(file: &ZipFile, index: usize, source: ZipError) -> ZipError {
+    let filename = String::from_utf8_lossy(file.entries()[index].filename_bytes()).into_owned();
+    ZipError::EntryValidationFailed { index, filename, source: Box::new(source) }
+}
+
 pub(crate) fn get_zip64_extra_field(extra_fields: &[ExtraField]) -> Option<&Zip64ExtendedInformationExtraField> {
     for field in extra_fields {
         if let ExtraField::Zip64ExtendedInformationExtraField(zip64field) = field {
@@ -109,6 +241,35 @@ pub(crate) fn get_zip64_extra_field(extra_fields: &[ExtraField]) -> Option<&Zip6
     None
 }
 
+/// The header ID of the WinZip AES encryption extra field.
+const AES_HEADER_ID: u16 = 0x9901;
+/// The compression method stored in the headers of WinZip AES-encrypted entries.
+const AES_COMPRESSION_METHOD: u16 = 99;
+
+/// Returns the data of the WinZip AES encryption extra field, if present and well-formed.
+pub(crate) fn get_aes_extra_field(extra_fields: &[ExtraField]) -> Option<AesExtraField> {
+    extra_fields.iter().find_map(|field| match field {
+        ExtraField::UnknownExtraField(field) if matches!(field.header_id, HeaderId::Other(AES_HEADER_ID)) => {
+            let content = field.content.get(..7)?;
+
+            Some(AesExtraField {
+                version: u16::from_le_bytes([content[0], content[1]]),
+                strength: content[4],
+                compression: u16::from_le_bytes([content[5], content[6]]),
+            })
+        }
+        _ => None,
+    })
+}
+
+/// Returns the compression method of an entry's data, which is stored in the AES extra field for AES-encrypted entries.
+fn get_compression(compression: u16, extra_fields: &[ExtraField]) -> Result<Compression> {
+    match (compression, get_aes_extra_field(extra_fields)) {
+        (AES_COMPRESSION_METHOD, Some(aes)) => Compression::try_from(aes.compression),
+        (compression, _) => Compression::try_from(compression),
+    }
+}
+
 pub(crate) fn get_zip64_extra_field_mut(
     extra_fields: &mut [ExtraField],
 ) -> Option<&mut Zip64ExtendedInformationExtraField> {
@@ -130,28 +291,28 @@ fn get_combined_sizes(
 
     if let Some(extra_field) = extra_field {
         if uncompressed_size == NON_ZIP64_MAX_SIZE as u64 {
-            uncompressed_size = extra_field.uncompressed_size.ok_or_else(|| ZipError::Zip64ExtendedFieldIncomplete)?
+            uncompressed_size = extra_field.uncompressed_size.ok_or(ZipError::Zip64ExtendedFieldIncomplete)?
         }
         if compressed_size == NON_ZIP64_MAX_SIZE as u64 {
-            compressed_size = extra_field.compressed_size.ok_or_else(|| ZipError::Zip64ExtendedFieldIncomplete)?;
+            compressed_size = extra_field.compressed_size.ok_or(ZipError::Zip64ExtendedFieldIncomplete)?;
         }
     }
 
     Ok((uncompressed_size, compressed_size))
 }
 
-pub(crate) async fn cd_record<R>(mut reader: R, _zip64: bool) -> Result<StoredZipEntry>
+pub(crate) async fn cd_record<R>(mut reader: R, _zip64: bool, lenient: bool) -> Result<StoredZipEntry>
 where
     R: AsyncRead + Unpin,
 {
     crate::utils::assert_signature(&mut reader, CDH_SIGNATURE).await?;
 
     let header = CentralDirectoryRecord::from_reader(&mut reader).await?;
-    let filename = io::read_string(&mut reader, header.file_name_length.into(), StringEncoding::Utf8).await?;
-    let compression = Compression::try_from(header.compression)?;
+    let filename = io::read_flagged_string(&mut reader, header.file_name_length.into(), header.flags).await?;
     let extra_field = io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
-    let extra_fields = parse_extra_fields(extra_field)?;
-    let comment = io::read_string(reader, header.file_comment_length.into(), StringEncoding::Utf8).await?;
+    let extra_fields = parse_extra_fields(&extra_field, (&header).into(), lenient)?;
+    let compression = get_compression(header.compression, &extra_fields)?;
+    let comment = io::read_flagged_string(reader, header.file_comment_length.into(), header.flags).await?;
 
     let zip64_extra_field = get_zip64_extra_field(&extra_fields);
     let (uncompressed_size, compressed_size) =
@@ -160,9 +321,7 @@ where
     let mut file_offset = header.lh_offset as u64;
     if let Some(zip64_extra_field) = zip64_extra_field {
         if file_offset == NON_ZIP64_MAX_SIZE as u64 {
-            if let Some(offset) = zip64_extra_field.relative_header_offset {
-                file_offset = offset;
-            }
+            file_offset = zip64_extra_field.relative_header_offset.ok_or(ZipError::Zip64ExtendedFieldIncomplete)?;
         }
     }
 
@@ -178,8 +337,11 @@ where
             feature = "deflate64"
         ))]
         compression_level: async_compression::Level::Default,
-        attribute_compatibility: AttributeCompatibility::Unix,
-        /// FIXME: Default to Unix for the moment
+        auto_stored: false,
+        attribute_compatibility: AttributeCompatibility::try_from(header.v_made_by >> 8)?,
+        version_needed: Some(header.v_needed),
+        general_purpose_flag: header.flags,
+        utf8_name: None,
         crc32: header.crc,
         uncompressed_size,
         compressed_size,
@@ -187,11 +349,11 @@ where
         internal_file_attribute: header.inter_attr,
         external_file_attribute: header.exter_attr,
         extra_fields,
+        extra_field_raw: extra_field,
         comment,
     };
 
-    // general_purpose_flag: header.flags,
-    Ok(StoredZipEntry { entry, file_offset })
+    Ok(StoredZipEntry { entry, file_offset, data_offset: OnceLock::new() })
 }
 
 pub(crate) async fn lfh<R>(mut reader: R) -> Result<Option<ZipEntry>>
@@ -210,22 +372,18 @@ where
     };
 
     let header = LocalFileHeader::from_reader(&mut reader).await?;
-    let filename = io::read_string(&mut reader, header.file_name_length.into(), StringEncoding::Utf8).await?;
-    let compression = Compression::try_from(header.compression)?;
+    let filename = io::read_flagged_string(&mut reader, header.file_name_length.into(), header.flags).await?;
     let extra_field = io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
-    let extra_fields = parse_extra_fields(extra_field)?;
+    let extra_fields = parse_extra_fields(&extra_field, (&header).into(), false)?;
+    let compression = get_compression(header.compression, &extra_fields)?;
 
     let zip64_extra_field = get_zip64_extra_field(&extra_fields);
     let (uncompressed_size, compressed_size) =
         get_combined_sizes(header.uncompressed_size, header.compressed_size, &zip64_extra_field)?;
 
-    if header.flags.data_descriptor {
-        return Err(ZipError::FeatureNotSupported(
-            "stream reading entries with data descriptors (planned to be reintroduced)",
-        ));
-    }
-    if header.flags.encrypted {
-        return Err(ZipError::FeatureNotSupported("encryption"));
+    // Encrypted entries are instead checked by the stream reader, as their sizes may be stored alongside the descriptor.
+    if header.flags.data_descriptor && compression == Compression::Stored && !header.flags.encrypted {
+        return Err(ZipError::FeatureNotSupported("stream reading Stored entries with data descriptors"));
     }
 
     let entry = ZipEntry {
@@ -240,8 +398,12 @@ where
             feature = "deflate64"
         ))]
         compression_level: async_compression::Level::Default,
+        auto_stored: false,
+        // The local file header doesn't store the host system, so default to Unix.
         attribute_compatibility: AttributeCompatibility::Unix,
-        /// FIXME: Default to Unix for the moment
+        version_needed: Some(header.version),
+        general_purpose_flag: header.flags,
+        utf8_name: None,
         crc32: header.crc,
         uncompressed_size,
         compressed_size,
@@ -249,6 +411,7 @@ where
         internal_file_attribute: 0,
         external_file_attribute: 0,
         extra_fields,
+        extra_field_raw: extra_field,
         comment: String::new().into(),
     };
 
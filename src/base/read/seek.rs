@@ -25,14 +25,18 @@
 //! }
 //! ```
 
-use crate::base::read::io::entry::ZipEntryReader;
+use crate::base::read::io::entry::{ExpansionLimit, ZipEntryReader};
+use crate::base::read::{ReadOptions, DEFAULT_BUFFER_CAPACITY};
+use crate::entry::StoredZipEntry;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 
 #[cfg(feature = "tokio")]
 use crate::tokio::read::seek::ZipFileReader as TokioZipFileReader;
 
-use futures_util::io::{AsyncRead, AsyncSeek, BufReader};
+use std::ops::Index;
+
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncWrite, BufReader, Take};
 
 #[cfg(feature = "tokio")]
 use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
@@ -44,6 +48,9 @@ use super::io::entry::{WithEntry, WithoutEntry};
 pub struct ZipFileReader<R> {
     reader: R,
     file: ZipFile,
+    buffer_capacity: usize,
+    expansion_limit: Option<ExpansionLimit>,
+    options: ReadOptions,
 }
 
 impl<R> ZipFileReader<R>
@@ -51,16 +58,68 @@ where
     R: AsyncRead + AsyncSeek + Unpin,
 {
     /// Constructs a new ZIP reader from a seekable source.
-    pub async fn new(mut reader: R) -> Result<ZipFileReader<R>> {
-        let file = crate::base::read::file(&mut reader).await?;
-        Ok(ZipFileReader::from_raw_parts(reader, file))
+    ///
+    /// Only the central directory is read, in a single pass. Each entry's local file header is read (and validated)
+    /// when its data is first accessed, so opening a ZIP file holding many entries doesn't require IO per entry.
+    pub async fn new(reader: R) -> Result<ZipFileReader<R>> {
+        ZipFileReader::new_with_options(reader, ReadOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a seekable source, locating and parsing its central directory in accordance
+    /// with the provided options.
+    ///
+    /// The options are also used by any later [`ZipFileReader::refresh()`].
+    pub async fn new_with_options(mut reader: R, options: ReadOptions) -> Result<ZipFileReader<R>> {
+        let file = crate::base::read::file(&mut reader, options).await?;
+        let mut reader = ZipFileReader::from_raw_parts(reader, file);
+        reader.options = options;
+        Ok(reader)
+    }
+
+    /// Constructs a new ZIP reader from a seekable source, tolerating non-fatal inconsistencies within its central
+    /// directory.
+    ///
+    /// Archives produced by some writers don't strictly follow the specification, despite their entries being readable.
+    /// In lenient mode:
+    /// - trailing extra field data which is too short to hold its declared field is ignored.
+    /// - extra fields which fail to parse are kept as unknown extra fields.
+    /// - differing total and on-disk entry counts within the end of central directory record are ignored.
+    ///
+    /// Structural impossibilities (eg. a missing or truncated central directory, or an archive spanning multiple disks)
+    /// still fail as they do with [`ZipFileReader::new()`]. Any later [`ZipFileReader::refresh()`] is also lenient.
+    pub async fn new_lenient(reader: R) -> Result<ZipFileReader<R>> {
+        ZipFileReader::new_with_options(reader, ReadOptions { lenient: true, ..Default::default() }).await
     }
 
     /// Constructs a ZIP reader from a seekable source and ZIP file information derived from that source.
     ///
     /// Providing a [`ZipFile`] that wasn't derived from that source may lead to inaccurate parsing.
     pub fn from_raw_parts(reader: R, file: ZipFile) -> ZipFileReader<R> {
-        ZipFileReader { reader, file }
+        ZipFileReader {
+            reader,
+            file,
+            buffer_capacity: DEFAULT_BUFFER_CAPACITY,
+            expansion_limit: None,
+            options: ReadOptions::default(),
+        }
+    }
+
+    /// Sets the capacity of the buffer which entry readers use when reading from the source (defaults to 8 KiB).
+    ///
+    /// Small sequential reads from an entry reader are batched into reads of up to this size from the source.
+    pub fn with_buffer_capacity(mut self, capacity: usize) -> Self {
+        self.buffer_capacity = capacity;
+        self
+    }
+
+    /// Limits the ratio of decompressed bytes to compressed bytes for entry readers opened by this reader, guarding
+    /// against ZIP bombs (see [`ZipEntryReader::with_expansion_limit()`]).
+    ///
+    /// The limit is only enforced once an entry reader has produced more than `threshold` bytes. Reads which exceed it
+    /// fail with [`ZipError::SuspiciousExpansion`]. No limit is enforced by default.
+    pub fn with_expansion_limit(mut self, ratio: u64, threshold: u64) -> Self {
+        self.expansion_limit = Some(ExpansionLimit { ratio, threshold });
+        self
     }
 
     /// Returns this ZIP file's information.
@@ -68,6 +127,46 @@ where
         &self.file
     }
 
+    /// Re-reads the central directory from the source, replacing this ZIP file's information.
+    ///
+    /// This allows entries appended to a growing source to be picked up without reconstructing the reader. If the
+    /// central directory can't be read (eg. the source was truncated or is mid-write), an error is returned and the
+    /// existing information is left intact.
+    pub async fn refresh(&mut self) -> Result<()> {
+        self.file = crate::base::read::file(&mut self.reader, self.options).await?;
+        Ok(())
+    }
+
+    crate::file::zip_file_accessors!();
+
+    /// Returns the offset in bytes to where the data of an entry starts, if the provided index is valid.
+    ///
+    /// The offset is determined from the entry's local file header (rather than the central directory), which is read
+    /// on the first call and cached thereafter.
+    pub async fn data_offset(&mut self, index: usize) -> Result<u64> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        stored_entry.data_offset(&mut self.reader).await
+    }
+
+    /// Returns the offsets in bytes to where the compressed data of an entry starts and ends (exclusive), if the
+    /// provided index is valid.
+    ///
+    /// The start is determined as with [`ZipFileReader::data_offset()`], and the end from the compressed size recorded
+    /// within the central directory.
+    pub async fn compressed_data_range(&mut self, index: usize) -> Result<(u64, u64)> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        stored_entry.compressed_data_range(&mut self.reader).await
+    }
+
+    /// Returns the offsets in bytes to where the data descriptor following an entry's data starts and ends (exclusive),
+    /// if the provided index is valid and the entry was written with one.
+    ///
+    /// The end of the descriptor is where the next local file header (or the central directory) is expected to start.
+    pub async fn data_descriptor_range(&mut self, index: usize) -> Result<Option<(u64, u64)>> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        stored_entry.data_descriptor_range(&mut self.reader).await
+    }
+
     /// Returns a mutable reference to the inner seekable source.
     ///
     /// Swapping the source (eg. via std::mem operations) may lead to inaccurate parsing.
@@ -83,33 +182,178 @@ where
     /// Returns a new entry reader if the provided index is valid.
     pub async fn reader_without_entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R, WithoutEntry>> {
         let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let mut reader = BufReader::new(&mut self.reader);
+        let mut reader = BufReader::with_capacity(self.buffer_capacity, &mut self.reader);
 
         stored_entry.seek_to_data_offset(&mut reader).await?;
 
         Ok(ZipEntryReader::new_with_borrow(
             reader,
             stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+            stored_entry.data_size(),
+            stored_entry.is_empty(),
+        )
+        .with_uncompressed_size(stored_entry.entry.uncompressed_size())
+        .with_expansion_limit_opt(self.expansion_limit))
     }
 
     /// Returns a new entry reader if the provided index is valid.
     pub async fn reader_with_entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R, WithEntry<'_>>> {
         let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let mut reader = BufReader::new(&mut self.reader);
+        let mut reader = BufReader::with_capacity(self.buffer_capacity, &mut self.reader);
 
         stored_entry.seek_to_data_offset(&mut reader).await?;
 
         let reader = ZipEntryReader::new_with_borrow(
             reader,
             stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        );
+            stored_entry.data_size(),
+            stored_entry.is_empty(),
+        )
+        .with_uncompressed_size(stored_entry.entry.uncompressed_size())
+        .with_expansion_limit_opt(self.expansion_limit);
+
+        Ok(reader.into_with_entry(stored_entry))
+    }
+
+    /// Returns a new entry reader for the provided entry, if it belongs to this ZIP file.
+    ///
+    /// This allows an entry found via [`ZipFile::entry()`] to be read without threading its index back through. See
+    /// [`ZipFile::index_of()`] for how the entry is matched; [`ZipError::ForeignEntry`] is returned if it doesn't
+    /// belong to this ZIP file.
+    ///
+    /// As this reader is borrowed mutably, an entry found via [`ZipFileReader::file()`] must be cloned beforehand.
+    pub async fn reader_for(&mut self, entry: &StoredZipEntry) -> Result<ZipEntryReader<'_, R, WithEntry<'_>>> {
+        let index = self.file().index_of(entry).ok_or(ZipError::ForeignEntry)?;
+        self.reader_with_entry(index).await
+    }
+
+    /// Returns a new entry reader which decrypts the entry's data with the provided password, if the provided index is
+    /// valid.
+    ///
+    /// If the entry isn't encrypted, the password is ignored. [`ZipError::WrongPassword`] is returned if the password
+    /// fails the check against the entry's encryption header.
+    pub async fn reader_with_password(
+        &mut self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'_, R, WithEntry<'_>>> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut reader = BufReader::with_capacity(self.buffer_capacity, &mut self.reader);
 
+        stored_entry.seek_to_data_offset(&mut reader).await?;
+
+        let reader = ZipEntryReader::new_with_borrow_password(reader, stored_entry, password)
+            .await?
+            .with_expansion_limit_opt(self.expansion_limit);
         Ok(reader.into_with_entry(stored_entry))
     }
 
+    /// Returns a reader over the raw data of an entry (ie. still compressed and, if applicable, encrypted), if the
+    /// provided index is valid.
+    ///
+    /// The reader yields exactly the entry's compressed size in bytes. Alongside the entry's compression method, CRC32
+    /// value, and sizes, this allows an entry to be copied into another ZIP file without decompressing it.
+    pub async fn raw_reader(&mut self, index: usize) -> Result<Take<BufReader<&mut R>>> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut reader = BufReader::with_capacity(self.buffer_capacity, &mut self.reader);
+
+        stored_entry.seek_to_data_offset(&mut reader).await?;
+
+        Ok(reader.take(stored_entry.compressed_size()))
+    }
+
+    /// Reads the entire decompressed contents of an entry, if the provided index is valid.
+    ///
+    /// The entry's CRC32 value is verified once all of its data has been read.
+    pub async fn read_entry(&mut self, index: usize) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.reader_with_entry(index).await?.read_to_end_checked(&mut data).await?;
+        Ok(data)
+    }
+
+    /// Computes the SHA-256 digest of the decompressed contents of an entry, if the provided index is valid.
+    ///
+    /// The entry's data is streamed through the hasher rather than buffered, and its CRC32 value is verified once all
+    /// of its data has been read. For a cheaper tag derived from the entry's metadata, see
+    /// [`ZipFileReader::entry_etag()`].
+    #[cfg(feature = "sha256")]
+    pub async fn entry_sha256(&mut self, index: usize) -> Result<[u8; 32]> {
+        self.reader_with_entry(index).await?.sha256_checked().await
+    }
+
+    /// Reads the entire decompressed contents of an entry, if the provided index is valid and it doesn't exceed the
+    /// provided limit (in bytes).
+    ///
+    /// This guards against entries whose data decompresses to far more than is expected (eg. ZIP bombs), as
+    /// decompression stops once the limit has been exceeded, returning [`ZipError::EntryTooLarge`]. The same error is
+    /// returned upfront if the entry's declared uncompressed size exceeds the limit.
+    pub async fn read_entry_limited(&mut self, index: usize, limit: u64) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.reader_with_entry(index).await?.read_to_end_limited(&mut data, limit).await?;
+        Ok(data)
+    }
+
+    /// Reads up to the first `length` decompressed bytes of an entry, if the provided index is valid.
+    ///
+    /// Fewer bytes are returned if the entry holds less data. As the entry isn't read in full, its CRC32 value isn't
+    /// verified.
+    pub async fn read_entry_prefix(&mut self, index: usize, length: usize) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.reader_without_entry(index).await?.take(length as u64).read_to_end(&mut data).await?;
+        Ok(data)
+    }
+
+    /// Copies the entire decompressed contents of an entry into the provided writer, if the provided index is valid.
+    ///
+    /// The number of bytes written is returned, and the entry's CRC32 value is verified once all of its data has been
+    /// read.
+    pub async fn copy_entry_to<W: AsyncWrite + Unpin>(&mut self, index: usize, writer: W) -> Result<u64> {
+        self.reader_with_entry(index).await?.copy_to_checked(writer).await
+    }
+
+    /// Reads the entire decompressed contents of the first entry with the provided filename, if one exists.
+    ///
+    /// See [`ZipFile::entry()`] for how filenames are matched.
+    pub async fn read_entry_by_name(&mut self, filename: impl AsRef<[u8]>) -> Result<Vec<u8>> {
+        let (index, _) = self.file().entry(filename).ok_or(ZipError::EntryNotFound)?;
+        self.read_entry(index).await
+    }
+
+    /// Reads every entry to completion (without buffering their data), verifying each entry's CRC32 value.
+    ///
+    /// The first failure is returned as [`ZipError::EntryValidationFailed`], which names the failing entry. Encrypted
+    /// entries can't be validated without a password (see [`ZipFileReader::validate_with_password()`]).
+    pub async fn validate(&mut self) -> Result<()> {
+        self.validate_entries(None).await
+    }
+
+    /// Reads every entry to completion (without buffering their data), decrypting any encrypted entries with the
+    /// provided password, and verifying each entry's CRC32 value (or authentication code for AE-2 entries).
+    ///
+    /// The first failure is returned as [`ZipError::EntryValidationFailed`], which names the failing entry.
+    pub async fn validate_with_password(&mut self, password: &[u8]) -> Result<()> {
+        self.validate_entries(Some(password)).await
+    }
+
+    async fn validate_entries(&mut self, password: Option<&[u8]>) -> Result<()> {
+        for index in 0..self.file().entries().len() {
+            let reader = match (password, self.file().entries()[index].is_encrypted()) {
+                (Some(password), _) => self.reader_with_password(index, password).await,
+                (None, true) => Err(ZipError::FeatureNotSupported("validating encrypted entries without a password")),
+                (None, false) => self.reader_with_entry(index).await,
+            };
+
+            let result = match reader {
+                Ok(mut reader) => reader.consume_checked().await,
+                Err(err) => Err(err),
+            };
+
+            result.map_err(|err| crate::base::read::validation_error(self.file(), index, err))?;
+        }
+
+        Ok(())
+    }
+
     /// Returns a new entry reader if the provided index is valid.
     /// Consumes self
     pub async fn into_entry<'a>(self, index: usize) -> Result<ZipEntryReader<'a, R, WithoutEntry>>
@@ -117,15 +361,18 @@ where
         R: 'a,
     {
         let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let mut reader = BufReader::new(self.reader);
+        let mut reader = BufReader::with_capacity(self.buffer_capacity, self.reader);
 
         stored_entry.seek_to_data_offset(&mut reader).await?;
 
         Ok(ZipEntryReader::new_with_owned(
             reader,
             stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+            stored_entry.data_size(),
+            stored_entry.is_empty(),
+        )
+        .with_uncompressed_size(stored_entry.entry.uncompressed_size())
+        .with_expansion_limit_opt(self.expansion_limit))
     }
 }
 
@@ -137,7 +384,19 @@ where
     /// Constructs a new tokio-specific ZIP reader from a seekable source.
     pub async fn with_tokio(reader: R) -> Result<TokioZipFileReader<R>> {
         let mut reader = reader.compat();
-        let file = crate::base::read::file(&mut reader).await?;
+        let file = crate::base::read::file(&mut reader, ReadOptions::default()).await?;
         Ok(ZipFileReader::from_raw_parts(reader, file))
     }
 }
+
+impl<R> Index<usize> for ZipFileReader<R> {
+    type Output = StoredZipEntry;
+
+    /// Returns the entry at the provided index.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds. See [`ZipFileReader::get()`] for a non-panicking alternative.
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.file.entries[index]
+    }
+}
@@ -19,6 +19,7 @@
 //! to it. We have to rely on information provided within the local file header which may not be accurate or complete.
 //! This results in:
 //! - The inability to read internally stored ZIP archives when using the Stored compression method.
+//! - The inability to read Stored entries which were written with a data descriptor, as their end can't be located.
 //! - No file comment being available (defaults to an empty string).
 //! - No internal or external file attributes being available (defaults to 0).
 //! - The extra field data potentially being inconsistent with what's stored in the central directory.
@@ -27,6 +28,14 @@
 //!     - compressed size
 //!     - uncompressed size
 //!
+//! Entries written with a data descriptor are otherwise supported; their compressed data is read until the end of the
+//! compressed stream, and the data descriptor which follows is consumed when transitioning back into the Ready state.
+//!
+//! Encrypted entries are decrypted inline with the password provided via [`ZipFileReader::with_password()`]. Without
+//! one, opening an encrypted entry returns [`ZipError::PasswordRequired`] rather than yielding its encrypted data.
+//! Encrypted entries written with a data descriptor can only be read if their sizes are also stored within the local
+//! file header.
+//!
 //! # Example
 //! ```no_run
 //! # use futures_util::io::Cursor;
@@ -46,15 +55,19 @@
 //! # }
 //! ```
 
+use crate::base::read::get_zip64_extra_field;
 use crate::base::read::io::entry::ZipEntryReader;
+use crate::entry::{StoredZipEntry, ZipEntry};
 use crate::error::Result;
 use crate::error::ZipError;
+use crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE;
+
+use std::sync::OnceLock;
 
 #[cfg(feature = "tokio")]
 use crate::tokio::read::stream::Ready as TokioReady;
 
 use futures_util::io::AsyncReadExt;
-use futures_util::io::Take;
 use futures_util::io::{AsyncRead, BufReader};
 
 #[cfg(feature = "tokio")]
@@ -64,10 +77,18 @@ use super::io::entry::WithEntry;
 use super::io::entry::WithoutEntry;
 
 /// A type which encodes that [`ZipFileReader`] is ready to open a new entry.
-pub struct Ready<R>(R);
+pub struct Ready<R>(BufReader<R>, Option<Vec<u8>>);
 
 /// A type which encodes that [`ZipFileReader`] is currently reading an entry.
-pub struct Reading<'a, R, E>(ZipEntryReader<'a, R, E>);
+pub struct Reading<'a, R, E>(ZipEntryReader<'a, R, E>, DataDescriptor, Option<Vec<u8>>);
+
+/// The kind of data descriptor which follows the entry currently being read, if any.
+#[derive(Clone, Copy)]
+enum DataDescriptor {
+    None,
+    Standard,
+    Zip64,
+}
 
 /// A ZIP reader which acts over a non-seekable source.
 ///
@@ -81,38 +102,48 @@ where
 {
     /// Constructs a new ZIP reader from a non-seekable source.
     pub fn new(reader: R) -> Self {
-        Self(Ready(reader))
+        Self(Ready(BufReader::new(reader), None))
+    }
+
+    /// Sets the password with which encrypted entries are decrypted as they're read.
+    ///
+    /// Unencrypted entries are read as normal, regardless of whether or not a password has been provided.
+    pub fn with_password(mut self, password: &[u8]) -> Self {
+        self.0 .1 = Some(password.to_vec());
+        self
     }
 
     /// Opens the next entry for reading if the central directory hasn’t yet been reached.
-    pub async fn next_without_entry(mut self) -> Result<Option<ZipFileReader<Reading<'a, Take<R>, WithoutEntry>>>> {
-        let entry = match crate::base::read::lfh(&mut self.0 .0).await? {
-            Some(entry) => entry,
+    pub async fn next_without_entry(mut self) -> Result<Option<ZipFileReader<Reading<'a, R, WithoutEntry>>>> {
+        let (entry, descriptor) = match next_entry(&mut self.0 .0).await? {
+            Some(next) => next,
             None => return Ok(None),
         };
 
-        let reader = BufReader::new(self.0 .0.take(entry.compressed_size));
-        let reader = ZipEntryReader::new_with_owned(reader, entry.compression, entry.compressed_size);
+        let Ready(reader, password) = self.0;
+        let (reader, _) = entry_reader(reader, entry, descriptor, password.as_deref()).await?;
 
-        Ok(Some(ZipFileReader(Reading(reader))))
+        Ok(Some(ZipFileReader(Reading(reader, descriptor, password))))
     }
 
     /// Opens the next entry for reading if the central directory hasn’t yet been reached.
-    pub async fn next_with_entry(mut self) -> Result<Option<ZipFileReader<Reading<'a, Take<R>, WithEntry<'a>>>>> {
-        let entry = match crate::base::read::lfh(&mut self.0 .0).await? {
-            Some(entry) => entry,
+    pub async fn next_with_entry(mut self) -> Result<Option<ZipFileReader<Reading<'a, R, WithEntry<'a>>>>> {
+        let (entry, descriptor) = match next_entry(&mut self.0 .0).await? {
+            Some(next) => next,
             None => return Ok(None),
         };
 
-        let reader = BufReader::new(self.0 .0.take(entry.compressed_size));
-        let reader = ZipEntryReader::new_with_owned(reader, entry.compression, entry.compressed_size);
+        let Ready(reader, password) = self.0;
+        let (reader, entry) = entry_reader(reader, entry, descriptor, password.as_deref()).await?;
 
-        Ok(Some(ZipFileReader(Reading(reader.into_with_entry_owned(entry)))))
+        Ok(Some(ZipFileReader(Reading(reader.into_with_entry_owned(entry), descriptor, password))))
     }
 
     /// Consumes the `ZipFileReader` returning the original `reader`
+    ///
+    /// Any data which has been buffered internally but not yet consumed will be lost.
     pub async fn into_inner(self) -> R {
-        self.0 .0
+        self.0 .0.into_inner()
     }
 }
 
@@ -123,21 +154,21 @@ where
 {
     /// Constructs a new tokio-specific ZIP reader from a non-seekable source.
     pub fn with_tokio(reader: R) -> ZipFileReader<TokioReady<R>> {
-        Self(Ready(reader.compat()))
+        Self(Ready(BufReader::new(reader.compat()), None))
     }
 }
 
-impl<'a, R, E> ZipFileReader<Reading<'a, Take<R>, E>>
+impl<'a, R, E> ZipFileReader<Reading<'a, R, E>>
 where
     R: AsyncRead + Unpin,
 {
     /// Returns an immutable reference to the inner entry reader.
-    pub fn reader(&self) -> &ZipEntryReader<'a, Take<R>, E> {
+    pub fn reader(&self) -> &ZipEntryReader<'a, R, E> {
         &self.0 .0
     }
 
     /// Returns a mutable reference to the inner entry reader.
-    pub fn reader_mut(&mut self) -> &mut ZipEntryReader<'a, Take<R>, E> {
+    pub fn reader_mut(&mut self) -> &mut ZipEntryReader<'a, R, E> {
         &mut self.0 .0
     }
 
@@ -147,12 +178,104 @@ where
             return Err(ZipError::EOFNotReached);
         }
 
-        Ok(ZipFileReader(Ready(self.0 .0.into_inner().into_inner())))
+        let mut inner = self.0 .0.into_inner();
+        consume_data_descriptor(&mut inner, self.0 .1).await?;
+
+        Ok(ZipFileReader(Ready(inner, self.0 .2)))
     }
 
     /// Reads until EOF and converts the reader back into the Ready state.
     pub async fn skip(mut self) -> Result<ZipFileReader<Ready<R>>> {
         while self.0 .0.read(&mut [0; 2048]).await? != 0 {}
-        Ok(ZipFileReader(Ready(self.0 .0.into_inner().into_inner())))
+
+        let mut inner = self.0 .0.into_inner();
+        consume_data_descriptor(&mut inner, self.0 .1).await?;
+
+        Ok(ZipFileReader(Ready(inner, self.0 .2)))
     }
 }
+
+/// Reads the next local file header and determines which kind of data descriptor follows its data, if any.
+async fn next_entry<R>(reader: &mut BufReader<R>) -> Result<Option<(ZipEntry, DataDescriptor)>>
+where
+    R: AsyncRead + Unpin,
+{
+    let entry = match crate::base::read::lfh(reader).await? {
+        Some(next) => next,
+        None => return Ok(None),
+    };
+
+    let descriptor = match (entry.has_data_descriptor(), get_zip64_extra_field(&entry.extra_fields).is_some()) {
+        (false, _) => DataDescriptor::None,
+        (true, false) => DataDescriptor::Standard,
+        (true, true) => DataDescriptor::Zip64,
+    };
+
+    Ok(Some((entry, descriptor)))
+}
+
+/// Constructs an entry reader over the data of an entry (returned alongside it), decrypting it if it's encrypted.
+async fn entry_reader<'a, R>(
+    reader: BufReader<R>,
+    entry: ZipEntry,
+    descriptor: DataDescriptor,
+    password: Option<&[u8]>,
+) -> Result<(ZipEntryReader<'a, R, WithoutEntry>, ZipEntry)>
+where
+    R: AsyncRead + Unpin + 'a,
+{
+    if !entry.is_encrypted() {
+        let (size, empty) = reader_parameters(&entry, descriptor);
+        let reader = ZipEntryReader::new_with_owned(reader, entry.compression, size, empty)
+            .with_uncompressed_size(entry.uncompressed_size);
+        return Ok((reader, entry));
+    }
+
+    let Some(password) = password else {
+        return Err(ZipError::PasswordRequired(String::from_utf8_lossy(entry.filename().as_bytes()).into_owned()));
+    };
+
+    // Decryption doesn't stop at the end of the compressed stream, so the size of the entry's data must be known.
+    if entry.has_data_descriptor() && entry.compressed_size == 0 {
+        return Err(ZipError::FeatureNotSupported("stream reading encrypted entries with data descriptors"));
+    }
+
+    let entry = StoredZipEntry { entry, file_offset: 0, data_offset: OnceLock::new() };
+
+    let reader = ZipEntryReader::new_with_owned_password(reader, &entry, password).await?;
+    Ok((reader, entry.entry))
+}
+
+/// Returns the number of bytes making up an entry's data and whether or not the entry is known to be empty.
+///
+/// When a data descriptor follows the entry, neither is known from the local file header, so the entry's data is read
+/// until the end of its compressed stream.
+fn reader_parameters(entry: &ZipEntry, descriptor: DataDescriptor) -> (u64, bool) {
+    match descriptor {
+        DataDescriptor::None => (entry.compressed_size, entry.uncompressed_size == 0),
+        _ => (u64::MAX, false),
+    }
+}
+
+/// Consumes a data descriptor (with or without its optional signature) from the reader.
+async fn consume_data_descriptor<R>(reader: &mut BufReader<R>, descriptor: DataDescriptor) -> Result<()>
+where
+    R: AsyncRead + Unpin,
+{
+    let sizes_length = match descriptor {
+        DataDescriptor::None => return Ok(()),
+        DataDescriptor::Standard => 8,
+        DataDescriptor::Zip64 => 16,
+    };
+
+    let mut buffer = [0; 4];
+    reader.read_exact(&mut buffer).await?;
+
+    // The signature is optional (4.3.9.3) so, if present, the CRC follows it.
+    if u32::from_le_bytes(buffer) == DATA_DESCRIPTOR_SIGNATURE {
+        reader.read_exact(&mut buffer).await?;
+    }
+
+    reader.read_exact(&mut [0; 16][..sizes_length]).await?;
+    Ok(())
+}
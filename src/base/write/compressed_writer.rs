@@ -1,7 +1,10 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "lzma")]
+use crate::base::write::io::lzma::LzmaHeaderWriter;
 use crate::base::write::io::offset::AsyncOffsetWriter;
+use crate::entry::ZipEntry;
 use crate::spec::Compression;
 
 use std::io::Error;
@@ -19,7 +22,7 @@ pub enum CompressedAsyncWriter<'b, W: AsyncWrite + Unpin> {
     #[cfg(feature = "bzip2")]
     Bz(write::BzEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
     #[cfg(feature = "lzma")]
-    Lzma(write::LzmaEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Lzma(write::LzmaEncoder<LzmaHeaderWriter<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>>),
     #[cfg(feature = "zstd")]
     Zstd(write::ZstdEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
     #[cfg(feature = "xz")]
@@ -27,23 +30,36 @@ pub enum CompressedAsyncWriter<'b, W: AsyncWrite + Unpin> {
 }
 
 impl<'b, W: AsyncWrite + Unpin> CompressedAsyncWriter<'b, W> {
-    pub fn from_raw(writer: &'b mut AsyncOffsetWriter<W>, compression: Compression) -> Self {
-        match compression {
+    pub fn from_raw(writer: &'b mut AsyncOffsetWriter<W>, entry: &ZipEntry) -> Self {
+        match entry.compression() {
             Compression::Stored => CompressedAsyncWriter::Stored(ShutdownIgnoredWriter(writer)),
             #[cfg(feature = "deflate")]
-            Compression::Deflate => {
-                CompressedAsyncWriter::Deflate(write::DeflateEncoder::new(ShutdownIgnoredWriter(writer)))
-            }
+            Compression::Deflate => CompressedAsyncWriter::Deflate(write::DeflateEncoder::with_quality(
+                ShutdownIgnoredWriter(writer),
+                entry.compression_level,
+            )),
             #[cfg(feature = "deflate64")]
             Compression::Deflate64 => panic!("writing deflate64 is not supported"),
             #[cfg(feature = "bzip2")]
-            Compression::Bz => CompressedAsyncWriter::Bz(write::BzEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Bz => CompressedAsyncWriter::Bz(write::BzEncoder::with_quality(
+                ShutdownIgnoredWriter(writer),
+                entry.compression_level,
+            )),
             #[cfg(feature = "lzma")]
-            Compression::Lzma => CompressedAsyncWriter::Lzma(write::LzmaEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Lzma => CompressedAsyncWriter::Lzma(write::LzmaEncoder::with_quality(
+                LzmaHeaderWriter::new(ShutdownIgnoredWriter(writer)),
+                entry.compression_level,
+            )),
             #[cfg(feature = "zstd")]
-            Compression::Zstd => CompressedAsyncWriter::Zstd(write::ZstdEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Zstd => CompressedAsyncWriter::Zstd(write::ZstdEncoder::with_quality(
+                ShutdownIgnoredWriter(writer),
+                entry.compression_level,
+            )),
             #[cfg(feature = "xz")]
-            Compression::Xz => CompressedAsyncWriter::Xz(write::XzEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Xz => CompressedAsyncWriter::Xz(write::XzEncoder::with_quality(
+                ShutdownIgnoredWriter(writer),
+                entry.compression_level,
+            )),
         }
     }
 
@@ -55,7 +71,7 @@ impl<'b, W: AsyncWrite + Unpin> CompressedAsyncWriter<'b, W> {
             #[cfg(feature = "bzip2")]
             CompressedAsyncWriter::Bz(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "lzma")]
-            CompressedAsyncWriter::Lzma(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Lzma(inner) => inner.into_inner().into_inner().into_inner(),
             #[cfg(feature = "zstd")]
             CompressedAsyncWriter::Zstd(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "xz")]
@@ -12,7 +12,6 @@ use crate::spec::header::{
     CentralDirectoryRecord, ExtraField, GeneralPurposeFlag, HeaderId, LocalFileHeader,
     Zip64ExtendedInformationExtraField,
 };
-use crate::string::StringEncoding;
 
 use std::io::Error;
 use std::pin::Pin;
@@ -54,7 +53,7 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
 
         let cd_entries = &mut writer.cd_entries;
         let is_zip64 = &mut writer.is_zip64;
-        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, entry.compression()));
+        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, &entry));
 
         Ok(EntryStreamWriter {
             writer,
@@ -114,8 +113,8 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             flags: GeneralPurposeFlag {
                 data_descriptor: true,
                 encrypted: false,
-                filename_unicode: matches!(entry.filename().encoding(), StringEncoding::Utf8)
-                    && matches!(entry.comment().encoding(), StringEncoding::Utf8),
+                filename_unicode: crate::base::write::utf8_name_flag(entry),
+                other: crate::base::write::compression_flag_bits(entry.compression()),
             },
         };
 
@@ -148,7 +147,7 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             if uncompressed_size > NON_ZIP64_MAX_SIZE as u64 || compressed_size > NON_ZIP64_MAX_SIZE as u64 {
                 return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
             }
-            (uncompressed_size as u32, compressed_size as u32)
+            (compressed_size as u32, uncompressed_size as u32)
         } else {
             // When streaming an entry, we are always using a zip64 field.
             match get_zip64_extra_field_mut(&mut self.entry.extra_fields) {
@@ -158,7 +157,7 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
                         Zip64ExtendedInformationExtraField {
                             header_id: HeaderId::Zip64ExtendedInformationExtraField,
                             data_size: 16,
-                            uncompressed_size: Some(compressed_size),
+                            uncompressed_size: Some(uncompressed_size),
                             compressed_size: Some(compressed_size),
                             relative_header_offset: None,
                             disk_start_number: None,
@@ -178,17 +177,47 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
 
         inner_writer.write_all(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
         inner_writer.write_all(&crc.to_le_bytes()).await?;
-        inner_writer.write_all(&cdr_compressed_size.to_le_bytes()).await?;
-        inner_writer.write_all(&cdr_uncompressed_size.to_le_bytes()).await?;
+
+        // The local file header carries a zip64 extended field unless zip64 is forced off, in which case the data
+        // descriptor's sizes must be eight bytes each (4.3.9.2).
+        if self.force_no_zip64 {
+            inner_writer.write_all(&cdr_compressed_size.to_le_bytes()).await?;
+            inner_writer.write_all(&cdr_uncompressed_size.to_le_bytes()).await?;
+        } else {
+            inner_writer.write_all(&compressed_size.to_le_bytes()).await?;
+            inner_writer.write_all(&uncompressed_size.to_le_bytes()).await?;
+        }
+
+        // The local file header's offset is only known to fit within the central directory's four-byte field when
+        // below the zip64 sentinel value, else it's stored within the zip64 extended field instead.
+        let lh_offset = if self.lfh_offset >= NON_ZIP64_MAX_SIZE as usize {
+            if self.force_no_zip64 {
+                return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
+            }
+            *self.is_zip64 = true;
+
+            if let Some(zip64) = get_zip64_extra_field_mut(&mut self.entry.extra_fields) {
+                zip64.relative_header_offset = Some(self.lfh_offset as u64);
+                zip64.data_size += 8;
+            }
+            NON_ZIP64_MAX_SIZE
+        } else {
+            self.lfh_offset as u32
+        };
 
         let cdh = CentralDirectoryRecord {
             compressed_size: cdr_compressed_size,
             uncompressed_size: cdr_uncompressed_size,
             crc,
-            v_made_by: crate::spec::version::as_made_by(),
+            v_made_by: crate::spec::version::as_made_by(self.entry.attribute_compatibility()),
             v_needed: self.lfh.version,
             compression: self.lfh.compression,
-            extra_field_length: self.lfh.extra_field_length,
+            extra_field_length: self
+                .entry
+                .extra_fields()
+                .count_bytes()
+                .try_into()
+                .map_err(|_| ZipError::ExtraFieldTooLarge)?,
             file_name_length: self.lfh.file_name_length,
             file_comment_length: self
                 .entry
@@ -203,10 +232,17 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             disk_start: 0,
             inter_attr: self.entry.internal_file_attribute(),
             exter_attr: self.entry.external_file_attribute(),
-            lh_offset: self.lfh_offset as u32,
+            lh_offset,
         };
 
-        self.cd_entries.push(CentralDirectoryEntry { header: cdh, entry: self.entry });
+        self.cd_entries.push(CentralDirectoryEntry {
+            header: cdh,
+            entry: self.entry,
+            lfh_offset: self.lfh_offset as u64,
+            data_offset: self.data_offset as u64,
+            data_length: compressed_size,
+            extra_field_raw: None,
+        });
         // Ensure that we can fit this many files in this archive if forcing no zip64
         if self.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize {
             if self.force_no_zip64 {
@@ -10,10 +10,11 @@ use crate::spec::{
     header::{CentralDirectoryRecord, ExtraField, GeneralPurposeFlag, LocalFileHeader},
     Compression,
 };
-use crate::StringEncoding;
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use futures_util::io::Cursor;
 
+#[cfg(feature = "lzma")]
+use crate::base::write::io::lzma::LzmaHeaderWriter;
 use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use async_compression::futures::write;
@@ -24,16 +25,35 @@ pub struct EntryWholeWriter<'b, 'c, W: AsyncWrite + Unpin> {
     writer: &'b mut ZipFileWriter<W>,
     entry: ZipEntry,
     data: &'c [u8],
+    compressed: bool,
 }
 
 impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
     pub fn from_raw(writer: &'b mut ZipFileWriter<W>, entry: ZipEntry, data: &'c [u8]) -> Self {
-        Self { writer, entry, data }
+        Self { writer, entry, data, compressed: false }
+    }
+
+    /// Constructs a writer for data which has already been compressed with the entry's compression method, and whose
+    /// CRC32 value & uncompressed size are taken from the entry.
+    pub fn from_compressed(writer: &'b mut ZipFileWriter<W>, mut entry: ZipEntry, data: &'c [u8]) -> Result<Self> {
+        if entry.is_encrypted() {
+            return Err(ZipError::FeatureNotSupported("writing encrypted entries"));
+        }
+
+        // Any zip64 extended information extra field describes where the entry was previously stored, so is rebuilt.
+        entry.extra_fields.retain(|field| !matches!(field, ExtraField::Zip64ExtendedInformationExtraField(_)));
+        Ok(Self { writer, entry, data, compressed: true })
     }
 
     pub async fn write(mut self) -> Result<()> {
+        let (uncompressed_size, crc) = match self.compressed {
+            true => (self.entry.uncompressed_size(), self.entry.crc32()),
+            false => (self.data.len() as u64, compute_crc(self.data)),
+        };
+
         let mut _compressed_data: Option<Vec<u8>> = None;
         let compressed_data = match self.entry.compression() {
+            _ if self.compressed => self.data,
             Compression::Stored => self.data,
             #[cfg(any(
                 feature = "deflate",
@@ -44,15 +64,21 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
                 feature = "deflate64"
             ))]
             _ => {
-                _compressed_data =
-                    Some(compress(self.entry.compression(), self.data, self.entry.compression_level).await);
-                _compressed_data.as_ref().unwrap()
+                let compressed = compress(self.entry.compression(), self.data, self.entry.compression_level).await;
+
+                // Compressing already-compressed data (eg. JPEGs) may only add overhead, so store it as-is instead.
+                if self.entry.auto_stored && compressed.len() >= self.data.len() {
+                    self.entry.compression = Compression::Stored;
+                    self.data
+                } else {
+                    _compressed_data.insert(compressed)
+                }
             }
         };
 
         let mut zip64_extra_field_builder = None;
 
-        let (lfh_uncompressed_size, lfh_compressed_size) = if self.data.len() as u64 > NON_ZIP64_MAX_SIZE as u64
+        let (lfh_uncompressed_size, lfh_compressed_size) = if uncompressed_size > NON_ZIP64_MAX_SIZE as u64
             || compressed_data.len() as u64 > NON_ZIP64_MAX_SIZE as u64
         {
             if self.writer.force_no_zip64 {
@@ -62,15 +88,14 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
                 self.writer.is_zip64 = true;
             }
             zip64_extra_field_builder = Some(
-                Zip64ExtendedInformationExtraFieldBuilder::new()
-                    .sizes(compressed_data.len() as u64, self.data.len() as u64),
+                Zip64ExtendedInformationExtraFieldBuilder::new().sizes(compressed_data.len() as u64, uncompressed_size),
             );
             (NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE)
         } else {
-            (self.data.len() as u32, compressed_data.len() as u32)
+            (uncompressed_size as u32, compressed_data.len() as u32)
         };
 
-        let lh_offset = if self.writer.writer.offset() > NON_ZIP64_MAX_SIZE as usize {
+        let lh_offset = if self.writer.writer.offset() >= NON_ZIP64_MAX_SIZE as usize {
             if self.writer.force_no_zip64 {
                 return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
             }
@@ -105,7 +130,7 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
             compressed_size: lfh_compressed_size,
             uncompressed_size: lfh_uncompressed_size,
             compression: self.entry.compression().into(),
-            crc: compute_crc(self.data),
+            crc,
             extra_field_length: self
                 .entry
                 .extra_fields()
@@ -125,13 +150,13 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
             flags: GeneralPurposeFlag {
                 data_descriptor: false,
                 encrypted: false,
-                filename_unicode: matches!(self.entry.filename().encoding(), StringEncoding::Utf8)
-                    && matches!(self.entry.comment().encoding(), StringEncoding::Utf8),
+                filename_unicode: crate::base::write::utf8_name_flag(&self.entry),
+                other: self.compression_flag_bits(),
             },
         };
 
         let mut header = CentralDirectoryRecord {
-            v_made_by: crate::spec::version::as_made_by(),
+            v_made_by: crate::spec::version::as_made_by(self.entry.attribute_compatibility()),
             v_needed: lf_header.version,
             compressed_size: lf_header.compressed_size,
             uncompressed_size: lf_header.uncompressed_size,
@@ -155,10 +180,13 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
             lh_offset,
         };
 
+        let lfh_offset = self.writer.writer.offset() as u64;
         self.writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
         self.writer.writer.write_all(&lf_header.as_slice()).await?;
         self.writer.writer.write_all(self.entry.filename().as_bytes()).await?;
         self.writer.writer.write_all(&self.entry.extra_fields().as_bytes()).await?;
+
+        let data_offset = self.writer.writer.offset() as u64;
         self.writer.writer.write_all(compressed_data).await?;
 
         if let Some(builder) = zip64_extra_field_builder {
@@ -167,7 +195,15 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
                 self.entry.extra_fields().count_bytes().try_into().map_err(|_| ZipError::ExtraFieldTooLarge)?;
         }
 
-        self.writer.cd_entries.push(CentralDirectoryEntry { header, entry: self.entry });
+        let data_length = compressed_data.len() as u64;
+        self.writer.cd_entries.push(CentralDirectoryEntry {
+            header,
+            entry: self.entry,
+            lfh_offset,
+            data_offset,
+            data_length,
+            extra_field_raw: None,
+        });
         // Ensure that we can fit this many files in this archive if forcing no zip64
         if self.writer.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize {
             if self.writer.force_no_zip64 {
@@ -179,6 +215,16 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
         }
         Ok(())
     }
+
+    /// Returns the general purpose flag bits describing how the entry's compressed data is stored.
+    ///
+    /// Pre-compressed data retains the compression option bits (1 & 2) of the entry it was copied from.
+    fn compression_flag_bits(&self) -> u16 {
+        match self.compressed {
+            true => self.entry.general_purpose_flag.other & 0x6,
+            false => crate::base::write::compression_flag_bits(self.entry.compression()),
+        }
+    }
 }
 
 #[cfg(any(
@@ -211,10 +257,10 @@ async fn compress(compression: Compression, data: &[u8], level: async_compressio
         }
         #[cfg(feature = "lzma")]
         Compression::Lzma => {
-            let mut writer = write::LzmaEncoder::with_quality(Cursor::new(Vec::new()), level);
+            let mut writer = write::LzmaEncoder::with_quality(LzmaHeaderWriter::new(Cursor::new(Vec::new())), level);
             writer.write_all(data).await.unwrap();
             writer.close().await.unwrap();
-            writer.into_inner().into_inner()
+            writer.into_inner().into_inner().into_inner()
         }
         #[cfg(feature = "xz")]
         Compression::Xz => {
@@ -0,0 +1,110 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::consts::{LZMA_HEADER_LENGTH, LZMA_PROPERTIES_LENGTH};
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_util::io::AsyncWrite;
+
+/// The LZMA SDK version recorded within the header, matching that written by Python's `zipfile` module.
+const LZMA_VERSION: [u8; 2] = [9, 4];
+
+/// The length of the header which precedes an LZMA stream within a ZIP entry's data.
+const ZIP_HEADER_LENGTH: usize = LZMA_HEADER_LENGTH + LZMA_PROPERTIES_LENGTH;
+
+/// The length of the header which precedes an LZMA stream in the legacy `.lzma` format (and exceeds the above).
+const ALONE_HEADER_LENGTH: usize = LZMA_PROPERTIES_LENGTH + 8;
+
+/// A wrapping writer which converts the legacy `.lzma` header written by the LZMA encoder into the LZMA properties
+/// header of a ZIP entry's data (5.8.8).
+///
+/// The encoder declares the uncompressed size as unknown and so terminates the stream with an end-of-stream marker,
+/// which must be signalled via the general purpose flag.
+pub struct LzmaHeaderWriter<W> {
+    writer: W,
+    header: [u8; ALONE_HEADER_LENGTH],
+    state: State,
+}
+
+enum State {
+    /// The legacy header is being buffered, with the number of bytes buffered so far.
+    Buffering(usize),
+    /// The ZIP header is being written, with the number of bytes written so far.
+    Writing(usize),
+    /// The compressed data is being passed through.
+    Data,
+}
+
+impl<W> LzmaHeaderWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Constructs a new wrapping writer from a generic [`AsyncWrite`] implementer.
+    pub fn new(writer: W) -> Self {
+        Self { writer, header: [0; ALONE_HEADER_LENGTH], state: State::Buffering(0) }
+    }
+
+    /// Consumes this writer and returns the inner value.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn poll_write_header(&mut self, cx: &mut Context) -> Poll<Result<(), Error>> {
+        while let State::Writing(written) = self.state {
+            if written == ZIP_HEADER_LENGTH {
+                self.state = State::Data;
+                break;
+            }
+
+            let length = ready!(Pin::new(&mut self.writer).poll_write(cx, &self.header[written..ZIP_HEADER_LENGTH]))?;
+            if length == 0 {
+                return Poll::Ready(Err(ErrorKind::WriteZero.into()));
+            }
+
+            self.state = State::Writing(written + length);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W> AsyncWrite for LzmaHeaderWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Error>> {
+        if let State::Buffering(buffered) = self.state {
+            let length = buf.len().min(ALONE_HEADER_LENGTH - buffered);
+            self.header[buffered..buffered + length].copy_from_slice(&buf[..length]);
+
+            if buffered + length == ALONE_HEADER_LENGTH {
+                let properties_length = (LZMA_PROPERTIES_LENGTH as u16).to_le_bytes();
+
+                self.header.copy_within(..LZMA_PROPERTIES_LENGTH, LZMA_HEADER_LENGTH);
+                self.header[..2].copy_from_slice(&LZMA_VERSION);
+                self.header[2..LZMA_HEADER_LENGTH].copy_from_slice(&properties_length);
+                self.state = State::Writing(0);
+            } else {
+                self.state = State::Buffering(buffered + length);
+            }
+
+            return Poll::Ready(Ok(length));
+        }
+
+        ready!(self.poll_write_header(cx))?;
+        Pin::new(&mut self.writer).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        ready!(self.poll_write_header(cx))?;
+        Pin::new(&mut self.writer).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        ready!(self.poll_write_header(cx))?;
+        Pin::new(&mut self.writer).poll_close(cx)
+    }
+}
@@ -1,4 +1,6 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "lzma")]
+pub(crate) mod lzma;
 pub(crate) mod offset;
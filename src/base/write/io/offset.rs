@@ -25,6 +25,11 @@ where
         Self { inner, offset: 0 }
     }
 
+    /// Constructs a new wrapper from an inner [`AsyncWrite`] writer, starting from the provided byte offset.
+    pub fn with_offset(inner: W, offset: usize) -> Self {
+        Self { inner, offset }
+    }
+
     /// Returns the current byte offset.
     pub fn offset(&self) -> usize {
         self.offset
@@ -59,13 +59,18 @@ pub use entry_stream::EntryStreamWriter;
 #[cfg(feature = "tokio")]
 use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
-use crate::entry::ZipEntry;
-use crate::error::Result;
+use crate::date::ZipDateTime;
+use crate::entry::builder::ZipEntryBuilder;
+use crate::entry::{StoredZipEntry, ZipEntry, DOS_DIRECTORY, S_IFDIR};
+use crate::error::{Result, ZipError};
+use crate::spec::attribute::AttributeCompatibility;
 use crate::spec::extra_field::ExtraFieldAsBytes;
 use crate::spec::header::{
-    CentralDirectoryRecord, EndOfCentralDirectoryHeader, Zip64EndOfCentralDirectoryLocator,
+    CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, Zip64EndOfCentralDirectoryLocator,
     Zip64EndOfCentralDirectoryRecord,
 };
+use crate::spec::Compression;
+use crate::string::{StringEncoding, ZipString};
 
 #[cfg(feature = "tokio")]
 use crate::tokio::write::ZipFileWriter as TokioZipFileWriter;
@@ -73,18 +78,34 @@ use crate::tokio::write::ZipFileWriter as TokioZipFileWriter;
 use entry_whole::EntryWholeWriter;
 use io::offset::AsyncOffsetWriter;
 
-use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
-use futures_util::io::{AsyncWrite, AsyncWriteExt};
+use crate::spec::consts::{DATA_DESCRIPTOR_SIGNATURE, NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH};
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt};
+use std::io::SeekFrom;
+
+/// The header IDs of extra fields which are removed from entries when writing in reproducible mode.
+///
+/// These cover the NTFS (0x000A) and UNIX (0x000D) fields from the specification, alongside Info-ZIP's extended
+/// timestamp ('UT'), UNIX ('UX'), UNIX type 2 ('Ux'), and UNIX type 3 ('ux') fields.
+const HOST_SPECIFIC_HEADER_IDS: [u16; 6] = [0x000A, 0x000D, 0x5455, 0x5855, 0x7855, 0x7875];
 
 pub(crate) struct CentralDirectoryEntry {
     pub header: CentralDirectoryRecord,
     pub entry: ZipEntry,
+    /// The offsets of the entry's local file header and data, alongside the length of its (compressed) data.
+    pub lfh_offset: u64,
+    pub data_offset: u64,
+    pub data_length: u64,
+    /// The raw extra field of an entry copied from an existing ZIP file, which is written in place of its parsed
+    /// extra fields so that its central directory header is kept unchanged.
+    pub extra_field_raw: Option<Vec<u8>>,
 }
 
 /// A ZIP file writer which acts over AsyncWrite implementers.
 ///
 /// # Note
-/// - [`ZipFileWriter::close()`] must be called before a stream writer goes out of scope.
+/// - [`ZipFileWriter::close()`] must be called (and awaited) before the writer goes out of scope. As async drop
+///   doesn't exist, nothing is written otherwise, leaving a ZIP file without its central directory which readers
+///   reject. With the `log` feature enabled, a warning is logged if the writer is dropped without being closed.
 pub struct ZipFileWriter<W> {
     pub(crate) writer: AsyncOffsetWriter<W>,
     pub(crate) cd_entries: Vec<CentralDirectoryEntry>,
@@ -92,7 +113,26 @@ pub struct ZipFileWriter<W> {
     force_no_zip64: bool,
     /// Whether to write Zip64 end of directory structs.
     pub(crate) is_zip64: bool,
-    comment_opt: Option<String>,
+    comment_opt: Option<Vec<u8>>,
+    /// Whether to normalise entries' metadata so that identical inputs produce identical output.
+    reproducible: bool,
+    close_guard: CloseGuard,
+}
+
+/// Warns when dropped unless disarmed, so that a [`ZipFileWriter`] which was never closed doesn't go unnoticed.
+///
+/// This is kept separate from the writer itself so that [`ZipFileWriter::close()`] can still move out of its fields.
+struct CloseGuard {
+    closed: bool,
+}
+
+impl Drop for CloseGuard {
+    fn drop(&mut self) {
+        if !self.closed && !std::thread::panicking() {
+            #[cfg(feature = "log")]
+            log::warn!("ZipFileWriter dropped without close() being called, so its ZIP file lacks a central directory");
+        }
+    }
 }
 
 impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
@@ -104,6 +144,8 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
             comment_opt: None,
             is_zip64: false,
             force_no_zip64: false,
+            reproducible: false,
+            close_guard: CloseGuard { closed: false },
         }
     }
 
@@ -121,21 +163,102 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
         self
     }
 
+    /// Operate the ZIP writer in a reproducible mode, where identical inputs produce byte-identical output.
+    ///
+    /// Every entry written has its last modification date & time fixed to 1980-01-01 00:00:00 (the earliest date
+    /// representable), and any extra fields holding timestamps or host-specific data (such as Unix UIDs/GIDs) removed.
+    /// Entries are always written in the order they're provided to this writer, so callers should ensure that order is
+    /// stable (eg. by sorting directory listings).
+    pub fn reproducible(mut self) -> Self {
+        self.reproducible = true;
+        self
+    }
+
     /// Write a new ZIP entry of known size and data.
     pub async fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
-        EntryWholeWriter::from_raw(self, entry.into(), data).write().await
+        let entry = self.prepare_entry(entry.into());
+        EntryWholeWriter::from_raw(self, entry, data).write().await
+    }
+
+    /// Write a new ZIP entry whose data has already been compressed with the entry's compression method.
+    ///
+    /// The data is written verbatim, with the entry's CRC32 value and uncompressed size being used as-is. Combined with
+    /// a reader's `raw_reader()` method, this allows an entry to be copied between ZIP files without decompressing and
+    /// recompressing its data. Encrypted entries aren't supported.
+    pub async fn write_entry_raw<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
+        let entry = self.prepare_entry(entry.into());
+        EntryWholeWriter::from_compressed(self, entry, data)?.write().await
     }
 
     /// Write an entry of unknown size and data via streaming (ie. using a data descriptor).
     /// The generated Local File Header will be invalid, with no compressed size, uncompressed size,
     /// and a null CRC. This might cause problems with the destination reader.
     pub async fn write_entry_stream<E: Into<ZipEntry>>(&mut self, entry: E) -> Result<EntryStreamWriter<'_, W>> {
-        EntryStreamWriter::from_raw(self, entry.into()).await
+        let entry = self.prepare_entry(entry.into());
+        EntryStreamWriter::from_raw(self, entry).await
+    }
+
+    /// Write an entry whose data is copied from the provided reader until EOF, via streaming (see
+    /// [`ZipFileWriter::write_entry_stream()`]).
+    ///
+    /// As the entry's data is compressed and written as it's read, with its CRC32 value and sizes being written within
+    /// a trailing data descriptor, memory usage is bounded regardless of the entry's size. The number of (uncompressed)
+    /// bytes read is returned.
+    pub async fn write_entry_stream_from<E, R>(&mut self, entry: E, reader: R) -> Result<u64>
+    where
+        E: Into<ZipEntry>,
+        R: AsyncRead + Unpin,
+    {
+        let mut entry_writer = self.write_entry_stream(entry).await?;
+        let written = futures_util::io::copy(reader, &mut entry_writer).await?;
+        entry_writer.close().await?;
+
+        Ok(written)
+    }
+
+    /// Write a directory entry with the provided name, appending a trailing slash if not already present.
+    ///
+    /// The entry is Stored with no data, and has both the MS-DOS directory attribute and a Unix directory mode (with
+    /// `0o755` permissions) set within its external file attribute, so that it's recreated as an empty directory when
+    /// extracted.
+    pub async fn write_directory(&mut self, name: ZipString) -> Result<()> {
+        let mut filename = name.as_bytes().to_vec();
+        if !filename.ends_with(b"/") {
+            filename.push(b'/');
+        }
+
+        let entry = ZipEntryBuilder::new(ZipString::new(filename, name.encoding()), Compression::Stored)
+            .external_file_attribute(DOS_DIRECTORY)
+            .unix_mode(S_IFDIR | 0o755);
+
+        self.write_entry_whole(entry, &[]).await
+    }
+
+    /// Normalises an entry's metadata if this writer is operating in reproducible mode.
+    fn prepare_entry(&self, mut entry: ZipEntry) -> ZipEntry {
+        if self.reproducible {
+            entry.last_modification_date = ZipDateTime::DOS_EPOCH;
+            entry.extra_fields.retain(|field| !is_host_specific_extra_field(field));
+        }
+
+        entry
     }
 
     /// Set the ZIP file comment.
+    ///
+    /// The comment is written within the end of central directory record when this writer is closed. It may be at most
+    /// 65,535 bytes long, else [`ZipFileWriter::close()`] returns a [`CommentTooLarge`](ZipError::CommentTooLarge)
+    /// error.
     pub fn comment(&mut self, comment: String) {
-        self.comment_opt = Some(comment);
+        self.comment_opt = Some(comment.into_bytes());
+    }
+
+    /// Sets the ZIP file comment.
+    ///
+    /// See [`ZipFileWriter::comment()`] for more information.
+    pub fn with_comment(mut self, comment: String) -> Self {
+        self.comment_opt = Some(comment.into_bytes());
+        self
     }
 
     /// Returns a mutable reference to the inner writer.
@@ -152,20 +275,35 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
     /// - Writing the end of central directory header.
     /// - Writing the file comment.
     ///
-    /// Failure to call this function before going out of scope would result in a corrupted ZIP file.
+    /// Failure to call this function before going out of scope would result in a corrupted ZIP file. The inner writer
+    /// is returned once the ZIP file is complete.
     pub async fn close(mut self) -> Result<W> {
+        let comment_length: u16 = self
+            .comment_opt
+            .as_ref()
+            .map(|comment| comment.len())
+            .unwrap_or_default()
+            .try_into()
+            .map_err(|_| ZipError::CommentTooLarge)?;
         let cd_offset = self.writer.offset();
 
         for entry in &self.cd_entries {
             self.writer.write_all(&crate::spec::consts::CDH_SIGNATURE.to_le_bytes()).await?;
             self.writer.write_all(&entry.header.as_slice()).await?;
             self.writer.write_all(entry.entry.filename().as_bytes()).await?;
-            self.writer.write_all(&entry.entry.extra_fields().as_bytes()).await?;
+            match &entry.extra_field_raw {
+                Some(extra_field) => self.writer.write_all(extra_field).await?,
+                None => self.writer.write_all(&entry.entry.extra_fields().as_bytes()).await?,
+            }
             self.writer.write_all(entry.entry.comment().as_bytes()).await?;
         }
 
         let central_directory_size = (self.writer.offset() - cd_offset) as u64;
-        let central_directory_size_u32 = if central_directory_size > NON_ZIP64_MAX_SIZE as u64 {
+        let central_directory_size_u32 = if central_directory_size >= NON_ZIP64_MAX_SIZE as u64 {
+            if self.force_no_zip64 {
+                return Err(crate::error::ZipError::Zip64Needed(crate::error::Zip64ErrorCase::LargeFile));
+            }
+            self.is_zip64 = true;
             NON_ZIP64_MAX_SIZE
         } else {
             central_directory_size as u32
@@ -177,7 +315,7 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
             num_entries_in_directory as u16
         };
         let cd_offset = cd_offset as u64;
-        let cd_offset_u32 = if cd_offset > NON_ZIP64_MAX_SIZE as u64 {
+        let cd_offset_u32 = if cd_offset >= NON_ZIP64_MAX_SIZE as u64 {
             if self.force_no_zip64 {
                 return Err(crate::error::ZipError::Zip64Needed(crate::error::Zip64ErrorCase::LargeFile));
             } else {
@@ -194,7 +332,7 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
 
             let eocdr = Zip64EndOfCentralDirectoryRecord {
                 size_of_zip64_end_of_cd_record: 44,
-                version_made_by: crate::spec::version::as_made_by(),
+                version_made_by: crate::spec::version::as_made_by(AttributeCompatibility::Unix),
                 version_needed_to_extract: 46,
                 disk_number: 0,
                 disk_number_start_of_cd: 0,
@@ -222,19 +360,168 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
             num_of_entries: num_entries_in_directory_u16,
             size_cent_dir: central_directory_size_u32,
             cent_dir_offset: cd_offset_u32,
-            file_comm_length: self.comment_opt.as_ref().map(|v| v.len() as u16).unwrap_or_default(),
+            file_comm_length: comment_length,
         };
 
         self.writer.write_all(&crate::spec::consts::EOCDR_SIGNATURE.to_le_bytes()).await?;
         self.writer.write_all(&header.as_slice()).await?;
         if let Some(comment) = self.comment_opt {
-            self.writer.write_all(comment.as_bytes()).await?;
+            self.writer.write_all(&comment).await?;
         }
 
+        self.close_guard.closed = true;
         Ok(self.writer.into_inner())
     }
 }
 
+impl<W: AsyncRead + AsyncWrite + AsyncSeek + Unpin> ZipFileWriter<W> {
+    /// Overwrites the data of a previously-written entry in place, if the provided index is valid.
+    ///
+    /// The entry must be Stored and unencrypted, and the new data must have the same length as its existing data, so
+    /// that nothing written after it needs to move. The entry's CRC32 value is updated within its local file header
+    /// (or data descriptor) and central directory header. [`ZipError::EntryDataLengthMismatch`] is returned if the
+    /// lengths differ.
+    pub async fn overwrite_entry_data(&mut self, index: usize, data: &[u8]) -> Result<()> {
+        let cd_entry = self.cd_entries.get_mut(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if cd_entry.entry.compression() != Compression::Stored {
+            return Err(ZipError::FeatureNotSupported("overwriting the data of entries which aren't Stored"));
+        }
+        if cd_entry.entry.is_encrypted() {
+            return Err(ZipError::FeatureNotSupported("overwriting the data of encrypted entries"));
+        }
+        if cd_entry.data_length != data.len() as u64 {
+            return Err(ZipError::EntryDataLengthMismatch(cd_entry.data_length, data.len() as u64));
+        }
+        let crc = crc32fast::hash(data);
+
+        // Offsets are relative to where this writer started, which needn't be the start of the inner writer.
+        self.writer.flush().await?;
+        let offset = self.writer.offset() as u64;
+        let inner = self.writer.inner_mut();
+        let end = inner.seek(SeekFrom::Current(0)).await?;
+        let start = end - offset;
+
+        // The CRC is stored within the data descriptor which follows the entry's data (after its signature, which is
+        // optional) if one was written, else within the local file header (after its signature and the five
+        // preceding fields).
+        let crc_offset = match cd_entry.header.flags.data_descriptor {
+            true => {
+                let descriptor_offset = cd_entry.data_offset + cd_entry.data_length;
+                let mut buffer = [0; 4];
+                inner.seek(SeekFrom::Start(start + descriptor_offset)).await?;
+                inner.read_exact(&mut buffer).await?;
+
+                match u32::from_le_bytes(buffer) {
+                    DATA_DESCRIPTOR_SIGNATURE => descriptor_offset + SIGNATURE_LENGTH as u64,
+                    _ => descriptor_offset,
+                }
+            }
+            false => cd_entry.lfh_offset + 14,
+        };
+
+        inner.seek(SeekFrom::Start(start + cd_entry.data_offset)).await?;
+        inner.write_all(data).await?;
+        inner.seek(SeekFrom::Start(start + crc_offset)).await?;
+        inner.write_all(&crc.to_le_bytes()).await?;
+        inner.seek(SeekFrom::Start(end)).await?;
+
+        cd_entry.header.crc = crc;
+        cd_entry.entry.crc32 = crc;
+        Ok(())
+    }
+}
+
+impl<W: AsyncRead + AsyncWrite + AsyncSeek + Unpin> ZipFileWriter<W> {
+    /// Constructs a ZIP file writer which appends entries to the existing ZIP file held by a writer.
+    ///
+    /// The existing central directory is read and then overwritten by any new entries, with a fresh central directory
+    /// covering both the existing and new entries being written by [`ZipFileWriter::close()`]. The existing entries'
+    /// data isn't modified, and if the existing ZIP file used zip64 structures, so will the new one. The existing
+    /// ZIP file's comment is kept unless replaced via [`ZipFileWriter::comment()`].
+    ///
+    /// As an [`AsyncWrite`] can't be truncated, any data following the new central directory (eg. if it's shorter
+    /// than the existing one) is left in place.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use async_zip::{Compression, ZipEntryBuilder, base::write::ZipFileWriter};
+    /// # use async_zip::error::Result;
+    /// # use tokio_util::compat::TokioAsyncReadCompatExt;
+    /// #
+    /// # async fn run() -> Result<()> {
+    /// let file = tokio::fs::OpenOptions::new().read(true).write(true).open("./foo.zip").await?;
+    /// let mut writer = ZipFileWriter::append(file.compat()).await?;
+    ///
+    /// writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".into(), Compression::Stored), b"bar").await?;
+    /// writer.close().await?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub async fn append(mut writer: W) -> Result<Self> {
+        let file = crate::base::read::file(&mut writer, Default::default()).await?;
+
+        // Offsets are recorded relative to the start of the ZIP file, which follows any prepended data.
+        let directory_start = file.directory_start.unwrap_or(file.metadata.directory_offset);
+        let prepended = directory_start - file.metadata.directory_offset;
+
+        // The existing central directory headers are read again so that they can be copied through unchanged.
+        let mut headers = Vec::with_capacity(file.entries.len());
+        writer.seek(SeekFrom::Start(directory_start)).await?;
+        for _ in &file.entries {
+            crate::utils::assert_signature(&mut writer, crate::spec::consts::CDH_SIGNATURE).await?;
+            let header = CentralDirectoryRecord::from_reader(&mut writer).await?;
+            let length = header.file_name_length as i64 + header.extra_field_length as i64;
+            writer.seek(SeekFrom::Current(length + header.file_comment_length as i64)).await?;
+            headers.push(header);
+        }
+
+        let mut cd_entries = Vec::with_capacity(file.entries.len());
+        for (stored_entry, header) in file.entries.iter().zip(headers) {
+            let data_offset = stored_entry.data_offset(&mut writer).await? - prepended;
+            cd_entries.push(existing_cd_entry(header, stored_entry, stored_entry.file_offset - prepended, data_offset));
+        }
+
+        writer.seek(SeekFrom::Start(directory_start)).await?;
+        let comment_opt = match file.comment.as_bytes().is_empty() {
+            true => None,
+            false => Some(file.comment.as_bytes().to_vec()),
+        };
+
+        Ok(Self {
+            writer: AsyncOffsetWriter::with_offset(writer, file.metadata.directory_offset as usize),
+            cd_entries,
+            comment_opt,
+            is_zip64: file.zip64,
+            force_no_zip64: false,
+            reproducible: false,
+            close_guard: CloseGuard { closed: false },
+        })
+    }
+}
+
+/// Builds the central directory entry of an entry read from an existing ZIP file from its existing header, given the
+/// offsets (relative to the start of that ZIP file) of its local file header and data.
+///
+/// Only the header's local file header offset is updated (if it isn't stored within the zip64 extended information
+/// extra field), as it differs from the existing one when data was prepended to the ZIP file.
+fn existing_cd_entry(
+    mut header: CentralDirectoryRecord,
+    stored_entry: &StoredZipEntry,
+    lfh_offset: u64,
+    data_offset: u64,
+) -> CentralDirectoryEntry {
+    let entry = stored_entry.entry.clone();
+    if header.lh_offset != NON_ZIP64_MAX_SIZE {
+        header.lh_offset = lfh_offset as u32;
+    }
+
+    let data_length = entry.compressed_size();
+    let extra_field_raw = Some(entry.extra_field_raw.clone());
+    CentralDirectoryEntry { header, entry, lfh_offset, data_offset, data_length, extra_field_raw }
+}
+
 #[cfg(feature = "tokio")]
 impl<W> ZipFileWriter<Compat<W>>
 where
@@ -248,6 +535,35 @@ where
             comment_opt: None,
             is_zip64: false,
             force_no_zip64: false,
+            reproducible: false,
+            close_guard: CloseGuard { closed: false },
         }
     }
 }
+
+/// Returns whether or not the given extra field stores timestamps or data specific to the host which created it.
+fn is_host_specific_extra_field(field: &ExtraField) -> bool {
+    matches!(field, ExtraField::UnknownExtraField(field) if HOST_SPECIFIC_HEADER_IDS.contains(&field.header_id.into()))
+}
+
+/// Returns the general purpose flag bits describing how data this writer compresses with a method is stored.
+///
+/// LZMA streams are always terminated with an end-of-stream marker as the uncompressed size isn't known upfront.
+pub(crate) fn compression_flag_bits(compression: Compression) -> u16 {
+    match compression {
+        #[cfg(feature = "lzma")]
+        Compression::Lzma => crate::spec::consts::LZMA_EOS_FLAG,
+        _ => 0,
+    }
+}
+
+/// Returns whether or not an entry's filename and comment should be flagged as being encoded in UTF-8.
+///
+/// Unless overridden, the flag is set when both are UTF-8 encoded, which covers any non-ASCII name provided as a Rust
+/// string.
+pub(crate) fn utf8_name_flag(entry: &ZipEntry) -> bool {
+    entry.utf8_name.unwrap_or_else(|| {
+        matches!(entry.filename().encoding(), StringEncoding::Utf8)
+            && matches!(entry.comment().encoding(), StringEncoding::Utf8)
+    })
+}
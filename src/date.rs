@@ -1,6 +1,8 @@
 // Copyright (c) 2021-2023 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "chrono")]
+use crate::spec::header::{ExtraField, HeaderId, UnknownExtraField};
 #[cfg(feature = "chrono")]
 use chrono::{DateTime, Datelike, LocalResult, TimeZone, Timelike, Utc};
 
@@ -15,6 +17,9 @@ pub struct ZipDateTime {
 }
 
 impl ZipDateTime {
+    /// The earliest date & time representable (1980-01-01 00:00:00).
+    pub(crate) const DOS_EPOCH: Self = ZipDateTime { date: (1 << 5) | 1, time: 0 };
+
     /// Returns the year of this date & time.
     pub fn year(&self) -> i32 {
         (((self.date & 0xFE00) >> 9) + 1980).into()
@@ -58,8 +63,15 @@ impl ZipDateTime {
     /// Constructs this date & time from chrono's [`DateTime`] representation.
     ///
     /// Note that this requires the `chrono` feature.
+    ///
+    /// As the MS-DOS representation can only hold years from 1980 to 2107, dates & times outside of that range are
+    /// clamped to it.
     #[cfg(feature = "chrono")]
     pub fn from_chrono(dt: &DateTime<Utc>) -> Self {
+        let min = Utc.with_ymd_and_hms(1980, 1, 1, 0, 0, 0).unwrap();
+        let max = Utc.with_ymd_and_hms(2107, 12, 31, 23, 59, 59).unwrap();
+        let dt = &(*dt).clamp(min, max);
+
         let year: u16 = (((dt.date_naive().year() - 1980) << 9) & 0xFE00).try_into().unwrap();
         let month: u16 = ((dt.date_naive().month() << 5) & 0x1E0).try_into().unwrap();
         let day: u16 = (dt.date_naive().day() & 0x1F).try_into().unwrap();
@@ -71,3 +83,154 @@ impl ZipDateTime {
         ZipDateTime { date: year | month | day, time: hour | min | second }
     }
 }
+
+/// The header ID of the NTFS extra field (4.5.5).
+#[cfg(feature = "chrono")]
+const NTFS_HEADER_ID: u16 = 0x000A;
+/// The header ID of the extended timestamp extra field (Info-ZIP's 'UT').
+#[cfg(feature = "chrono")]
+const EXTENDED_TIMESTAMP_HEADER_ID: u16 = 0x5455;
+/// The number of seconds between the NTFS epoch (1601-01-01) and the Unix epoch (1970-01-01).
+#[cfg(feature = "chrono")]
+const NTFS_EPOCH_OFFSET_SECS: i64 = 11_644_473_600;
+
+/// The high-resolution timestamps stored within an NTFS extra field (0x000A).
+#[cfg(feature = "chrono")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct NtfsTimes {
+    /// The last modification date & time.
+    pub modified: DateTime<Utc>,
+    /// The last access date & time.
+    pub accessed: DateTime<Utc>,
+    /// The creation date & time.
+    pub created: DateTime<Utc>,
+}
+
+/// The Unix timestamps stored within an extended timestamp extra field (0x5455).
+///
+/// Each time is only present if indicated by the field's flags and stored within the field. The central directory's
+/// copy of the field typically only holds the modification time, even when the local file header's copy holds more.
+#[cfg(feature = "chrono")]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ExtendedTimestamps {
+    /// The last modification date & time.
+    pub modified: Option<DateTime<Utc>>,
+    /// The last access date & time.
+    pub accessed: Option<DateTime<Utc>>,
+    /// The creation date & time.
+    pub created: Option<DateTime<Utc>>,
+}
+
+/// Returns the highest-resolution last modification date & time stored within the given extra fields, if any.
+///
+/// An NTFS extra field (100ns resolution) is preferred over an extended timestamp extra field (1s resolution).
+#[cfg(feature = "chrono")]
+pub(crate) fn last_modified_from_extra_fields(fields: &[ExtraField]) -> Option<DateTime<Utc>> {
+    ntfs_times_from_extra_fields(fields)
+        .map(|times| times.modified)
+        .or_else(|| extended_timestamps_from_extra_fields(fields).and_then(|times| times.modified))
+}
+
+/// Returns whether or not the given extra field stores a last modification date & time.
+#[cfg(feature = "chrono")]
+pub(crate) fn is_timestamp_extra_field(field: &ExtraField) -> bool {
+    matches!(field, ExtraField::UnknownExtraField(field)
+        if matches!(field.header_id, HeaderId::Other(NTFS_HEADER_ID | EXTENDED_TIMESTAMP_HEADER_ID)))
+}
+
+/// Constructs an extended timestamp extra field holding only the given modification date & time.
+///
+/// This returns None if the date & time can't be represented as a 32-bit Unix timestamp.
+#[cfg(feature = "chrono")]
+pub(crate) fn extended_timestamp_extra_field(modified: &DateTime<Utc>) -> Option<ExtraField> {
+    let secs = i32::try_from(modified.timestamp()).ok()?;
+
+    let mut content = vec![0x01];
+    content.extend_from_slice(&secs.to_le_bytes());
+
+    let header_id = HeaderId::Other(EXTENDED_TIMESTAMP_HEADER_ID);
+    Some(ExtraField::UnknownExtraField(UnknownExtraField { header_id, data_size: 5, content }))
+}
+
+/// Returns the timestamps stored within the NTFS extra field of the given extra fields, if present and well-formed.
+#[cfg(feature = "chrono")]
+pub(crate) fn ntfs_times_from_extra_fields(fields: &[ExtraField]) -> Option<NtfsTimes> {
+    extra_field_content(fields, NTFS_HEADER_ID).and_then(ntfs_times)
+}
+
+/// Returns the timestamps stored within the extended timestamp extra field of the given extra fields, if present.
+#[cfg(feature = "chrono")]
+pub(crate) fn extended_timestamps_from_extra_fields(fields: &[ExtraField]) -> Option<ExtendedTimestamps> {
+    extra_field_content(fields, EXTENDED_TIMESTAMP_HEADER_ID).and_then(extended_timestamps)
+}
+
+/// Returns the content of the first extra field with the given header ID.
+#[cfg(feature = "chrono")]
+fn extra_field_content(fields: &[ExtraField], id: u16) -> Option<&[u8]> {
+    fields.iter().find_map(|field| match field {
+        ExtraField::UnknownExtraField(field) if matches!(field.header_id, HeaderId::Other(other) if other == id) => {
+            Some(&field.content[..])
+        }
+        _ => None,
+    })
+}
+
+/// Parses the timestamps from the content of an NTFS extra field.
+///
+/// The content consists of four reserved bytes followed by a series of tagged attributes, of which tag 0x0001 holds
+/// the modification, access, and creation times as 64-bit counts of 100ns intervals since 1601-01-01.
+#[cfg(feature = "chrono")]
+fn ntfs_times(content: &[u8]) -> Option<NtfsTimes> {
+    let mut attributes = content.get(4..)?;
+
+    while attributes.len() >= 4 {
+        let tag = u16::from_le_bytes([attributes[0], attributes[1]]);
+        let size = u16::from_le_bytes([attributes[2], attributes[3]]) as usize;
+        let data = attributes.get(4..4 + size)?;
+
+        if tag == 0x0001 && size >= 24 {
+            return Some(NtfsTimes {
+                modified: filetime(&data[0..8])?,
+                accessed: filetime(&data[8..16])?,
+                created: filetime(&data[16..24])?,
+            });
+        }
+
+        attributes = &attributes[4 + size..];
+    }
+
+    None
+}
+
+/// Converts a 64-bit count of 100ns intervals since 1601-01-01 (a Windows FILETIME) into a date & time.
+#[cfg(feature = "chrono")]
+fn filetime(data: &[u8]) -> Option<DateTime<Utc>> {
+    let ticks = i64::try_from(u64::from_le_bytes(data.try_into().ok()?)).ok()?;
+    let secs = ticks.div_euclid(10_000_000) - NTFS_EPOCH_OFFSET_SECS;
+    let nanos = (ticks.rem_euclid(10_000_000) * 100) as u32;
+    Utc.timestamp_opt(secs, nanos).single()
+}
+
+/// Parses the timestamps from the content of an extended timestamp extra field.
+///
+/// The content consists of a flags byte followed by the present times as 32-bit Unix timestamps, with bits 0, 1, and 2
+/// of the flags indicating that the modification, access, and creation times are present (in that order). Flagged
+/// times which have been truncated from the content are treated as absent.
+#[cfg(feature = "chrono")]
+fn extended_timestamps(content: &[u8]) -> Option<ExtendedTimestamps> {
+    let (flags, mut data) = content.split_first()?;
+    let mut times = [None; 3];
+
+    for (bit, time) in times.iter_mut().enumerate() {
+        if flags & (1 << bit) == 0 {
+            continue;
+        }
+
+        let Some(secs) = data.get(..4) else { break };
+        *time = Utc.timestamp_opt(i32::from_le_bytes(secs.try_into().ok()?).into(), 0).single();
+        data = &data[4..];
+    }
+
+    let [modified, accessed, created] = times;
+    Some(ExtendedTimestamps { modified, accessed, created })
+}
@@ -1,11 +1,29 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
-use crate::entry::ZipEntry;
+use crate::entry::{ZipEntry, S_IFDIR, S_IFMT, S_IFREG};
 use crate::spec::{attribute::AttributeCompatibility, header::ExtraField, Compression};
 use crate::{date::ZipDateTime, string::ZipString};
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+
 /// A builder for [`ZipEntry`].
+///
+/// Only a filename and compression method are required, with any metadata which isn't set taking a default value (eg.
+/// no comment, no extra fields, zeroed file attributes, and Unix attribute host compatibility).
+///
+/// ```
+/// # use async_zip::{Compression, ZipEntryBuilder};
+/// #
+/// let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored)
+///     .unix_permissions(0o644)
+///     .comment("bar".to_string().into())
+///     .build();
+///
+/// assert_eq!(entry.unix_permissions(), Some(0o644));
+/// assert_eq!(entry.comment().as_str().unwrap(), "bar");
+/// ```
 pub struct ZipEntryBuilder(pub(crate) ZipEntry);
 
 impl From<ZipEntry> for ZipEntryBuilder {
@@ -45,7 +63,10 @@ impl ZipEntryBuilder {
 
     /// Set the deflate compression option.
     ///
-    /// If the compression type isn't deflate, this option has no effect.
+    /// [`DeflateOption::Other`] provides a precise compression level (eg. 0-9 for deflate), whilst the remaining
+    /// options map onto the encoder's default, best, and fastest levels. If unset, the encoder's default level is used.
+    ///
+    /// [`DeflateOption::Other`]: crate::DeflateOption::Other
     #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
     pub fn deflate_option(mut self, option: crate::DeflateOption) -> Self {
         self.0.compression_level = option.into_level();
@@ -64,6 +85,23 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Sets the entry's last modification date & time from chrono's [`DateTime`] representation.
+    ///
+    /// This is stored as both the MS-DOS date & time (which has a resolution of two seconds, and is clamped to the years
+    /// 1980 to 2107) and an extended timestamp extra field (which has a resolution of one second), replacing any
+    /// existing NTFS or extended timestamp extra fields. This allows a fixed timestamp to be pinned, eg. for
+    /// reproducible builds.
+    ///
+    /// Note that this requires the `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn last_modified(mut self, date: DateTime<Utc>) -> Self {
+        self.0.last_modification_date = ZipDateTime::from_chrono(&date);
+        self.0.extra_fields.retain(|field| !crate::date::is_timestamp_extra_field(field));
+        self.0.extra_fields.extend(crate::date::extended_timestamp_extra_field(&date));
+        self.0.extra_field_raw.clear();
+        self
+    }
+
     /// Sets the entry's internal file attribute.
     pub fn internal_file_attribute(mut self, attribute: u16) -> Self {
         self.0.internal_file_attribute = attribute;
@@ -79,6 +117,19 @@ impl ZipEntryBuilder {
     /// Sets the entry's extra field data.
     pub fn extra_fields(mut self, field: Vec<ExtraField>) -> Self {
         self.0.extra_fields = field;
+        self.0.extra_field_raw.clear();
+        self
+    }
+
+    /// Sets whether or not the entry falls back to the Stored compression method when compressing its data doesn't
+    /// reduce its size (defaults to false).
+    ///
+    /// The data is compressed into a temporary buffer, so this is only honoured by
+    /// [`ZipFileWriter::write_entry_whole()`]. Entries written as a stream always use the set compression method.
+    ///
+    /// [`ZipFileWriter::write_entry_whole()`]: crate::base::write::ZipFileWriter::write_entry_whole
+    pub fn auto_stored(mut self, enabled: bool) -> Self {
+        self.0.auto_stored = enabled;
         self
     }
 
@@ -88,6 +139,14 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Sets whether or not the entry's filename and comment are flagged as being encoded in UTF-8 when written.
+    ///
+    /// By default, the flag is set when both the filename and comment are UTF-8 encoded [`ZipString`]s.
+    pub fn utf8_name(mut self, utf8: bool) -> Self {
+        self.0.utf8_name = Some(utf8);
+        self
+    }
+
     /// Sets the entry's Unix permissions mode.
     ///
     /// If the attribute host compatibility isn't set to Unix, this will have no effect.
@@ -98,6 +157,24 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Sets the entry's Unix file mode (eg. `0o755`), also setting the attribute host compatibility to Unix.
+    ///
+    /// The mode is stored within the high 16 bits of the external file attribute. If it has no file type bits set, the
+    /// entry is marked as a regular file (or a directory if its filename ends with a slash), which some extractors
+    /// require in order to apply the permissions.
+    pub fn unix_mode(mut self, mode: u32) -> Self {
+        let file_type = match (mode & S_IFMT, self.0.filename.as_bytes().ends_with(b"/")) {
+            (0, true) => S_IFDIR,
+            (0, false) => S_IFREG,
+            _ => 0,
+        };
+
+        self.0.attribute_compatibility = AttributeCompatibility::Unix;
+        self.0.external_file_attribute =
+            (self.0.external_file_attribute & 0xFFFF) | ((mode | file_type) & 0xFFFF) << 16;
+        self
+    }
+
     /// Consumes this builder and returns a final [`ZipEntry`].
     ///
     /// This is equivalent to:
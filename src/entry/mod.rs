@@ -4,27 +4,46 @@
 pub mod builder;
 
 use std::ops::Deref;
+use std::path::PathBuf;
+use std::sync::OnceLock;
 
 use futures_util::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+
 use crate::entry::builder::ZipEntryBuilder;
 use crate::error::{Result, ZipError};
 use crate::spec::{
     attribute::AttributeCompatibility,
-    consts::LFH_SIGNATURE,
-    header::{ExtraField, LocalFileHeader},
+    consts::{DATA_DESCRIPTOR_SIGNATURE, LFH_LENGTH, LFH_SIGNATURE, SIGNATURE_LENGTH},
+    header::{ExtraField, GeneralPurposeFlag, HeaderId, LocalFileHeader},
     Compression,
 };
-use crate::{
-    string::{StringEncoding, ZipString},
-    ZipDateTime,
-};
+use crate::{string::ZipString, ZipDateTime};
+
+/// The MS-DOS directory attribute, stored within the low byte of the external file attribute.
+pub(crate) const DOS_DIRECTORY: u32 = 0x10;
+/// The bit of the internal file attribute which indicates that the entry is apparently an ASCII/text file.
+const INTERNAL_TEXT: u16 = 0x1;
+/// The header ID of the ASi Unix extra field.
+const ASI_UNIX_HEADER_ID: u16 = 0x756E;
+/// The bit mask of the file type within a Unix mode.
+pub(crate) const S_IFMT: u32 = 0o170000;
+/// The file type of a directory within a Unix mode.
+pub(crate) const S_IFDIR: u32 = 0o040000;
+/// The file type of a regular file within a Unix mode.
+pub(crate) const S_IFREG: u32 = 0o100000;
+/// The file type of a symbolic link within a Unix mode.
+const S_IFLNK: u32 = 0o120000;
 
 /// An immutable store of data about a ZIP entry.
 ///
 /// This type cannot be directly constructed so instead, the [`ZipEntryBuilder`] must be used. Internally this builder
 /// stores a [`ZipEntry`] so conversions between these two types via the [`From`] implementations will be
 /// non-allocating.
+///
+/// Entries may be cloned out of a reader (eg. to build a manifest of an archive which outlives the reader).
 #[derive(Clone, Debug)]
 pub struct ZipEntry {
     pub(crate) filename: ZipString,
@@ -38,14 +57,19 @@ pub struct ZipEntry {
         feature = "deflate64"
     ))]
     pub(crate) compression_level: async_compression::Level,
+    pub(crate) auto_stored: bool,
     pub(crate) crc32: u32,
     pub(crate) uncompressed_size: u64,
     pub(crate) compressed_size: u64,
     pub(crate) attribute_compatibility: AttributeCompatibility,
+    pub(crate) version_needed: Option<u16>,
+    pub(crate) general_purpose_flag: GeneralPurposeFlag,
+    pub(crate) utf8_name: Option<bool>,
     pub(crate) last_modification_date: ZipDateTime,
     pub(crate) internal_file_attribute: u16,
     pub(crate) external_file_attribute: u32,
     pub(crate) extra_fields: Vec<ExtraField>,
+    pub(crate) extra_field_raw: Vec<u8>,
     pub(crate) comment: ZipString,
 }
 
@@ -55,6 +79,57 @@ impl From<ZipEntryBuilder> for ZipEntry {
     }
 }
 
+// The compression level (`async_compression::Level`) doesn't implement `PartialEq`, so it's compared separately.
+impl PartialEq for ZipEntry {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(any(
+            feature = "deflate",
+            feature = "bzip2",
+            feature = "zstd",
+            feature = "lzma",
+            feature = "xz",
+            feature = "deflate64"
+        ))]
+        if !level_eq(self.compression_level, other.compression_level) {
+            return false;
+        }
+
+        self.filename == other.filename
+            && self.compression == other.compression
+            && self.crc32 == other.crc32
+            && self.uncompressed_size == other.uncompressed_size
+            && self.compressed_size == other.compressed_size
+            && self.attribute_compatibility == other.attribute_compatibility
+            && self.version_needed == other.version_needed
+            && self.general_purpose_flag == other.general_purpose_flag
+            && self.utf8_name == other.utf8_name
+            && self.last_modification_date == other.last_modification_date
+            && self.internal_file_attribute == other.internal_file_attribute
+            && self.external_file_attribute == other.external_file_attribute
+            && self.extra_fields == other.extra_fields
+            && self.extra_field_raw == other.extra_field_raw
+            && self.comment == other.comment
+    }
+}
+
+impl Eq for ZipEntry {}
+
+/// Returns whether or not two compression levels are equal.
+#[cfg(any(
+    feature = "deflate",
+    feature = "bzip2",
+    feature = "zstd",
+    feature = "lzma",
+    feature = "xz",
+    feature = "deflate64"
+))]
+fn level_eq(first: async_compression::Level, second: async_compression::Level) -> bool {
+    match (first, second) {
+        (async_compression::Level::Precise(first), async_compression::Level::Precise(second)) => first == second,
+        (first, second) => std::mem::discriminant(&first) == std::mem::discriminant(&second),
+    }
+}
+
 impl ZipEntry {
     pub(crate) fn new(filename: ZipString, compression: Compression) -> Self {
         ZipEntry {
@@ -69,14 +144,24 @@ impl ZipEntry {
                 feature = "deflate64"
             ))]
             compression_level: async_compression::Level::Default,
+            auto_stored: false,
             crc32: 0,
             uncompressed_size: 0,
             compressed_size: 0,
             attribute_compatibility: AttributeCompatibility::Unix,
+            version_needed: None,
+            general_purpose_flag: GeneralPurposeFlag {
+                encrypted: false,
+                data_descriptor: false,
+                filename_unicode: false,
+                other: 0,
+            },
+            utf8_name: None,
             last_modification_date: ZipDateTime::default(),
             internal_file_attribute: 0,
             external_file_attribute: 0,
             extra_fields: Vec::new(),
+            extra_field_raw: Vec::new(),
             comment: String::new().into(),
         }
     }
@@ -91,12 +176,23 @@ impl ZipEntry {
         &self.filename
     }
 
+    /// Returns the entry's raw filename bytes, exactly as stored during ZIP creation.
+    ///
+    /// Unlike [`ZipEntry::filename()`]'s string conversions, this is unaffected by the filename's encoding.
+    pub fn filename_bytes(&self) -> &[u8] {
+        self.filename.as_bytes()
+    }
+
     /// Returns the entry's compression method.
     pub fn compression(&self) -> Compression {
         self.compression
     }
 
     /// Returns the entry's CRC32 value.
+    ///
+    /// For entries read from the central directory, this is the central directory's value, against which the entry's
+    /// data is verified. Opening an entry fails with [`ZipError::HeaderMismatch`] if its local file header stores a
+    /// differing value.
     pub fn crc32(&self) -> u32 {
         self.crc32
     }
@@ -106,21 +202,114 @@ impl ZipEntry {
         self.uncompressed_size
     }
 
+    /// Returns a cheap ETag for the entry's content, derived from its CRC32 value and uncompressed size.
+    ///
+    /// No data is read, so the tag is only as strong as the CRC32 value (which AE-2 encrypted entries don't store). The
+    /// tag is returned unquoted, so should be quoted when used within an HTTP `ETag` header.
+    pub fn etag(&self) -> String {
+        format!("{:08x}-{:x}", self.crc32, self.uncompressed_size)
+    }
+
     /// Returns the entry's compressed size.
     pub fn compressed_size(&self) -> u64 {
         self.compressed_size
     }
 
+    /// Returns the ratio of the entry's compressed size to its uncompressed size.
+    ///
+    /// For example, an entry compressed to a quarter of its original size has a ratio of 0.25. This will return None
+    /// if the entry is empty.
+    pub fn compression_ratio(&self) -> Option<f64> {
+        match self.uncompressed_size {
+            0 => None,
+            uncompressed_size => Some(self.compressed_size as f64 / uncompressed_size as f64),
+        }
+    }
+
+    /// Returns the entry's raw general purpose bit flags.
+    ///
+    /// For entries read from an archive, these are the flags stored within it. Otherwise, they're all unset as the
+    /// flags written are determined during writing.
+    pub fn flags(&self) -> u16 {
+        self.general_purpose_flag.as_u16()
+    }
+
+    /// Returns whether or not the entry's data is encrypted (bit 0 of the general purpose flags).
+    pub fn is_encrypted(&self) -> bool {
+        self.general_purpose_flag.encrypted
+    }
+
+    /// Returns whether or not the entry's sizes and CRC were written within a data descriptor following its data (bit
+    /// 3 of the general purpose flags).
+    pub fn has_data_descriptor(&self) -> bool {
+        self.general_purpose_flag.data_descriptor
+    }
+
+    /// Returns whether or not the entry's filename and comment are flagged as being encoded in UTF-8 (bit 11 of the
+    /// general purpose flags).
+    pub fn is_utf8_name(&self) -> bool {
+        self.general_purpose_flag.filename_unicode
+    }
+
     /// Returns the entry's attribute's host compatibility.
+    ///
+    /// This is the host system the entry was produced on, as stored within the high byte of the central directory's
+    /// "version made by" field. When stream reading, it isn't available and defaults to Unix.
     pub fn attribute_compatibility(&self) -> AttributeCompatibility {
         self.attribute_compatibility
     }
 
+    /// Returns the minimum ZIP specification version needed to extract the entry.
+    ///
+    /// For entries read from an archive, this is the version stored within it. Otherwise, it's the version that will
+    /// be written for the entry.
+    pub fn version_needed(&self) -> u16 {
+        self.version_needed.unwrap_or_else(|| crate::spec::version::as_needed_to_extract(self))
+    }
+
     /// Returns the entry's last modification time & date.
     pub fn last_modification_date(&self) -> &ZipDateTime {
         &self.last_modification_date
     }
 
+    /// Returns the entry's last modification date & time as chrono's [`DateTime`] representation.
+    ///
+    /// If an NTFS or extended timestamp extra field is present, its higher-resolution value is preferred over the
+    /// MS-DOS date & time (see [`ZipEntry::last_modification_date()`]). This will return None if no valid date & time
+    /// could be decoded.
+    ///
+    /// Note that this requires the `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn last_modified(&self) -> Option<DateTime<Utc>> {
+        crate::date::last_modified_from_extra_fields(&self.extra_fields)
+            .or_else(|| self.last_modification_date.as_chrono().single())
+    }
+
+    /// Returns the entry's modification, access, and creation dates & times from its NTFS extra field (0x000A).
+    ///
+    /// These have a resolution of 100ns and are commonly written by archivers on Windows. This will return None if no
+    /// NTFS extra field is present, or if it doesn't hold valid timestamps.
+    ///
+    /// Note that this requires the `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn ntfs_times(&self) -> Option<crate::date::NtfsTimes> {
+        crate::date::ntfs_times_from_extra_fields(&self.extra_fields)
+    }
+
+    /// Returns the entry's Unix modification, access, and creation dates & times from its extended timestamp extra
+    /// field (0x5455), as written by Info-ZIP.
+    ///
+    /// These have a resolution of one second. Only the times included within the field are returned and, as the
+    /// central directory's copy of the field usually only holds the modification time, the others are generally only
+    /// available for entries read via the streaming reader (which reads the local file header). This will return None
+    /// if no extended timestamp extra field is present.
+    ///
+    /// Note that this requires the `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn extended_timestamps(&self) -> Option<crate::date::ExtendedTimestamps> {
+        crate::date::extended_timestamps_from_extra_fields(&self.extra_fields)
+    }
+
     /// Returns the entry's internal file attribute.
     pub fn internal_file_attribute(&self) -> u16 {
         self.internal_file_attribute
@@ -131,12 +320,44 @@ impl ZipEntry {
         self.external_file_attribute
     }
 
+    /// Returns the entry's MS-DOS attributes, stored within the low 16 bits of the external file attribute.
+    ///
+    /// The commonly used bits are read-only (0x01), hidden (0x02), system (0x04), directory (0x10), and archive (0x20).
+    pub fn dos_attributes(&self) -> u16 {
+        self.external_file_attribute as u16
+    }
+
+    /// Returns whether or not the entry is apparently an ASCII/text file, as per its internal file attribute.
+    ///
+    /// Some tools rely on this to decide whether line endings should be converted when extracting.
+    pub fn is_text(&self) -> bool {
+        self.internal_file_attribute & INTERNAL_TEXT != 0
+    }
+
     /// Returns the entry's extra field data.
     pub fn extra_fields(&self) -> &[ExtraField] {
         &self.extra_fields
     }
 
+    /// Returns the entry's extra field data exactly as it was stored within the header the entry was read from.
+    ///
+    /// This allows extra fields this crate doesn't understand (eg. ones with application-specific header IDs) to be
+    /// parsed by the caller. This is empty for entries which weren't read from a ZIP file, and is cleared if the
+    /// entry's extra fields are replaced via [`ZipEntryBuilder`].
+    pub fn extra_field_raw(&self) -> &[u8] {
+        &self.extra_field_raw
+    }
+
+    /// Returns an iterator over the header IDs and data of the fields within [`ZipEntry::extra_field_raw()`].
+    ///
+    /// Iteration stops at the first field whose data would extend beyond the end of the extra field.
+    pub fn raw_extra_fields(&self) -> impl Iterator<Item = (u16, &[u8])> {
+        crate::spec::extra_field::raw_extra_fields(&self.extra_field_raw)
+    }
+
     /// Returns the entry's file comment.
+    ///
+    /// The comment follows the same language encoding flag as the filename, so is decoded in the same way.
     pub fn comment(&self) -> &ZipString {
         &self.comment
     }
@@ -153,31 +374,151 @@ impl ZipEntry {
         Some(((self.external_file_attribute) >> 16) as u16)
     }
 
+    /// Returns the entry's Unix file mode (including the file type bits).
+    ///
+    /// This is read from the high 16 bits of the external file attribute for entries whose attribute host
+    /// compatibility is listed as Unix, falling back to the ASi Unix extra field (0x756E) when present. Note that the
+    /// Info-ZIP Unix extra fields (0x7875 & 0x000D) only carry ownership & timestamps, so never provide a mode.
+    ///
+    /// This will return None if no mode was stored for the entry.
+    pub fn unix_mode(&self) -> Option<u32> {
+        if matches!(self.attribute_compatibility, AttributeCompatibility::Unix) {
+            let mode = self.external_file_attribute >> 16;
+
+            if mode != 0 {
+                return Some(mode);
+            }
+        }
+
+        self.extra_fields.iter().find_map(|field| match field {
+            // The ASi Unix extra field's content begins with a CRC of the remaining data, followed by the mode.
+            ExtraField::UnknownExtraField(field) if matches!(field.header_id, HeaderId::Other(ASI_UNIX_HEADER_ID)) => {
+                Some(u16::from_le_bytes(field.content.get(4..6)?.try_into().ok()?).into())
+            }
+            _ => None,
+        })
+    }
+
+    /// Returns whether or not the entry represents a symbolic link, as per the file type bits of its Unix mode.
+    ///
+    /// The data of such an entry is the link's target path, so an entry reader will yield that path as its content.
+    pub fn is_symlink(&self) -> bool {
+        self.unix_mode().is_some_and(|mode| mode & S_IFMT == S_IFLNK)
+    }
+
     /// Returns whether or not the entry represents a directory.
+    ///
+    /// This only checks for the trailing slash convention (see [`ZipEntry::is_dir()`]).
     pub fn dir(&self) -> Result<bool> {
         Ok(self.filename.as_str()?.ends_with('/'))
     }
+
+    /// Returns an iterator over the components of the entry's filename.
+    ///
+    /// Both forward slashes and backslashes are treated as separators, and empty or `.` components are skipped. Parent
+    /// directory (`..`) components are yielded as-is; see [`ZipEntry::sanitized_path()`] for a path which is safe to
+    /// join onto an extraction directory. No components are yielded if the filename isn't valid UTF-8.
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.filename
+            .as_str()
+            .unwrap_or_default()
+            .split(['/', '\\'])
+            .filter(|component| !component.is_empty() && *component != ".")
+    }
+
+    /// Returns the entry's filename as a relative path which is guaranteed to stay within any directory it's joined to.
+    ///
+    /// The path is built from [`ZipEntry::components()`]. An [`ZipError::UnsafePath`] error is returned if the filename
+    /// is absolute, has a drive or device prefix, or contains any parent directory (`..`) components.
+    pub fn sanitized_path(&self) -> Result<PathBuf> {
+        let filename = self.filename.as_str()?;
+        let unsafe_path = || ZipError::UnsafePath(filename.to_owned());
+
+        if filename.starts_with(['/', '\\']) {
+            return Err(unsafe_path());
+        }
+
+        let mut path = PathBuf::new();
+        for component in self.components() {
+            if component == ".." || component.contains(':') {
+                return Err(unsafe_path());
+            }
+            path.push(component);
+        }
+
+        Ok(path)
+    }
+
+    /// Returns whether or not the entry represents a directory.
+    ///
+    /// An entry is treated as a directory if its filename ends with a slash (or a backslash, as written by some Windows
+    /// tools), or if its external file attribute marks it as one (via either the MS-DOS directory attribute or the file
+    /// type bits of its Unix mode). Directory entries have no data.
+    pub fn is_dir(&self) -> bool {
+        self.filename_bytes().ends_with(b"/")
+            || self.filename_bytes().ends_with(b"\\")
+            || self.external_file_attribute & DOS_DIRECTORY != 0
+            || self.unix_mode().is_some_and(|mode| mode & S_IFMT == S_IFDIR)
+    }
 }
 
 /// An immutable store of data about how a ZIP entry is stored within a specific archive.
 ///
 /// Besides storing archive independent information like the size and timestamp it can also be used to query
 /// information about how the entry is stored in an archive.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct StoredZipEntry {
     pub(crate) entry: ZipEntry,
-    // pub(crate) general_purpose_flag: GeneralPurposeFlag,
     pub(crate) file_offset: u64,
+    pub(crate) data_offset: OnceLock<u64>,
 }
 
 impl StoredZipEntry {
-    /// Returns the offset in bytes to where the header of the entry starts.
+    /// Returns whether or not the entry's data is encrypted.
+    pub fn encrypted(&self) -> bool {
+        self.entry.is_encrypted()
+    }
+
+    /// Returns the number of bytes of data an entry reader should read before reaching EOF.
+    ///
+    /// Archives produced by some streaming writers don't record the sizes of entries written with a data descriptor
+    /// within the central directory. For compression methods whose data is self-terminating, we instead read until the
+    /// end of the compressed stream.
+    pub(crate) fn data_size(&self) -> u64 {
+        match (self.entry.compressed_size, self.general_purpose_flag.data_descriptor, self.entry.compression) {
+            (0, true, compression) if compression != Compression::Stored => u64::MAX,
+            (size, _, _) => size,
+        }
+    }
+
+    /// Returns whether or not the entry is known to hold no data (ie. its uncompressed size is zero).
+    ///
+    /// Entries whose sizes weren't recorded within the central directory (see [`StoredZipEntry::data_size()`]) aren't
+    /// known to be empty.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entry.uncompressed_size == 0 && self.data_size() != u64::MAX
+    }
+
+    /// Returns the offset in bytes to where the local file header of the entry starts.
+    ///
+    /// This is the relative offset stored within the central directory (or its zip64 extended information extra
+    /// field), shifted by the length of any data prepended to the ZIP file so that it's relative to the start of the
+    /// reader. As the offset describes where the entry is stored within a specific ZIP file, it's held here rather than
+    /// on [`ZipEntry`].
     pub fn header_offset(&self) -> u64 {
         self.file_offset
     }
 
-    /// Seek to the offset in bytes where the data of the entry starts.
-    pub(crate) async fn seek_to_data_offset<R: AsyncRead + AsyncSeek + Unpin>(&self, mut reader: &mut R) -> Result<()> {
+    /// Returns the offset in bytes to where the data of the entry starts.
+    ///
+    /// The local file header is read on the first call and the result cached for subsequent calls. The local extra
+    /// field may differ in length from the one within the central directory, so the lengths stored in the local file
+    /// header must be used.
+    pub(crate) async fn data_offset<R: AsyncRead + AsyncSeek + Unpin>(&self, mut reader: &mut R) -> Result<u64> {
+        if let Some(offset) = self.data_offset.get() {
+            return Ok(*offset);
+        }
+
         // Seek to the header
         reader.seek(SeekFrom::Start(self.file_offset)).await?;
 
@@ -193,17 +534,93 @@ impl StoredZipEntry {
             actual => return Err(ZipError::UnexpectedHeaderError(actual, LFH_SIGNATURE)),
         };
 
-        // Skip the local file header and trailing data
         let header = LocalFileHeader::from_reader(&mut reader).await?;
-        let _filename =
-            crate::base::read::io::read_string(&mut reader, header.file_name_length.into(), StringEncoding::Utf8)
-                .await?;
-        let _extra_field = crate::base::read::io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
 
+        // Entries written with a data descriptor store a zeroed CRC32 value within their local file header. Otherwise,
+        // a differing value indicates a corrupt or tampered header, so which value is authoritative can't be known.
+        if !header.flags.data_descriptor && header.crc != self.entry.crc32 {
+            let (local, central) = (header.crc.into(), self.entry.crc32.into());
+            return Err(ZipError::HeaderMismatch { field: "CRC32 value", local, central });
+        }
+
+        let trailing_length = header.file_name_length as u64 + header.extra_field_length as u64;
+        let offset = self.file_offset + (SIGNATURE_LENGTH + LFH_LENGTH) as u64 + trailing_length;
+
+        Ok(*self.data_offset.get_or_init(|| offset))
+    }
+
+    /// Returns the offsets in bytes to where the compressed data of the entry starts and ends (exclusive).
+    pub(crate) async fn compressed_data_range<R>(&self, reader: &mut R) -> Result<(u64, u64)>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+    {
+        let start = self.data_offset(reader).await?;
+        Ok((start, start + self.entry.compressed_size))
+    }
+
+    /// Returns the offsets in bytes to where the data descriptor following the entry's data starts and ends
+    /// (exclusive), if the entry has one.
+    ///
+    /// The descriptor's signature is optional (4.3.9.3) so is checked for, and its sizes are eight bytes each when the
+    /// entry has a zip64 extended information extra field (4.3.9.2), else four bytes each.
+    pub(crate) async fn data_descriptor_range<R>(&self, reader: &mut R) -> Result<Option<(u64, u64)>>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+    {
+        if !self.entry.has_data_descriptor() {
+            return Ok(None);
+        }
+
+        let (_, start) = self.compressed_data_range(reader).await?;
+        reader.seek(SeekFrom::Start(start)).await?;
+
+        let mut buffer = [0; 4];
+        reader.read_exact(&mut buffer).await?;
+
+        let signature_length = match u32::from_le_bytes(buffer) {
+            DATA_DESCRIPTOR_SIGNATURE => SIGNATURE_LENGTH as u64,
+            _ => 0,
+        };
+        let sizes_length = match crate::base::read::get_zip64_extra_field(&self.entry.extra_fields) {
+            Some(_) => 16,
+            None => 8,
+        };
+
+        Ok(Some((start, start + signature_length + 4 + sizes_length)))
+    }
+
+    /// Seek to the offset in bytes where the data of the entry starts.
+    ///
+    /// An error is returned if the entry's data would extend beyond the end of the reader (eg. as the file was
+    /// truncated), rather than later yielding incomplete data.
+    pub(crate) async fn seek_to_data_offset<R: AsyncRead + AsyncSeek + Unpin>(&self, reader: &mut R) -> Result<()> {
+        let length = reader.seek(SeekFrom::End(0)).await?;
+        let data_offset = self.data_offset(reader).await?;
+
+        // The size isn't known ahead of time when reading until the end of a self-terminating compressed stream.
+        let data_end = match self.data_size() {
+            u64::MAX => data_offset,
+            size => data_offset.saturating_add(size),
+        };
+
+        if data_end > length {
+            return Err(ZipError::TruncatedEntry(data_end, length));
+        }
+
+        reader.seek(SeekFrom::Start(data_offset)).await?;
         Ok(())
     }
 }
 
+// The cached data offset is excluded as it doesn't affect how the entry is stored.
+impl PartialEq for StoredZipEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.entry == other.entry && self.file_offset == other.file_offset
+    }
+}
+
+impl Eq for StoredZipEntry {}
+
 impl Deref for StoredZipEntry {
     type Target = ZipEntry;
 
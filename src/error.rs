@@ -4,11 +4,13 @@
 //! A module which holds relevant error reporting structures/types.
 
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// A Result type alias over ZipError to minimise repetition.
 pub type Result<V> = std::result::Result<V, ZipError>;
 
+/// The reason why ZIP64 was needed when writing an archive with ZIP64 disabled.
 #[derive(Debug, PartialEq, Eq)]
 pub enum Zip64ErrorCase {
     TooManyFiles,
@@ -25,6 +27,8 @@ impl Display for Zip64ErrorCase {
 }
 
 /// An enum of possible errors and their descriptions.
+///
+/// This implements [`std::error::Error`], with wrapped IO errors being exposed via [`std::error::Error::source()`].
 #[non_exhaustive]
 #[derive(Debug, Error)]
 pub enum ZipError {
@@ -49,19 +53,59 @@ pub enum ZipError {
     #[error("attempted to convert non-UTF8 bytes to a string/str")]
     StringNotUtf8,
 
-    #[error("unable to locate the end of central directory record")]
-    UnableToLocateEOCDR,
+    #[error("not a ZIP file as no end of central directory record could be located")]
+    NotAZipFile,
+    #[error("the ZIP file ended unexpectedly, so may have been truncated")]
+    UnexpectedEof,
+    #[error("spanned/split archives are not supported")]
+    SpannedArchiveUnsupported,
     #[error("extra field size was indicated to be {0} but only {1} bytes remain")]
     InvalidExtraFieldHeader(u16, usize),
     #[error("zip64 extended information field was incomplete")]
     Zip64ExtendedFieldIncomplete,
+    #[error("entry data ends at offset {0} but the file is only {1} bytes long")]
+    TruncatedEntry(u64, u64),
 
     #[error("an upstream reader returned an error: {0}")]
-    UpstreamReadError(#[from] std::io::Error),
-    #[error("a computed CRC32 value did not match the expected value")]
-    CRC32CheckError,
+    UpstreamReadError(#[source] std::io::Error),
+    #[error("failed to open '{}': {source}", .path.display())]
+    FileOpenError { path: PathBuf, source: std::io::Error },
+    #[error("a computed CRC32 value ({actual:#010x}) did not match the expected value ({expected:#010x})")]
+    CRC32CheckError { expected: u32, actual: u32 },
     #[error("entry index was out of bounds")]
     EntryIndexOutOfBounds,
+    #[error("no entry with the provided filename was found")]
+    EntryNotFound,
+    #[error("the provided entry doesn't belong to this ZIP file")]
+    ForeignEntry,
+    #[error("entry's uncompressed data exceeds the limit of {0} bytes")]
+    EntryTooLarge(u64),
+    #[error("the provided data's length ({1} bytes) doesn't match that of the entry's existing data ({0} bytes)")]
+    EntryDataLengthMismatch(u64, u64),
+    #[error("entry's data expanded by more than the limit of {0}:1 when decompressed")]
+    SuspiciousExpansion(u64),
     #[error("Encountered an unexpected header (actual: {0:#x}, expected: {1:#x}).")]
     UnexpectedHeaderError(u32, u32),
+    #[error("the local file header's {field} ({local:#x}) doesn't match the central directory's ({central:#x})")]
+    HeaderMismatch { field: &'static str, local: u64, central: u64 },
+    #[error("entry '{0}' is encrypted but no password was provided")]
+    PasswordRequired(String),
+    #[error("the provided password was incorrect")]
+    WrongPassword,
+    #[error("entry {index} ('{filename}') failed validation: {source}")]
+    EntryValidationFailed { index: usize, filename: String, source: Box<ZipError> },
+    #[error("entry filename would resolve to a path outside of the extraction directory: '{0}'")]
+    UnsafePath(String),
+}
+
+/// Errors which originate from this crate but are surfaced via [`std::io::Error`] (eg. from within an entry reader's
+/// [`AsyncRead`](futures_util::io::AsyncRead) implementation) are unwrapped rather than treated as upstream errors.
+impl From<std::io::Error> for ZipError {
+    fn from(error: std::io::Error) -> Self {
+        if error.get_ref().is_some_and(|inner| inner.is::<ZipError>()) {
+            return *error.into_inner().and_then(|inner| inner.downcast().ok()).expect("inner error is a ZipError");
+        }
+
+        ZipError::UpstreamReadError(error)
+    }
 }
@@ -14,7 +14,7 @@ impl From<ZipFile> for ZipFileBuilder {
 
 impl Default for ZipFileBuilder {
     fn default() -> Self {
-        ZipFileBuilder(ZipFile { entries: Vec::new(), zip64: false, comment: String::new().into() })
+        ZipFileBuilder(ZipFile::new(Vec::new(), false, String::new().into()))
     }
 }
 
@@ -6,12 +6,66 @@ pub(crate) mod builder;
 use crate::{entry::StoredZipEntry, string::ZipString};
 use builder::ZipFileBuilder;
 
+use std::collections::HashMap;
+use std::ops::Index;
+
 /// An immutable store of data about a ZIP file.
 #[derive(Clone)]
 pub struct ZipFile {
     pub(crate) entries: Vec<StoredZipEntry>,
     pub(crate) zip64: bool,
     pub(crate) comment: ZipString,
+    pub(crate) name_index: HashMap<String, usize>,
+    /// Raw filenames which differ from (or couldn't be decoded as) their entry's decoded filename.
+    pub(crate) raw_index: HashMap<Vec<u8>, usize>,
+    /// Raw filenames with any backslashes normalised to forward slashes.
+    pub(crate) separator_index: HashMap<Vec<u8>, usize>,
+    pub(crate) metadata: ZipFileMetadata,
+    /// The offset of the start of the central directory within the reader, if this ZIP file was read from one.
+    pub(crate) directory_start: Option<u64>,
+}
+
+/// The structural metadata of a ZIP file, as stored within its end of central directory record.
+///
+/// Values stored within the zip64 end of central directory record take precedence where present.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ZipFileMetadata {
+    pub(crate) disk_number: u32,
+    pub(crate) entries_on_disk: u64,
+    pub(crate) total_entries: u64,
+    pub(crate) directory_size: u64,
+    pub(crate) directory_offset: u64,
+}
+
+impl ZipFileMetadata {
+    /// Returns the number of the disk on which the end of central directory record is stored.
+    ///
+    /// As spanned archives aren't supported, this is always zero for ZIP files which have been read.
+    pub fn disk_number(&self) -> u32 {
+        self.disk_number
+    }
+
+    /// Returns the number of entries within the central directory on this disk.
+    pub fn entries_on_disk(&self) -> u64 {
+        self.entries_on_disk
+    }
+
+    /// Returns the total number of entries within the central directory.
+    pub fn total_entries(&self) -> u64 {
+        self.total_entries
+    }
+
+    /// Returns the size of the central directory in bytes.
+    pub fn directory_size(&self) -> u64 {
+        self.directory_size
+    }
+
+    /// Returns the offset of the start of the central directory, as recorded.
+    ///
+    /// This offset is relative to the start of the ZIP file, so excludes any data prepended to it.
+    pub fn directory_offset(&self) -> u64 {
+        self.directory_offset
+    }
 }
 
 impl From<ZipFileBuilder> for ZipFile {
@@ -21,11 +75,197 @@ impl From<ZipFileBuilder> for ZipFile {
 }
 
 impl ZipFile {
+    /// Constructs a new ZIP file from its parts, building indices of its entries' filenames.
+    pub(crate) fn new(entries: Vec<StoredZipEntry>, zip64: bool, comment: ZipString) -> Self {
+        let mut name_index = HashMap::with_capacity(entries.len());
+        let mut raw_index = HashMap::new();
+        let mut separator_index = HashMap::with_capacity(entries.len());
+
+        // Each index maps to the first entry with a given filename, as ZIP files may contain duplicates.
+        for (index, entry) in entries.iter().enumerate() {
+            let raw = entry.filename_bytes();
+
+            if let Ok(filename) = entry.filename().as_str() {
+                name_index.entry(filename.to_owned()).or_insert(index);
+            }
+            if entry.filename().as_str().map_or(true, |filename| filename.as_bytes() != raw) {
+                raw_index.entry(raw.to_vec()).or_insert(index);
+            }
+            separator_index.entry(normalise_separators(raw)).or_insert(index);
+        }
+
+        Self {
+            entries,
+            zip64,
+            comment,
+            name_index,
+            raw_index,
+            separator_index,
+            metadata: ZipFileMetadata::default(),
+            directory_start: None,
+        }
+    }
+
     /// Returns a list of this ZIP file's entries.
     pub fn entries(&self) -> &[StoredZipEntry] {
         &self.entries
     }
 
+    /// Returns the number of entries within this ZIP file.
+    ///
+    /// ```
+    /// # use async_zip::base::read::mem::ZipFileReader;
+    /// # use async_zip::base::write::ZipFileWriter;
+    /// # use async_zip::{Compression, ZipEntryBuilder};
+    /// #
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let mut writer = ZipFileWriter::new(Vec::new());
+    /// writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await?;
+    /// let reader = ZipFileReader::new(writer.close().await?).await?;
+    /// let file = reader.file();
+    ///
+    /// assert_eq!(file.len(), 1);
+    /// assert!(!file.is_empty());
+    /// assert_eq!(file.get(0).unwrap().filename().as_str()?, "foo.txt");
+    /// assert!(file.get(1).is_none());
+    /// # Ok::<(), async_zip::error::ZipError>(())
+    /// # }).unwrap();
+    /// ```
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether or not this ZIP file contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entry at the provided index, if valid.
+    pub fn get(&self, index: usize) -> Option<&StoredZipEntry> {
+        self.entries.get(index)
+    }
+
+    /// Returns the index of the first entry with the provided filename alongside the entry itself, if present.
+    ///
+    /// The filename is matched against both the entry's decoded filename and its raw filename bytes (which differ for
+    /// entries whose filenames were decoded from IBM Code Page 437), so either a `&str` or `&[u8]` can be provided.
+    ///
+    /// Both are looked up via indices built when the ZIP file is read, so lookups (incl. those which find no entry)
+    /// don't scale with the number of entries. As ZIP files may contain multiple entries with the same filename, the
+    /// first of those entries is always returned.
+    ///
+    /// Some Windows tools write backslashes as path separators, so if no entry matches exactly, forward slashes and
+    /// backslashes are then treated as equivalent (eg. `dir/file.txt` matches an entry named `dir\file.txt`). The raw
+    /// filename is still available via [`ZipEntry::filename_bytes()`](crate::ZipEntry::filename_bytes).
+    pub fn entry(&self, filename: impl AsRef<[u8]>) -> Option<(usize, &StoredZipEntry)> {
+        let filename = filename.as_ref();
+        let indexed = std::str::from_utf8(filename).ok().and_then(|filename| self.name_index.get(filename));
+
+        let index = indexed
+            .or_else(|| self.raw_index.get(filename))
+            .or_else(|| self.separator_index.get(&normalise_separators(filename)))?;
+
+        Some((*index, &self.entries[*index]))
+    }
+
+    /// Returns the index of the provided entry, if it belongs to this ZIP file.
+    ///
+    /// An entry belongs to this ZIP file if it's a reference to (or a clone of) one of its entries, ie. its information
+    /// and header offset match those of the entry at the returned index.
+    pub fn index_of(&self, entry: &StoredZipEntry) -> Option<usize> {
+        let mut entries = self.entries.iter();
+        entries
+            .clone()
+            .position(|candidate| std::ptr::eq(candidate, entry))
+            .or_else(|| entries.position(|candidate| candidate == entry))
+    }
+
+    /// Returns the index mapping each entry's filename to the index of its entry.
+    ///
+    /// Only filenames which are valid UTF-8 are included. Where multiple entries share the same filename, the index
+    /// of the first of those entries is mapped.
+    pub fn name_index(&self) -> &HashMap<String, usize> {
+        &self.name_index
+    }
+
+    /// Returns an iterator over the entries whose filenames start with the provided prefix, alongside their indices.
+    ///
+    /// Entries are yielded in the order they're stored within the central directory. The prefix is matched against
+    /// the entry's decoded filename, falling back to its raw filename bytes if it isn't valid UTF-8.
+    pub fn entries_matching<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (usize, &'a StoredZipEntry)> {
+        self.entries.iter().enumerate().filter(move |(_, entry)| match entry.filename().as_str() {
+            Ok(filename) => filename.starts_with(prefix),
+            Err(_) => entry.filename_bytes().starts_with(prefix.as_bytes()),
+        })
+    }
+
+    /// Returns the entry whose stored region contains the provided offset, alongside its index.
+    ///
+    /// An entry's region spans from the start of its local file header up to the start of the next entry's local file
+    /// header (or, for the final entry, the start of the central directory), so includes its data and any data
+    /// descriptor. Offsets are relative to the start of the reader, as with [`StoredZipEntry::header_offset()`].
+    pub fn entry_at_offset(&self, offset: u64) -> Option<(usize, &StoredZipEntry)> {
+        let (index, entry) = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.header_offset() <= offset)
+            .min_by_key(|(_, entry)| offset - entry.header_offset())?;
+
+        let end = self
+            .entries
+            .iter()
+            .map(StoredZipEntry::header_offset)
+            .filter(|&start| start > entry.header_offset())
+            .min()
+            .or(self.directory_start.filter(|&start| start > entry.header_offset()))
+            .unwrap_or(u64::MAX);
+
+        (offset < end).then_some((index, entry))
+    }
+
+    /// Returns the indices of this ZIP file's encrypted entries.
+    ///
+    /// Entries are detected as encrypted via either the encryption bit of their general purpose flags or the presence
+    /// of a WinZip AES extra field. As this information is stored within the central directory, no password is needed.
+    pub fn encrypted_entries(&self) -> Vec<usize> {
+        let encrypted = |entry: &StoredZipEntry| {
+            entry.is_encrypted() || crate::base::read::get_aes_extra_field(entry.extra_fields()).is_some()
+        };
+
+        self.entries.iter().enumerate().filter(|(_, entry)| encrypted(entry)).map(|(index, _)| index).collect()
+    }
+
+    /// Returns the indices of this ZIP file's entries, sorted by where each entry is stored within the ZIP file.
+    ///
+    /// The central directory needn't list entries in the order their data is stored, so reading entries in this order
+    /// (eg. when extracting from a spinning disk) avoids seeking backwards between them. As an entry's data directly
+    /// follows its local file header, entries are sorted via [`StoredZipEntry::header_offset()`] without any IO.
+    pub fn entries_in_disk_order(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.entries.len()).collect();
+        indices.sort_by_key(|&index| self.entries[index].header_offset());
+        indices
+    }
+
+    /// Returns the sum of the uncompressed sizes of this ZIP file's entries.
+    ///
+    /// The sum saturates at [`u64::MAX`] rather than overflowing, so may be inaccurate for malformed ZIP files.
+    pub fn total_uncompressed_size(&self) -> u64 {
+        self.entries.iter().fold(0, |total, entry| total.saturating_add(entry.uncompressed_size()))
+    }
+
+    /// Returns the sum of the compressed sizes of this ZIP file's entries.
+    ///
+    /// The sum saturates at [`u64::MAX`] rather than overflowing, so may be inaccurate for malformed ZIP files.
+    pub fn total_compressed_size(&self) -> u64 {
+        self.entries.iter().fold(0, |total, entry| total.saturating_add(entry.compressed_size()))
+    }
+
+    /// Returns this ZIP file's structural metadata.
+    pub fn metadata(&self) -> &ZipFileMetadata {
+        &self.metadata
+    }
+
     /// Returns this ZIP file's trailing comment.
     pub fn comment(&self) -> &ZipString {
         &self.comment
@@ -36,3 +276,106 @@ impl ZipFile {
         self.zip64
     }
 }
+
+/// Generates the accessors of a ZIP reader which forward to the [`ZipFile`] it holds, for use within the
+/// reader's `impl` block.
+///
+/// The reader must provide a `file(&self) -> &ZipFile` method.
+macro_rules! zip_file_accessors {
+    () => {
+        /// Returns the number of entries within this ZIP file.
+        pub fn len(&self) -> usize {
+            self.file().len()
+        }
+
+        /// Returns whether or not this ZIP file contains no entries.
+        pub fn is_empty(&self) -> bool {
+            self.file().is_empty()
+        }
+
+        /// Returns the entry at the provided index, if valid.
+        pub fn get(&self, index: usize) -> Option<&$crate::entry::StoredZipEntry> {
+            self.file().get(index)
+        }
+
+        /// Returns this ZIP file's structural metadata.
+        pub fn metadata(&self) -> &$crate::file::ZipFileMetadata {
+            self.file().metadata()
+        }
+
+        /// Returns the indices of this ZIP file's encrypted entries.
+        ///
+        /// See [`ZipFile::encrypted_entries()`](crate::ZipFile::encrypted_entries) for more information.
+        pub fn encrypted_entries(&self) -> Vec<usize> {
+            self.file().encrypted_entries()
+        }
+
+        /// Returns the indices of this ZIP file's entries, sorted by where each entry is stored within the ZIP file.
+        ///
+        /// See [`ZipFile::entries_in_disk_order()`](crate::ZipFile::entries_in_disk_order) for more information.
+        pub fn entries_in_disk_order(&self) -> Vec<usize> {
+            self.file().entries_in_disk_order()
+        }
+
+        /// Returns an iterator over the entries whose filenames start with the provided prefix, alongside their
+        /// indices.
+        ///
+        /// See [`ZipFile::entries_matching()`](crate::ZipFile::entries_matching) for more information.
+        pub fn entries_matching<'a>(
+            &'a self,
+            prefix: &'a str,
+        ) -> impl Iterator<Item = (usize, &'a $crate::entry::StoredZipEntry)> {
+            self.file().entries_matching(prefix)
+        }
+
+        /// Returns the entry whose stored region contains the provided offset, alongside its index.
+        ///
+        /// See [`ZipFile::entry_at_offset()`](crate::ZipFile::entry_at_offset) for more information.
+        pub fn entry_at_offset(&self, offset: u64) -> Option<(usize, &$crate::entry::StoredZipEntry)> {
+            self.file().entry_at_offset(offset)
+        }
+
+        /// Returns the index mapping each entry's filename to the index of its entry.
+        ///
+        /// See [`ZipFile::name_index()`](crate::ZipFile::name_index) for more information.
+        pub fn name_index(&self) -> &std::collections::HashMap<String, usize> {
+            self.file().name_index()
+        }
+
+        /// Returns the ZIP file's comment.
+        pub fn comment(&self) -> &$crate::string::ZipString {
+            self.file().comment()
+        }
+
+        /// Returns a cheap ETag for the content of an entry, if the provided index is valid.
+        ///
+        /// See [`ZipEntry::etag()`](crate::ZipEntry::etag) for more information.
+        pub fn entry_etag(&self, index: usize) -> $crate::error::Result<String> {
+            self.file()
+                .entries
+                .get(index)
+                .map(|entry| entry.etag())
+                .ok_or($crate::error::ZipError::EntryIndexOutOfBounds)
+        }
+    };
+}
+
+pub(crate) use zip_file_accessors;
+
+impl Index<usize> for ZipFile {
+    type Output = StoredZipEntry;
+
+    /// Returns the entry at the provided index.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds. See [`ZipFile::get()`] for a non-panicking alternative.
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.entries[index]
+    }
+}
+
+/// Returns the provided filename with any backslashes replaced by forward slashes, so that filenames which only differ
+/// in their path separators are equal.
+fn normalise_separators(filename: &[u8]) -> Vec<u8> {
+    filename.iter().map(|&byte| if byte == b'\\' { b'/' } else { byte }).collect()
+}
@@ -9,7 +9,7 @@
 //! ## Features
 //! - A base implementation atop `futures`'s IO traits.
 //! - An extended implementation atop `tokio`'s IO traits.
-//! - Support for Stored, Deflate, bzip2, LZMA, zstd, and xz compression methods.
+//! - Support for Stored, Deflate, bzip2, LZMA, zstd, and xz compression methods (and Deflate64 decompression).
 //! - Various different reading approaches (seek, stream, filesystem, in-memory buffer).
 //! - Support for writing complete data (u8 slices) or stream writing using data descriptors.
 //! - Initial support for ZIP64 reading and writing.
@@ -33,12 +33,20 @@
 //! - `lzma` - Enables support for the LZMA compression method.
 //! - `zstd` - Enables support for the zstd compression method.
 //! - `xz` - Enables support for the xz compression method.
+//! - `deflate64` - Enables support for decompressing the Deflate64 compression method.
+//! - `aes` - Enables support for decrypting WinZip AES-encrypted (AE-1/AE-2) entries.
+//! - `sha256` - Enables support for computing SHA-256 digests of entries' decompressed contents.
+//! - `sync` - Enables support for the blocking `sync` reading module.
+//! - `log` - Enables logging a warning (via `log`) when a ZIP file writer is dropped without being closed.
 //!
 //! [Read more.](https://github.com/Majored/rs-async-zip)
 
 pub mod base;
 pub mod error;
+pub mod prelude;
 
+#[cfg(feature = "sync")]
+pub mod sync;
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
@@ -56,7 +64,9 @@ pub use crate::spec::attribute::AttributeCompatibility;
 pub use crate::spec::compression::{Compression, DeflateOption};
 
 pub use crate::entry::{builder::ZipEntryBuilder, StoredZipEntry, ZipEntry};
-pub use crate::file::{builder::ZipFileBuilder, ZipFile};
+pub use crate::file::{builder::ZipFileBuilder, ZipFile, ZipFileMetadata};
 
 pub use crate::date::ZipDateTime;
+#[cfg(feature = "chrono")]
+pub use crate::date::{ExtendedTimestamps, NtfsTimes};
 pub use crate::string::{StringEncoding, ZipString};
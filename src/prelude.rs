@@ -0,0 +1,48 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which re-exports the most commonly used types and traits.
+//!
+//! The IO extension traits are re-exported anonymously, so that their methods are brought into scope without
+//! conflicting with any similarly-named traits imported by the user.
+//!
+//! # Example
+//! ```no_run
+//! use async_zip::prelude::*;
+//!
+//! # async fn run() -> Result<()> {
+//! let mut data = futures_util::io::Cursor::new(Vec::new());
+//! let mut writer = ZipFileWriter::new(&mut data);
+//!
+//! let builder = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+//! writer.write_entry_whole(builder, b"bar").await?;
+//! writer.close().await?;
+//!
+//! data.set_position(0);
+//! let mut zip = ZipFileReader::new(data).await?;
+//!
+//! let mut string = String::new();
+//! let mut reader: ZipEntryReader<'_, _, WithEntry<'_>> = zip.reader_with_entry(0).await?;
+//! reader.read_to_string_checked(&mut string).await?;
+//!
+//! let entry: &ZipEntry = reader.entry();
+//! assert_eq!(entry.filename().as_str()?, "foo.txt");
+//! assert_eq!(string, "bar");
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+pub use crate::base::read::seek::ZipFileReader;
+pub use crate::base::read::{WithEntry, WithoutEntry, ZipEntryReader};
+pub use crate::base::write::ZipFileWriter;
+pub use crate::error::{Result, ZipError};
+pub use crate::{Compression, StoredZipEntry, ZipDateTime, ZipEntry, ZipEntryBuilder, ZipFile, ZipString};
+
+pub use futures_util::io::{AsyncBufReadExt as _, AsyncReadExt as _, AsyncSeekExt as _, AsyncWriteExt as _};
+
+#[cfg(feature = "tokio")]
+pub use tokio_util::compat::{
+    FuturesAsyncReadCompatExt as _, FuturesAsyncWriteCompatExt as _, TokioAsyncReadCompatExt as _,
+    TokioAsyncWriteCompatExt as _,
+};
@@ -1,24 +1,43 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
-use crate::error::{Result, ZipError};
+use crate::error::Result;
 
-/// An attribute host compatibility supported by this crate.
+/// An attribute host compatibility, which describes the host system an entry was produced on (and so how its external
+/// file attribute should be interpreted).
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AttributeCompatibility {
+    /// MS-DOS and OS/2 (FAT / VFAT / FAT32 file systems).
+    Dos,
+    /// UNIX.
     Unix,
+    /// Macintosh.
+    Macintosh,
+    /// Windows NTFS.
+    Ntfs,
+    /// VFAT.
+    Vfat,
+    /// OS X (Darwin).
+    Darwin,
+    /// Any other host system, holding the value stored.
+    Other(u16),
 }
 
 impl TryFrom<u16> for AttributeCompatibility {
-    type Error = ZipError;
+    type Error = crate::error::ZipError;
 
     // Convert a u16 stored with little endianness into a supported attribute host compatibility.
     // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4422
     fn try_from(value: u16) -> Result<Self> {
         match value {
+            0 => Ok(AttributeCompatibility::Dos),
             3 => Ok(AttributeCompatibility::Unix),
-            _ => Err(ZipError::AttributeCompatibilityNotSupported(value)),
+            7 => Ok(AttributeCompatibility::Macintosh),
+            10 => Ok(AttributeCompatibility::Ntfs),
+            14 => Ok(AttributeCompatibility::Vfat),
+            19 => Ok(AttributeCompatibility::Darwin),
+            other => Ok(AttributeCompatibility::Other(other)),
         }
     }
 }
@@ -28,7 +47,13 @@ impl From<&AttributeCompatibility> for u16 {
     // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4422
     fn from(compatibility: &AttributeCompatibility) -> Self {
         match compatibility {
+            AttributeCompatibility::Dos => 0,
             AttributeCompatibility::Unix => 3,
+            AttributeCompatibility::Macintosh => 7,
+            AttributeCompatibility::Ntfs => 10,
+            AttributeCompatibility::Vfat => 14,
+            AttributeCompatibility::Darwin => 19,
+            AttributeCompatibility::Other(value) => *value,
         }
     }
 }
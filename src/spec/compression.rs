@@ -7,6 +7,10 @@ use crate::error::{Result, ZipError};
 use async_compression::Level;
 
 /// A compression method supported by this crate.
+///
+/// The legacy Shrink (1), Reduce (2-5), and Implode (6) methods used by very old archives aren't supported, so reading
+/// an entry compressed with one of them fails with [`ZipError::CompressionNotSupported`] (as for any other unsupported
+/// method) rather than yielding its compressed data.
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Compression {
@@ -45,6 +49,8 @@ impl TryFrom<u16> for Compression {
             93 => Ok(Compression::Zstd),
             #[cfg(feature = "xz")]
             95 => Ok(Compression::Xz),
+            // Incl. Shrink (1), Reduce (2-5), and Implode (6), which predate Deflate and are no longer produced by
+            // modern tools.
             _ => Err(ZipError::CompressionNotSupported(value)),
         }
     }
@@ -100,12 +106,13 @@ pub enum DeflateOption {
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 impl DeflateOption {
     pub(crate) fn into_level(self) -> Level {
-        // FIXME: There's no clear documentation on what these specific levels defined in the ZIP specification relate
-        // to. We want to be compatible with any other library, and not specific to `async_compression`'s levels.
-        if let Self::Other(l) = self {
-            Level::Precise(l)
-        } else {
-            Level::Default
+        // There's no clear documentation on what these specific levels defined in the ZIP specification relate to, so
+        // we map them onto the encoder's own notion of each (with Other(0..=9) mapping onto deflate's levels directly).
+        match self {
+            Self::Normal => Level::Default,
+            Self::Maximum => Level::Best,
+            Self::Fast | Self::Super => Level::Fastest,
+            Self::Other(l) => Level::Precise(l),
         }
     }
 }
@@ -41,3 +41,14 @@ pub const NON_ZIP64_MAX_NUM_FILES: u16 = 0xFFFF;
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#439
 pub const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x8074b50;
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#588
+/// The length of the LZMA properties header's version and properties size fields.
+#[cfg(feature = "lzma")]
+pub const LZMA_HEADER_LENGTH: usize = 4;
+/// The length of the LZMA properties data written by the LZMA SDK.
+#[cfg(feature = "lzma")]
+pub const LZMA_PROPERTIES_LENGTH: usize = 5;
+/// The general purpose flag bit which signals that an LZMA stream is terminated by an end-of-stream marker.
+#[cfg(feature = "lzma")]
+pub const LZMA_EOS_FLAG: u16 = 0x2;
@@ -1,7 +1,11 @@
 // Copyright Cognite AS, 2023
 
 use crate::error::{Result as ZipResult, ZipError};
-use crate::spec::header::{ExtraField, HeaderId, UnknownExtraField, Zip64ExtendedInformationExtraField};
+use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
+use crate::spec::header::{
+    CentralDirectoryRecord, ExtraField, HeaderId, LocalFileHeader, UnknownExtraField,
+    Zip64ExtendedInformationExtraField,
+};
 
 impl From<u16> for HeaderId {
     fn from(value: u16) -> Self {
@@ -99,42 +103,115 @@ impl ExtraFieldAsBytes for Zip64ExtendedInformationExtraField {
         4 + self.uncompressed_size.map(|_| 8).unwrap_or_default()
             + self.compressed_size.map(|_| 8).unwrap_or_default()
             + self.relative_header_offset.map(|_| 8).unwrap_or_default()
-            + self.disk_start_number.map(|_| 8).unwrap_or_default()
+            + self.disk_start_number.map(|_| 4).unwrap_or_default()
+    }
+}
+
+/// The values of a header's fields which, when set to their sentinel value, are instead stored within a zip64 extended
+/// information extra field.
+#[derive(Clone, Copy)]
+pub(crate) struct Zip64Sentinels {
+    pub(crate) uncompressed_size: u32,
+    pub(crate) compressed_size: u32,
+    pub(crate) relative_header_offset: u32,
+    pub(crate) disk_start_number: u16,
+}
+
+impl From<&CentralDirectoryRecord> for Zip64Sentinels {
+    fn from(header: &CentralDirectoryRecord) -> Self {
+        Self {
+            uncompressed_size: header.uncompressed_size,
+            compressed_size: header.compressed_size,
+            relative_header_offset: header.lh_offset,
+            disk_start_number: header.disk_start,
+        }
+    }
+}
+
+impl From<&LocalFileHeader> for Zip64Sentinels {
+    // A local file header has no offset or disk start number fields, so neither are ever stored within its zip64 field.
+    fn from(header: &LocalFileHeader) -> Self {
+        Self {
+            uncompressed_size: header.uncompressed_size,
+            compressed_size: header.compressed_size,
+            relative_header_offset: 0,
+            disk_start_number: 0,
+        }
     }
 }
 
 /// Parse a zip64 extra field from bytes.
 /// The content of "data" should exclude the header.
+///
+/// Each field is only present when its counterpart within the record's header is set to the zip64 sentinel value, and
+/// present fields always appear in a fixed order.
 fn zip64_extended_information_field_from_bytes(
     header_id: HeaderId,
     data_size: u16,
     data: &[u8],
+    sentinels: Zip64Sentinels,
 ) -> ZipResult<Zip64ExtendedInformationExtraField> {
-    // First ensure that the data is sufficient to populate compressed & uncompressed size.
-    if data.len() < 16 {
-        return Err(ZipError::Zip64ExtendedFieldIncomplete);
-    }
-    let uncompressed_size = u64::from_le_bytes(data[0..8].try_into().unwrap());
-    let compressed_size = u64::from_le_bytes(data[8..16].try_into().unwrap());
-    let relative_header_offset =
-        if data.len() >= 24 { Some(u64::from_le_bytes(data[16..24].try_into().unwrap())) } else { None };
-    let disk_start_number =
-        if data.len() >= 32 { Some(u32::from_le_bytes(data[24..32].try_into().unwrap())) } else { None };
+    // slice::take() is nightly-only so we'll just use an index to track the current position.
+    let mut current_idx = 0;
+
+    let uncompressed_size = if sentinels.uncompressed_size == NON_ZIP64_MAX_SIZE {
+        let value = read_u64(data, current_idx).ok_or(ZipError::Zip64ExtendedFieldIncomplete)?;
+        current_idx += 8;
+        Some(value)
+    } else {
+        None
+    };
+
+    let compressed_size = if sentinels.compressed_size == NON_ZIP64_MAX_SIZE {
+        let value = read_u64(data, current_idx).ok_or(ZipError::Zip64ExtendedFieldIncomplete)?;
+        current_idx += 8;
+        Some(value)
+    } else {
+        None
+    };
+
+    let relative_header_offset = if sentinels.relative_header_offset == NON_ZIP64_MAX_SIZE {
+        let value = read_u64(data, current_idx).ok_or(ZipError::Zip64ExtendedFieldIncomplete)?;
+        current_idx += 8;
+        Some(value)
+    } else {
+        None
+    };
+
+    let disk_start_number = if sentinels.disk_start_number == NON_ZIP64_MAX_NUM_FILES {
+        let value = data
+            .get(current_idx..current_idx + 4)
+            .map(|v| u32::from_le_bytes(v.try_into().unwrap()))
+            .ok_or(ZipError::Zip64ExtendedFieldIncomplete)?;
+        Some(value)
+    } else {
+        None
+    };
 
     Ok(Zip64ExtendedInformationExtraField {
         header_id,
         data_size,
-        uncompressed_size: Some(uncompressed_size),
-        compressed_size: Some(compressed_size),
+        uncompressed_size,
+        compressed_size,
         relative_header_offset,
         disk_start_number,
     })
 }
 
-pub(crate) fn extra_field_from_bytes(header_id: HeaderId, data_size: u16, data: &[u8]) -> ZipResult<ExtraField> {
+/// Read a little endian u64 from the data at the given index, if enough data remains.
+fn read_u64(data: &[u8], index: usize) -> Option<u64> {
+    data.get(index..index + 8).map(|v| u64::from_le_bytes(v.try_into().unwrap()))
+}
+
+pub(crate) fn extra_field_from_bytes(
+    header_id: HeaderId,
+    data_size: u16,
+    data: &[u8],
+    sentinels: Zip64Sentinels,
+) -> ZipResult<ExtraField> {
     match header_id {
         HeaderId::Zip64ExtendedInformationExtraField => Ok(ExtraField::Zip64ExtendedInformationExtraField(
-            zip64_extended_information_field_from_bytes(header_id, data_size, data)?,
+            zip64_extended_information_field_from_bytes(header_id, data_size, data, sentinels)?,
         )),
         header_id @ HeaderId::Other(_) => {
             Ok(ExtraField::UnknownExtraField(UnknownExtraField { header_id, data_size, content: data.to_vec() }))
@@ -142,6 +219,20 @@ pub(crate) fn extra_field_from_bytes(header_id: HeaderId, data_size: u16, data:
     }
 }
 
+/// Returns an iterator over the header IDs and data of the fields within raw extra field bytes.
+///
+/// Iteration stops at the first field whose data would extend beyond the end of the provided bytes.
+pub(crate) fn raw_extra_fields(mut data: &[u8]) -> impl Iterator<Item = (u16, &[u8])> {
+    std::iter::from_fn(move || {
+        let header_id = u16::from_le_bytes(data.get(0..2)?.try_into().unwrap());
+        let size = u16::from_le_bytes(data.get(2..4)?.try_into().unwrap()) as usize;
+        let content = data.get(4..4 + size)?;
+
+        data = &data[4 + size..];
+        Some((header_id, content))
+    })
+}
+
 pub struct Zip64ExtendedInformationExtraFieldBuilder {
     field: Zip64ExtendedInformationExtraField,
 }
@@ -188,7 +279,7 @@ impl Zip64ExtendedInformationExtraFieldBuilder {
         field.data_size = field.uncompressed_size.map(|_| 8).unwrap_or_default()
             + field.compressed_size.map(|_| 8).unwrap_or_default()
             + field.relative_header_offset.map(|_| 8).unwrap_or_default()
-            + field.disk_start_number.map(|_| 8).unwrap_or_default();
+            + field.disk_start_number.map(|_| 4).unwrap_or_default();
 
         if field.data_size == 0 {
             return Err(ZipError::Zip64ExtendedFieldIncomplete);
@@ -16,16 +16,18 @@ pub struct LocalFileHeader {
 }
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#444
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct GeneralPurposeFlag {
     pub encrypted: bool,
     pub data_descriptor: bool,
     pub filename_unicode: bool,
+    /// Any remaining bits, which aren't otherwise interpreted by this crate.
+    pub other: u16,
 }
 
 /// 2 byte header ids
 /// Ref https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#452
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HeaderId {
     Zip64ExtendedInformationExtraField,
     Other(u16),
@@ -33,7 +35,7 @@ pub enum HeaderId {
 
 /// Represents each extra field.
 /// Not strictly part of the spec, but is the most useful way to represent the data.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ExtraField {
     Zip64ExtendedInformationExtraField(Zip64ExtendedInformationExtraField),
     UnknownExtraField(UnknownExtraField),
@@ -42,7 +44,7 @@ pub enum ExtraField {
 /// An extended information header for Zip64.
 /// This field is used both for local file headers and central directory records.
 /// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#453
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Zip64ExtendedInformationExtraField {
     pub header_id: HeaderId,
     pub data_size: u16,
@@ -54,13 +56,25 @@ pub struct Zip64ExtendedInformationExtraField {
 }
 
 /// Represents any unparsed extra field.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct UnknownExtraField {
     pub header_id: HeaderId,
     pub data_size: u16,
     pub content: Vec<u8>,
 }
 
+/// The data of a WinZip AES encryption extra field (0x9901), which is present on entries encrypted with AE-1 or AE-2.
+#[derive(Clone, Copy, Debug)]
+pub struct AesExtraField {
+    /// The vendor version (1 for AE-1, 2 for AE-2).
+    pub version: u16,
+    /// The key strength (1 for AES-128, 2 for AES-192, 3 for AES-256).
+    #[cfg_attr(not(feature = "aes"), allow(dead_code))]
+    pub strength: u8,
+    /// The actual compression method of the entry's data.
+    pub compression: u16,
+}
+
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4312
 pub struct CentralDirectoryRecord {
     pub v_made_by: u16,
@@ -4,7 +4,7 @@
 use crate::error::{Result, ZipError};
 use crate::spec::header::{
     CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, GeneralPurposeFlag, HeaderId, LocalFileHeader,
-    Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord,
+    UnknownExtraField, Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord,
 };
 
 use futures_util::io::{AsyncRead, AsyncReadExt};
@@ -29,8 +29,15 @@ impl LocalFileHeader {
     }
 }
 
+/// The bits of the general purpose flag which are interpreted by [`GeneralPurposeFlag`]'s named fields.
+const INTERPRETED_FLAG_BITS: u16 = 0x1 | 0x8 | 0x800;
+
 impl GeneralPurposeFlag {
     pub fn as_slice(&self) -> [u8; 2] {
+        self.as_u16().to_le_bytes()
+    }
+
+    pub fn as_u16(&self) -> u16 {
         let encrypted: u16 = match self.encrypted {
             false => 0x0,
             true => 0b1,
@@ -44,7 +51,7 @@ impl GeneralPurposeFlag {
             true => 0x800,
         };
 
-        (encrypted | data_descriptor | filename_unicode).to_le_bytes()
+        encrypted | data_descriptor | filename_unicode | (self.other & !INTERPRETED_FLAG_BITS)
     }
 }
 
@@ -114,7 +121,9 @@ impl From<u16> for GeneralPurposeFlag {
         let data_descriptor = !matches!((value & 0x8) >> 3, 0);
         let filename_unicode = !matches!((value & 0x800) >> 11, 0);
 
-        GeneralPurposeFlag { encrypted, data_descriptor, filename_unicode }
+        let other = value & !INTERPRETED_FLAG_BITS;
+
+        GeneralPurposeFlag { encrypted, data_descriptor, filename_unicode, other }
     }
 }
 
@@ -263,18 +272,35 @@ impl Zip64EndOfCentralDirectoryLocator {
     }
 }
 
-/// Parse the extra fields.
-pub fn parse_extra_fields(data: Vec<u8>) -> Result<Vec<ExtraField>> {
+/// Parses the extra fields stored within a header.
+///
+/// The header's sentinel values are required to determine which fields are present within a zip64 extended information
+/// extra field.
+///
+/// When lenient, trailing data which is too short to hold its declared field is ignored, and fields which fail to parse
+/// are kept as unknown fields (so that any values required from them are found to be missing later).
+pub fn parse_extra_fields(data: &[u8], sentinels: Zip64Sentinels, lenient: bool) -> Result<Vec<ExtraField>> {
     let mut cursor = 0;
     let mut extra_fields = Vec::new();
     while cursor + 4 < data.len() {
         let header_id: HeaderId = u16::from_le_bytes(data[cursor..cursor + 2].try_into().unwrap()).into();
         let field_size = u16::from_le_bytes(data[cursor + 2..cursor + 4].try_into().unwrap());
         if cursor + 4 + field_size as usize > data.len() {
-            return Err(ZipError::InvalidExtraFieldHeader(field_size, data.len() - cursor - 8 - field_size as usize));
+            if lenient {
+                break;
+            }
+            return Err(ZipError::InvalidExtraFieldHeader(field_size, data.len() - cursor - 4));
         }
         let data = &data[cursor + 4..cursor + 4 + field_size as usize];
-        extra_fields.push(extra_field_from_bytes(header_id, field_size, data)?);
+        match extra_field_from_bytes(header_id, field_size, data, sentinels) {
+            Ok(field) => extra_fields.push(field),
+            Err(_) if lenient => extra_fields.push(ExtraField::UnknownExtraField(UnknownExtraField {
+                header_id,
+                data_size: field_size,
+                content: data.to_vec(),
+            })),
+            Err(err) => return Err(err),
+        }
         cursor += 4 + field_size as usize;
     }
     Ok(extra_fields)
@@ -291,12 +317,13 @@ macro_rules! array_push {
 }
 
 use crate::spec::consts::ZIP64_EOCDL_SIGNATURE;
-use crate::spec::extra_field::extra_field_from_bytes;
+use crate::spec::extra_field::{extra_field_from_bytes, Zip64Sentinels};
 pub(crate) use array_push;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
 
     #[test]
     fn test_parse_zip64_eocdr() {
@@ -342,4 +369,55 @@ mod tests {
             }
         )
     }
+
+    fn zip64_extra_field(values: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&0x0001u16.to_le_bytes());
+        data.extend_from_slice(&(values.len() as u16).to_le_bytes());
+        data.extend_from_slice(values);
+        data
+    }
+
+    #[test]
+    fn test_parse_zip64_extra_field_without_sentinels() {
+        // Trailing data isn't an offset or disk start number unless the header says so.
+        let data = zip64_extra_field(&[0x10, 0, 0, 0, 0, 0, 0, 0, 0x02, 0, 0, 0]);
+        let sentinels = Zip64Sentinels {
+            uncompressed_size: 1,
+            compressed_size: 1,
+            relative_header_offset: 0,
+            disk_start_number: 0,
+        };
+
+        let fields = parse_extra_fields(&data, sentinels, false).unwrap();
+        let ExtraField::Zip64ExtendedInformationExtraField(field) = &fields[0] else {
+            panic!("expected a zip64 field")
+        };
+        assert_eq!(field.uncompressed_size, None);
+        assert_eq!(field.compressed_size, None);
+        assert_eq!(field.relative_header_offset, None);
+        assert_eq!(field.disk_start_number, None);
+    }
+
+    #[test]
+    fn test_parse_zip64_extra_field_with_sentinels() {
+        let data = zip64_extra_field(&[0x10, 0, 0, 0, 0, 0, 0, 0, 0x02, 0, 0, 0]);
+        let sentinels = Zip64Sentinels {
+            uncompressed_size: 1,
+            compressed_size: 1,
+            relative_header_offset: NON_ZIP64_MAX_SIZE,
+            disk_start_number: NON_ZIP64_MAX_NUM_FILES,
+        };
+
+        let fields = parse_extra_fields(&data, sentinels, false).unwrap();
+        let ExtraField::Zip64ExtendedInformationExtraField(field) = &fields[0] else {
+            panic!("expected a zip64 field")
+        };
+        assert_eq!(field.relative_header_offset, Some(0x10));
+        assert_eq!(field.disk_start_number, Some(2));
+
+        // A sentinel without its value present is an incomplete field.
+        let data = zip64_extra_field(&[0x10, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(matches!(parse_extra_fields(&data, sentinels, false), Err(ZipError::Zip64ExtendedFieldIncomplete)));
+    }
 }
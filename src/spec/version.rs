@@ -2,6 +2,7 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::entry::ZipEntry;
+use crate::spec::attribute::AttributeCompatibility;
 #[cfg(any(
     feature = "deflate",
     feature = "bzip2",
@@ -36,7 +37,6 @@ pub fn as_needed_to_extract(entry: &ZipEntry) -> u16 {
 }
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#442
-pub fn as_made_by() -> u16 {
-    // Default to UNIX mapping for the moment.
-    3 << 8 | SPEC_VERSION_MADE_BY
+pub fn as_made_by(compatibility: AttributeCompatibility) -> u16 {
+    u16::from(compatibility) << 8 | SPEC_VERSION_MADE_BY
 }
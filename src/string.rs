@@ -3,18 +3,32 @@
 
 use crate::error::{Result, ZipError};
 
+/// The characters of IBM Code Page 437 for the bytes 0x80 to 0xFF (the lower half is identical to ASCII).
+#[rustfmt::skip]
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{A0}',
+];
+
 /// A string encoding supported by this crate.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StringEncoding {
     Utf8,
     Raw,
 }
 
 /// A string wrapper for handling different encodings.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ZipString {
     encoding: StringEncoding,
     raw: Vec<u8>,
+    decoded: Option<String>,
 }
 
 impl ZipString {
@@ -30,7 +44,21 @@ impl ZipString {
             }
         }
 
-        Self { encoding, raw }
+        Self { encoding, raw, decoded: None }
+    }
+
+    /// Constructs a new encoded string from its raw bytes as encoded in IBM Code Page 437.
+    ///
+    /// This is the encoding of filenames and comments for which the language encoding flag (bit 11) isn't set. ASCII
+    /// strings are identical in both encodings so are stored as [`StringEncoding::Utf8`], whereas others are stored
+    /// as [`StringEncoding::Raw`] alongside their decoded form (returned by [`ZipString::as_str()`]).
+    pub(crate) fn from_cp437(raw: Vec<u8>) -> Self {
+        if raw.is_ascii() {
+            return Self { encoding: StringEncoding::Utf8, raw, decoded: None };
+        }
+
+        let decoded = raw.iter().map(|&byte| if byte < 0x80 { byte as char } else { CP437_HIGH[byte as usize - 0x80] });
+        Self { encoding: StringEncoding::Raw, decoded: Some(decoded.collect()), raw }
     }
 
     /// Returns the raw bytes for this string.
@@ -46,10 +74,11 @@ impl ZipString {
     /// Returns the raw bytes converted into a string slice.
     ///
     /// # Note
-    /// A call to this method will only succeed if the encoding type is [`StringEncoding::Utf8`].
+    /// A call to this method will only succeed if the encoding type is [`StringEncoding::Utf8`], or if the raw bytes
+    /// were decoded from IBM Code Page 437 when reading.
     pub fn as_str(&self) -> Result<&str> {
         if !matches!(self.encoding, StringEncoding::Utf8) {
-            return Err(ZipError::StringNotUtf8);
+            return self.decoded.as_deref().ok_or(ZipError::StringNotUtf8);
         }
 
         // SAFETY:
@@ -67,10 +96,11 @@ impl ZipString {
     /// Returns the raw bytes converted to an owned string.
     ///
     /// # Note
-    /// A call to this method will only succeed if the encoding type is [`StringEncoding::Utf8`].
+    /// A call to this method will only succeed if the encoding type is [`StringEncoding::Utf8`], or if the raw bytes
+    /// were decoded from IBM Code Page 437 when reading.
     pub fn into_string(self) -> Result<String> {
         if !matches!(self.encoding, StringEncoding::Utf8) {
-            return Err(ZipError::StringNotUtf8);
+            return self.decoded.ok_or(ZipError::StringNotUtf8);
         }
 
         // SAFETY: See above.
@@ -80,12 +110,12 @@ impl ZipString {
 
 impl From<String> for ZipString {
     fn from(value: String) -> Self {
-        Self { encoding: StringEncoding::Utf8, raw: value.into_bytes() }
+        Self { encoding: StringEncoding::Utf8, raw: value.into_bytes(), decoded: None }
     }
 }
 
 impl From<&str> for ZipString {
     fn from(value: &str) -> Self {
-        Self { encoding: StringEncoding::Utf8, raw: value.as_bytes().to_vec() }
+        Self { encoding: StringEncoding::Utf8, raw: value.as_bytes().to_vec(), decoded: None }
     }
 }
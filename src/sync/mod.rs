@@ -0,0 +1,271 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A blocking wrapper around the [`base`] implementation for use outside of async contexts.
+//!
+//! # Usage
+//! With the `sync` feature enabled, this module provides a [`ZipFileReader`] which acts over a file system path and
+//! drives the [`base::read::seek::ZipFileReader`] to completion on the current thread (via
+//! [`futures_executor::block_on()`]), so no async runtime needs to be set up.
+//!
+//! As each call blocks the current thread, this reader shouldn't be used from within an async context.
+//!
+//! The file is opened in a shared read mode, so it can also be opened by other readers or processes at the same time
+//! (incl. ones which hold it open for writing). However, the central directory is only read when the reader is
+//! constructed (or via [`ZipFileReader::refresh()`]), so the file shouldn't be modified whilst it's being read.
+//!
+//! # Example
+//! ```no_run
+//! # use async_zip::error::Result;
+//! # use async_zip::sync::ZipFileReader;
+//! #
+//! # fn run() -> Result<()> {
+//! let mut reader = ZipFileReader::new("./foo.zip")?;
+//!
+//! let data = reader.read_entry(0)?;
+//! reader.extract_to("./foo")?;
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+#[cfg(doc)]
+use crate::base;
+
+use crate::base::read::{seek, ReadOptions};
+use crate::entry::{StoredZipEntry, ZipEntry};
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+
+use std::fs::File;
+use std::ops::Index;
+use std::path::Path;
+
+use futures_executor::block_on;
+use futures_util::io::AllowStdIo;
+
+/// Generates methods which block the current thread until the [`seek::ZipFileReader`] method of the same name
+/// completes, given the doc comments, attributes, and signature of each (without its `Result`).
+macro_rules! blocking_methods {
+    ($($(#[$attr:meta])* fn $name:ident(&mut self $(, $arg:ident: $ty:ty)*) -> $output:ty;)*) => {
+        $(
+            $(#[$attr])*
+            ///
+            #[doc = concat!("Blocks the current thread on [`seek::ZipFileReader::", stringify!($name), "()`].")]
+            pub fn $name(&mut self $(, $arg: $ty)*) -> Result<$output> {
+                block_on(self.inner.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+/// A blocking ZIP reader which acts over a file system path.
+pub struct ZipFileReader {
+    inner: seek::ZipFileReader<AllowStdIo<File>>,
+}
+
+impl ZipFileReader {
+    /// Constructs a new blocking ZIP reader from a file system path.
+    pub fn new<P>(path: P) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        ZipFileReader::new_with_options(path, ReadOptions::default())
+    }
+
+    /// Constructs a new blocking ZIP reader from a file system path, locating and parsing its central directory in
+    /// accordance with the provided options.
+    ///
+    /// See [`seek::ZipFileReader::new_with_options()`] for more information.
+    pub fn new_with_options<P>(path: P, options: ReadOptions) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = crate::utils::shared_read_options()
+            .open(path)
+            .map_err(|source| ZipError::FileOpenError { path: path.to_owned(), source })?;
+        let file = AllowStdIo::new(file);
+        Ok(ZipFileReader { inner: block_on(seek::ZipFileReader::new_with_options(file, options))? })
+    }
+
+    /// Constructs a new blocking ZIP reader from a file system path, tolerating non-fatal inconsistencies within its
+    /// central directory.
+    ///
+    /// See [`seek::ZipFileReader::new_lenient()`] for more information.
+    pub fn new_lenient<P>(path: P) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        ZipFileReader::new_with_options(path, ReadOptions { lenient: true, ..Default::default() })
+    }
+
+    /// Limits the ratio of decompressed bytes to compressed bytes when reading entries, guarding against ZIP bombs.
+    ///
+    /// See [`seek::ZipFileReader::with_expansion_limit()`] for more information.
+    pub fn with_expansion_limit(self, ratio: u64, threshold: u64) -> Self {
+        ZipFileReader { inner: self.inner.with_expansion_limit(ratio, threshold) }
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        self.inner.file()
+    }
+
+    blocking_methods! {
+        /// Re-reads the central directory from the file, replacing this ZIP file's information.
+        fn refresh(&mut self) -> ();
+
+        /// Returns the offset in bytes to where the data of an entry starts, if the provided index is valid.
+        fn data_offset(&mut self, index: usize) -> u64;
+
+        /// Returns the offsets in bytes to where the compressed data of an entry starts and ends (exclusive), if the
+        /// provided index is valid.
+        fn compressed_data_range(&mut self, index: usize) -> (u64, u64);
+
+        /// Returns the offsets in bytes to where the data descriptor following an entry's data starts and ends
+        /// (exclusive), if the provided index is valid and the entry was written with one.
+        fn data_descriptor_range(&mut self, index: usize) -> Option<(u64, u64)>;
+
+        /// Reads the entire decompressed contents of an entry, if the provided index is valid.
+        ///
+        /// The entry's CRC32 value is verified once all of its data has been read.
+        fn read_entry(&mut self, index: usize) -> Vec<u8>;
+
+        /// Computes the SHA-256 digest of the decompressed contents of an entry, if the provided index is valid.
+        #[cfg(feature = "sha256")]
+        fn entry_sha256(&mut self, index: usize) -> [u8; 32];
+
+        /// Reads the entire decompressed contents of an entry, if the provided index is valid and it doesn't exceed the
+        /// provided limit (in bytes).
+        fn read_entry_limited(&mut self, index: usize, limit: u64) -> Vec<u8>;
+
+        /// Reads up to the first `length` decompressed bytes of an entry, if the provided index is valid.
+        fn read_entry_prefix(&mut self, index: usize, length: usize) -> Vec<u8>;
+
+        /// Reads the entire decompressed contents of the first entry with the provided filename, if one exists.
+        fn read_entry_by_name(&mut self, filename: impl AsRef<[u8]>) -> Vec<u8>;
+
+        /// Reads every entry to completion (without buffering their data), verifying each entry's CRC32 value.
+        ///
+        /// See [`seek::ZipFileReader::validate()`] for how failures are reported.
+        fn validate(&mut self) -> ();
+    }
+
+    crate::file::zip_file_accessors!();
+
+    /// Extracts every entry into the provided directory, creating it and any parent directories as needed.
+    ///
+    /// On Unix, entries which represent symbolic links are recreated as such and the permissions of each file are set
+    /// from its Unix mode (if one was stored). Elsewhere, symbolic links are extracted as regular files containing the
+    /// link's target. With the `chrono` feature enabled, each file's modification time is also set from its entry's
+    /// (see [`ZipEntry::last_modified()`]).
+    ///
+    /// [`ZipError::UnsafePath`] is returned if an entry's filename is absolute or would otherwise resolve to a path
+    /// outside of the directory (eg. via `..`), if a symbolic link's target could do the same, or if an entry's path
+    /// passes through an existing symbolic link (eg. one extracted from an earlier entry).
+    pub fn extract_to<P>(&mut self, dir: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.extract_to_with_progress(dir, |_, _, _| {})
+    }
+
+    /// Extracts every entry into the provided directory, reporting progress to the provided callback.
+    ///
+    /// The callback is provided with the entry being extracted, the number of bytes written so far across all entries,
+    /// and the total number of bytes to be written (the sum of each entry's uncompressed size). It's invoked after each
+    /// chunk of an entry's data has been written, and once more when the entry has been fully extracted.
+    ///
+    /// See [`ZipFileReader::extract_to()`] for how entries are extracted.
+    pub fn extract_to_with_progress<P, F>(&mut self, dir: P, mut progress: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&ZipEntry, u64, u64),
+    {
+        let total = self.file().total_uncompressed_size();
+        let mut written = 0;
+        let mut buffer = vec![0; 8192];
+
+        for index in 0..self.file().entries().len() {
+            let entry = self.file().entries()[index].entry.clone();
+            let relative = entry.sanitized_path()?;
+            let path = dir.as_ref().join(&relative);
+            crate::utils::check_no_symlinks(dir.as_ref(), &relative)?;
+
+            if entry.is_dir() {
+                std::fs::create_dir_all(&path)?;
+                progress(&entry, written, total);
+                continue;
+            }
+
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            if entry.is_symlink() {
+                let mut target = String::new();
+                let mut reader = block_on(self.inner.reader_with_entry(index))?;
+                written += block_on(reader.read_to_string_checked(&mut target))? as u64;
+                let target = crate::utils::sanitize_symlink_target(&relative, &target)?;
+
+                #[cfg(unix)]
+                std::os::unix::fs::symlink(target, &path)?;
+                #[cfg(not(unix))]
+                std::fs::write(&path, target.to_string_lossy().as_bytes())?;
+
+                progress(&entry, written, total);
+                continue;
+            }
+
+            self.extract_file(index, &path, &mut buffer, |read| {
+                written += read;
+                progress(&entry, written, total);
+            })?;
+
+            progress(&entry, written, total);
+        }
+
+        Ok(())
+    }
+
+    /// Streams a regular file entry's data to the provided path, invoking the callback with the length of each chunk
+    /// written.
+    ///
+    /// The file's modification time (with the `chrono` feature enabled) and permissions are then set from the entry.
+    fn extract_file<F>(&mut self, index: usize, path: &Path, buffer: &mut [u8], mut on_write: F) -> Result<()>
+    where
+        F: FnMut(u64),
+    {
+        use futures_util::io::AsyncReadExt;
+        use std::io::Write;
+
+        let mut reader = block_on(self.inner.reader_with_entry(index))?;
+        let mut fs_file = File::create(path)?;
+
+        loop {
+            let read = block_on(reader.read(buffer))?;
+            if read == 0 {
+                break;
+            }
+
+            fs_file.write_all(&buffer[..read])?;
+            on_write(read as u64);
+        }
+
+        block_on(reader.verify())?;
+        crate::utils::apply_file_metadata(&fs_file, &self.file().entries()[index])
+    }
+}
+
+impl Index<usize> for ZipFileReader {
+    type Output = StoredZipEntry;
+
+    /// Returns the entry at the provided index.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds. See [`ZipFileReader::get()`] for a non-panicking alternative.
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.inner[index]
+    }
+}
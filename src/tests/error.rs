@@ -0,0 +1,41 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::{Zip64ErrorCase, ZipError};
+
+use std::error::Error;
+
+#[test]
+fn display_test() {
+    assert_eq!(ZipError::EntryIndexOutOfBounds.to_string(), "entry index was out of bounds");
+    assert_eq!(ZipError::CompressionNotSupported(99).to_string(), "compression not supported: 99");
+    assert_eq!(
+        ZipError::UnexpectedHeaderError(0x04034b00, 0x04034b50).to_string(),
+        "Encountered an unexpected header (actual: 0x4034b00, expected: 0x4034b50)."
+    );
+    assert_eq!(
+        ZipError::Zip64Needed(Zip64ErrorCase::LargeFile).to_string(),
+        "attempted to write a ZIP file with force_no_zip64 when ZIP64 is needed: File is larger than 4 GiB"
+    );
+}
+
+#[test]
+fn source_test() {
+    let io_err = std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "early eof");
+    let err = ZipError::from(io_err);
+
+    assert_eq!(err.to_string(), "an upstream reader returned an error: early eof");
+    assert_eq!(err.source().unwrap().to_string(), "early eof");
+    assert!(ZipError::CRC32CheckError { expected: 0, actual: 1 }.source().is_none());
+}
+
+#[test]
+fn boxed_test() {
+    fn fallible() -> Result<(), Box<dyn Error + Send + Sync>> {
+        Err(ZipError::EntryNotFound)?;
+        Ok(())
+    }
+
+    let err = fallible().unwrap_err();
+    assert!(matches!(err.downcast_ref::<ZipError>(), Some(ZipError::EntryNotFound)));
+}
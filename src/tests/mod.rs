@@ -2,10 +2,14 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 pub(crate) mod combined;
+pub(crate) mod error;
 pub(crate) mod read;
 pub(crate) mod spec;
 pub(crate) mod write;
 
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder, ZipString};
+
 use std::sync::Once;
 static ENV_LOGGER: Once = Once::new();
 
@@ -14,3 +18,47 @@ static ENV_LOGGER: Once = Once::new();
 fn init_logger() {
     ENV_LOGGER.call_once(|| env_logger::Builder::from_default_env().format_module_path(true).init());
 }
+
+/// Returns a deterministic stream of pseudo-random values from a xorshift generator, for tests which need data that
+/// isn't trivially compressible.
+pub(crate) fn xorshift() -> impl Iterator<Item = u32> {
+    let mut state: u32 = 0x2545F491;
+
+    std::iter::repeat_with(move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    })
+}
+
+/// Writes a ZIP file containing an entry for each of the provided filenames and data, compressed with the provided
+/// method.
+pub(crate) async fn zip_file<N, D>(compression: Compression, entries: impl IntoIterator<Item = (N, D)>) -> Vec<u8>
+where
+    N: Into<ZipString>,
+    D: AsRef<[u8]>,
+{
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for (filename, data) in entries {
+        let entry = ZipEntryBuilder::new(filename.into(), compression);
+        writer.write_entry_whole(entry, data.as_ref()).await.unwrap();
+    }
+    writer.close().await.unwrap()
+}
+
+/// Returns an entry named `{index}.txt` for each of the provided lengths, containing its index repeated that many times.
+pub(crate) fn numbered_entries(lengths: impl IntoIterator<Item = usize>) -> impl Iterator<Item = (String, Vec<u8>)> {
+    lengths
+        .into_iter()
+        .enumerate()
+        .map(|(index, length)| (format!("{index}.txt"), index.to_string().repeat(length).into_bytes()))
+}
+
+/// Writes a ZIP file into the temporary directory under a path unique to the provided name and this process.
+#[cfg(feature = "tokio-fs")]
+pub(crate) async fn temp_zip_file(name: &str, data: Vec<u8>) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("async_zip_{name}_{}.zip", std::process::id()));
+    tokio::fs::write(&path, data).await.unwrap();
+    path
+}
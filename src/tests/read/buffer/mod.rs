@@ -0,0 +1,71 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncSeek, Cursor, SeekFrom};
+
+/// A reader which counts the number of reads made against the inner reader.
+struct CountingReader<R> {
+    inner: R,
+    reads: usize,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for CountingReader<R> {
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        self.reads += 1;
+        Pin::new(&mut self.inner).poll_read(c, b)
+    }
+}
+
+impl<R: AsyncSeek + Unpin> AsyncSeek for CountingReader<R> {
+    fn poll_seek(mut self: Pin<&mut Self>, c: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        Pin::new(&mut self.inner).poll_seek(c, pos)
+    }
+}
+
+/// Reads the first entry in chunks of 16 bytes, returning the number of reads made against the source.
+async fn count_entry_reads(capacity: Option<usize>) -> usize {
+    let data = vec![0xAB; 64 * 1024];
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("data.bin".into(), Compression::Stored), &data).await.unwrap();
+
+    let source = CountingReader { inner: Cursor::new(writer.close().await.unwrap()), reads: 0 };
+    let mut reader = ZipFileReader::new(source).await.unwrap();
+    if let Some(capacity) = capacity {
+        reader = reader.with_buffer_capacity(capacity);
+    }
+
+    reader.inner_mut().reads = 0;
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+    let mut output = Vec::new();
+    let mut chunk = [0; 16];
+
+    loop {
+        match entry_reader.read(&mut chunk).await.unwrap() {
+            0 => break,
+            read => output.extend_from_slice(&chunk[..read]),
+        }
+    }
+
+    entry_reader.verify().await.unwrap();
+    assert_eq!(output, data);
+
+    drop(entry_reader);
+    reader.inner_mut().reads
+}
+
+#[tokio::test]
+async fn buffered_entry_reads_test() {
+    // 4096 reads of 16 bytes are batched into reads of up to 8 KiB by default.
+    let default_reads = count_entry_reads(None).await;
+    assert!(default_reads <= 16, "{default_reads} reads made against the source");
+
+    let large_reads = count_entry_reads(Some(128 * 1024)).await;
+    assert!(large_reads < default_reads, "{large_reads} reads made against the source");
+}
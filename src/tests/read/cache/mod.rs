@@ -0,0 +1,74 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::tests::{numbered_entries, temp_zip_file, zip_file};
+use crate::tokio::read::fs::ZipFileReader;
+use crate::Compression;
+
+use std::path::PathBuf;
+
+/// Writes an archive holding three 100 byte entries and a single 1000 byte entry.
+async fn archive(name: &str) -> PathBuf {
+    let data = zip_file(Compression::Stored, numbered_entries([100, 100, 100, 1000])).await;
+    temp_zip_file(&format!("cache_{name}"), data).await
+}
+
+#[tokio::test]
+async fn cache_hit_test() {
+    let path = archive("hit").await;
+    let reader = ZipFileReader::new(&path).await.unwrap().with_cache(1024, 512);
+
+    let data = reader.read_entry(0).await.unwrap();
+    assert_eq!(reader.files_opened(), 1);
+
+    // Cached entries are served without reopening the file, including via clones.
+    assert_eq!(reader.read_entry(0).await.unwrap(), data);
+    assert_eq!(reader.clone().read_entry(0).await.unwrap(), data);
+    assert_eq!(reader.files_opened(), 1);
+
+    // Entries above the threshold bypass the cache.
+    reader.read_entry(3).await.unwrap();
+    reader.read_entry(3).await.unwrap();
+    assert_eq!(reader.files_opened(), 3);
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn cache_eviction_test() {
+    let path = archive("eviction").await;
+    let reader = ZipFileReader::new(&path).await.unwrap().with_cache(200, 512);
+
+    reader.read_entry(0).await.unwrap();
+    reader.read_entry(1).await.unwrap();
+    reader.read_entry(0).await.unwrap();
+    assert_eq!(reader.files_opened(), 2);
+
+    // The budget only fits two entries, so the least recently read (1) is evicted.
+    reader.read_entry(2).await.unwrap();
+    reader.read_entry(0).await.unwrap();
+    assert_eq!(reader.files_opened(), 3);
+    reader.read_entry(1).await.unwrap();
+    assert_eq!(reader.files_opened(), 4);
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn cache_disabled_test() {
+    let path = archive("disabled").await;
+    let mut reader = ZipFileReader::new(&path).await.unwrap();
+
+    reader.read_entry(0).await.unwrap();
+    reader.read_entry(0).await.unwrap();
+    assert_eq!(reader.files_opened(), 2);
+
+    // Refreshing clears the cache, so the entry is read from the file again (counted by the new pool).
+    reader = reader.with_cache(1024, 512);
+    reader.read_entry(0).await.unwrap();
+    reader.refresh().await.unwrap();
+    reader.read_entry(0).await.unwrap();
+    assert_eq!(reader.files_opened(), 1);
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
@@ -0,0 +1,94 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::tests::zip_file;
+use crate::Compression;
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncSeek, Cursor, SeekFrom};
+
+const ENTRY_SIZE: usize = 256 * 1024;
+
+/// A seekable source which returns [`Poll::Pending`] on every other read.
+struct IntermittentReader {
+    inner: Cursor<Vec<u8>>,
+    pending: bool,
+}
+
+impl AsyncRead for IntermittentReader {
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        self.pending = !self.pending;
+        if self.pending {
+            c.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        Pin::new(&mut self.inner).poll_read(c, b)
+    }
+}
+
+impl AsyncSeek for IntermittentReader {
+    fn poll_seek(mut self: Pin<&mut Self>, c: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        Pin::new(&mut self.inner).poll_seek(c, pos)
+    }
+}
+
+fn entry_data() -> Vec<u8> {
+    (0..ENTRY_SIZE).map(|index| (index % 251) as u8 ^ (index / 4096) as u8).collect()
+}
+
+async fn archive(compression: Compression) -> ZipFileReader<IntermittentReader> {
+    let data = zip_file(compression, [("data.bin", entry_data())]).await;
+    ZipFileReader::new(IntermittentReader { inner: Cursor::new(data), pending: false }).await.unwrap()
+}
+
+/// Reads the first entry in full, cancelling any read which doesn't complete on its first poll.
+async fn read_cancelling(reader: &mut ZipFileReader<IntermittentReader>) -> (Vec<u8>, usize) {
+    let mut entry = reader.reader_with_entry(0).await.unwrap();
+    let mut buffer = vec![0; 4096];
+    let mut data = Vec::new();
+    let mut cancelled = 0;
+
+    loop {
+        tokio::select! {
+            biased;
+            read = entry.read(&mut buffer) => match read.unwrap() {
+                0 => break,
+                read => data.extend_from_slice(&buffer[..read]),
+            },
+            _ = std::future::ready(()) => cancelled += 1,
+        }
+    }
+
+    entry.verify().await.unwrap();
+    (data, cancelled)
+}
+
+async fn check_cancelled_reads(compression: Compression) {
+    let mut reader = archive(compression).await;
+
+    let (data, cancelled) = read_cancelling(&mut reader).await;
+    assert!(cancelled > 0);
+    assert_eq!(data, entry_data());
+
+    // Abandon a reader mid-entry, and ensure a fresh reader still produces the full output.
+    let mut entry = reader.reader_with_entry(0).await.unwrap();
+    entry.read_exact(&mut [0; 1000]).await.unwrap();
+    drop(entry);
+
+    let (data, _) = read_cancelling(&mut reader).await;
+    assert_eq!(data, entry_data());
+}
+
+#[tokio::test]
+async fn cancelled_reads_stored_test() {
+    check_cancelled_reads(Compression::Stored).await;
+}
+
+#[tokio::test]
+async fn cancelled_reads_deflate_test() {
+    check_cancelled_reads(Compression::Deflate).await;
+}
@@ -21,6 +21,55 @@ compressed_test_helper!(zstd_test, Compression::Zstd, "foo bar", include_bytes!(
 #[cfg(feature = "xz")]
 compressed_test_helper!(xz_test, Compression::Xz, "foo bar", include_bytes!("xz.data"));
 
+/// Writes a single stored entry, then rewrites the compression method within both of its headers.
+async fn with_compression_method(method: u16) -> Vec<u8> {
+    use crate::base::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    let cdh_offset = data.windows(4).position(|window| window == crate::spec::consts::CDH_SIGNATURE.to_le_bytes());
+    for offset in [8, cdh_offset.unwrap() + 10] {
+        data[offset..offset + 2].copy_from_slice(&method.to_le_bytes());
+    }
+
+    data
+}
+
+#[tokio::test]
+async fn unsupported_method_test() {
+    use crate::base::read::{mem, stream};
+    use crate::error::ZipError;
+
+    // 99 is the AES encryption method, yet no AES extra field is present.
+    let data = with_compression_method(99).await;
+
+    let result = mem::ZipFileReader::new(data.clone()).await;
+    assert!(matches!(result, Err(ZipError::CompressionNotSupported(99))));
+
+    let result = stream::ZipFileReader::new(futures_util::io::Cursor::new(data)).next_with_entry().await;
+    assert!(matches!(result, Err(ZipError::CompressionNotSupported(99))));
+}
+
+#[tokio::test]
+async fn legacy_method_test() {
+    use crate::base::read::{mem, stream};
+    use crate::error::ZipError;
+
+    // Shrink, Reduce (with compression factors 1 to 4), and Implode.
+    for method in 1..=6 {
+        let data = with_compression_method(method).await;
+
+        let result = mem::ZipFileReader::new(data.clone()).await;
+        assert!(matches!(result, Err(ZipError::CompressionNotSupported(actual)) if actual == method));
+
+        let result = stream::ZipFileReader::new(futures_util::io::Cursor::new(data)).next_with_entry().await;
+        assert!(matches!(result, Err(ZipError::CompressionNotSupported(actual)) if actual == method));
+    }
+}
+
 /// A helper macro for generating a CompressedReader test using a specific compression method.
 macro_rules! compressed_test_helper {
     ($name:ident, $typ:expr, $data_raw:expr, $data:expr) => {
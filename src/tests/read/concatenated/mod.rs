@@ -0,0 +1,96 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::{mem, stream};
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use async_compression::futures::bufread::DeflateEncoder;
+use futures_util::io::{AsyncReadExt, BufReader, Cursor};
+
+const FIRST: &[u8] = b"The quick brown fox jumps over the lazy dog.\n";
+const SECOND: &[u8] = b"Pack my box with five dozen liquor jugs.\n";
+
+/// Compresses the provided data as a single, complete Deflate stream.
+async fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut compressed = Vec::new();
+    DeflateEncoder::new(BufReader::new(Cursor::new(data))).read_to_end(&mut compressed).await.unwrap();
+    compressed
+}
+
+/// Writes an entry whose compressed data is made up of the provided bytes, declared as decompressing to the provided
+/// data, followed by a stored entry.
+async fn archive(compressed: &[u8], declared: &[u8]) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let mut entry = ZipEntryBuilder::new("concatenated.txt".into(), Compression::Deflate)
+        .size(compressed.len() as u64, declared.len() as u64)
+        .build();
+    entry.crc32 = crc32fast::hash(declared);
+    writer.write_entry_raw(entry, compressed).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("next.txt".into(), Compression::Stored), b"next").await.unwrap();
+
+    writer.close().await.unwrap()
+}
+
+/// Returns the data of both streams, alongside their concatenated compressed forms.
+async fn concatenated() -> (Vec<u8>, Vec<u8>) {
+    let compressed = [deflate(FIRST).await, deflate(SECOND).await].concat();
+    ([FIRST, SECOND].concat(), compressed)
+}
+
+#[tokio::test]
+async fn concatenated_deflate_mem_test() {
+    let (data, compressed) = concatenated().await;
+    let reader = mem::ZipFileReader::new(archive(&compressed, &data).await).await.unwrap();
+
+    assert_eq!(reader.read_entry(0).await.unwrap(), data);
+    assert_eq!(reader.read_entry(1).await.unwrap(), b"next");
+}
+
+#[tokio::test]
+async fn concatenated_deflate_stream_test() {
+    let (data, compressed) = concatenated().await;
+    let reader = stream::ZipFileReader::new(Cursor::new(archive(&compressed, &data).await));
+
+    let mut entry = reader.next_with_entry().await.unwrap().unwrap();
+    let mut buffer = Vec::new();
+    entry.reader_mut().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data);
+
+    // The second stream must have been fully consumed for the next entry to be located.
+    let mut entry = entry.done().await.unwrap().next_with_entry().await.unwrap().unwrap();
+    assert_eq!(entry.reader().entry().filename().as_str().unwrap(), "next.txt");
+    buffer.clear();
+    entry.reader_mut().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, b"next");
+}
+
+#[tokio::test]
+async fn concatenated_deflate_declared_size_reached_test() {
+    // Once the declared size has been produced, anything following the first stream is ignored as before.
+    let compressed = [deflate(FIRST).await, vec![0xFF; 8]].concat();
+    let reader = mem::ZipFileReader::new(archive(&compressed, FIRST).await).await.unwrap();
+
+    assert_eq!(reader.read_entry(0).await.unwrap(), FIRST);
+}
+
+#[tokio::test]
+async fn concatenated_deflate_declared_size_unreachable_test() {
+    // The compressed data is exhausted before the declared size is reached.
+    let data = [FIRST, SECOND].concat();
+    let reader = mem::ZipFileReader::new(archive(&deflate(FIRST).await, &data).await).await.unwrap();
+
+    assert!(matches!(reader.read_entry(0).await, Err(ZipError::CRC32CheckError { .. })));
+}
+
+#[tokio::test]
+async fn concatenated_deflate_invalid_stream_test() {
+    // More data was declared and more input remains, but it isn't a valid Deflate stream.
+    let data = [FIRST, SECOND].concat();
+    let compressed = [deflate(FIRST).await, vec![0xFF; 8]].concat();
+    let reader = mem::ZipFileReader::new(archive(&compressed, &data).await).await.unwrap();
+
+    assert!(reader.read_entry(0).await.is_err());
+}
@@ -0,0 +1,52 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::error::ZipError;
+use crate::tests::zip_file;
+use crate::Compression;
+
+const DATA: &[u8] = b"Some entry data which is copied into another writer.";
+
+async fn archive() -> Vec<u8> {
+    zip_file(Compression::Stored, [("foo.txt", DATA)]).await
+}
+
+#[tokio::test]
+async fn copy_entry_to_test() {
+    let reader = ZipFileReader::new(archive().await).await.unwrap();
+
+    let mut output = Vec::new();
+    assert_eq!(reader.copy_entry_to(0, &mut output).await.unwrap(), DATA.len() as u64);
+    assert_eq!(output, DATA);
+
+    assert!(matches!(reader.copy_entry_to(1, Vec::new()).await, Err(ZipError::EntryIndexOutOfBounds)));
+}
+
+#[tokio::test]
+async fn copy_entry_to_crc_mismatch_test() {
+    let mut data = archive().await;
+    let offset = data.windows(DATA.len()).position(|window| window == DATA).unwrap();
+    data[offset] ^= 0xFF;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert!(matches!(reader.copy_entry_to(0, Vec::new()).await, Err(ZipError::CRC32CheckError { .. })));
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn copy_entry_to_file_test() {
+    let base = std::env::temp_dir().join(format!("async_zip_copy_{}", std::process::id()));
+    tokio::fs::create_dir_all(&base).await.unwrap();
+
+    let archive_path = base.join("archive.zip");
+    tokio::fs::write(&archive_path, archive().await).await.unwrap();
+
+    let reader = crate::tokio::read::fs::ZipFileReader::new(&archive_path).await.unwrap();
+    let output_path = base.join("foo.txt");
+    let file = tokio::fs::File::create(&output_path).await.unwrap();
+    assert_eq!(reader.copy_entry_to(0, file).await.unwrap(), DATA.len() as u64);
+    assert_eq!(tokio::fs::read(&output_path).await.unwrap(), DATA);
+
+    tokio::fs::remove_dir_all(&base).await.unwrap();
+}
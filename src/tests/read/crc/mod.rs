@@ -0,0 +1,99 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_util::io::AsyncReadExt;
+
+const ENTRY_DATA: &[u8] = b"The quick brown fox jumps over the lazy dog.";
+
+/// Writes a single stored entry and returns the ZIP file's bytes alongside the offset of the entry's data.
+async fn single_stored_entry() -> (Vec<u8>, usize) {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, ENTRY_DATA).await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    // The local file header (30 bytes) is immediately followed by the filename and an empty extra field.
+    (data, 30 + "foo.txt".len())
+}
+
+#[tokio::test]
+async fn crc_verify_test() {
+    let (data, _) = single_stored_entry().await;
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+    let mut buffer = Vec::new();
+    entry_reader.read_to_end(&mut buffer).await.unwrap();
+
+    assert_eq!(buffer, ENTRY_DATA);
+    assert!(entry_reader.verify().await.is_ok());
+}
+
+#[tokio::test]
+async fn crc_verify_corrupted_test() {
+    let (mut data, data_offset) = single_stored_entry().await;
+    data[data_offset] ^= 0xFF;
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+    let mut buffer = Vec::new();
+    let result = entry_reader.read_to_end_checked(&mut buffer).await;
+
+    let Err(ZipError::CRC32CheckError { expected, actual }) = result else { panic!("expected a CRC32 mismatch") };
+    assert_eq!(expected, crc32fast::hash(ENTRY_DATA));
+    assert_eq!(actual, crc32fast::hash(&buffer));
+}
+
+#[tokio::test]
+async fn crc_verify_disabled_test() {
+    let (mut data, data_offset) = single_stored_entry().await;
+    data[data_offset] ^= 0xFF;
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap().without_crc_check();
+    let mut buffer = Vec::new();
+    entry_reader.read_to_end_checked(&mut buffer).await.unwrap();
+
+    assert_eq!(buffer[0], ENTRY_DATA[0] ^ 0xFF);
+    assert_eq!(buffer[1..], ENTRY_DATA[1..]);
+}
+
+#[tokio::test]
+async fn crc_verify_partial_read_test() {
+    let (data, _) = single_stored_entry().await;
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+    let mut buffer = [0; 4];
+    entry_reader.read_exact(&mut buffer).await.unwrap();
+
+    assert!(matches!(entry_reader.verify().await, Err(ZipError::EOFNotReached)));
+
+    // The byte read whilst checking for EOF isn't lost, and is still covered by the CRC32 check.
+    let mut remaining = Vec::new();
+    entry_reader.read_to_end(&mut remaining).await.unwrap();
+    assert_eq!([&buffer[..], &remaining].concat(), ENTRY_DATA);
+    assert!(entry_reader.verify().await.is_ok());
+}
+
+#[tokio::test]
+async fn crc_header_mismatch_test() {
+    let (mut data, _) = single_stored_entry().await;
+
+    // Tamper with the CRC32 value within the local file header, leaving the central directory's intact.
+    let expected = crc32fast::hash(ENTRY_DATA);
+    data[14..18].copy_from_slice(&(expected ^ 1).to_le_bytes());
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].crc32(), expected);
+
+    let result = reader.reader_with_entry(0).await;
+    let Err(ZipError::HeaderMismatch { field, local, central }) = result else { panic!("expected a header mismatch") };
+    assert_eq!(field, "CRC32 value");
+    assert_eq!((local, central), ((expected ^ 1) as u64, expected as u64));
+}
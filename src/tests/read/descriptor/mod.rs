@@ -0,0 +1,102 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+#![cfg(feature = "deflate")]
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_util::io::AsyncWriteExt;
+
+/// Writes the data as entries via the stream writer (so with data descriptors) and then zeroes the sizes recorded
+/// within the central directory, as some streaming writers do.
+async fn zeroed_sizes_zip(entries: &[(&str, Compression, &[u8])]) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, compression, data) in entries {
+        let mut entry_writer =
+            writer.write_entry_stream(ZipEntryBuilder::new((*filename).into(), *compression)).await.unwrap();
+        entry_writer.write_all(data).await.unwrap();
+        entry_writer.close().await.unwrap();
+    }
+
+    let mut data = writer.close().await.unwrap();
+    let cd_offset = data.windows(4).position(|window| window == CDH_SIGNATURE.to_le_bytes()).unwrap();
+
+    let mut offset = cd_offset;
+    while data[offset..offset + 4] == CDH_SIGNATURE.to_le_bytes() {
+        data[offset + 20..offset + 28].fill(0);
+
+        let lengths = |at: usize| u16::from_le_bytes([data[offset + at], data[offset + at + 1]]) as usize;
+        offset += 46 + lengths(28) + lengths(30) + lengths(32);
+    }
+
+    data
+}
+
+#[tokio::test]
+async fn zeroed_sizes_deflate_test() {
+    let first = "foo bar ".repeat(1024);
+    let second = "lorem ipsum dolor sit amet\n".repeat(512);
+    let data = zeroed_sizes_zip(&[
+        ("first.txt", Compression::Deflate, first.as_bytes()),
+        ("second.txt", Compression::Deflate, second.as_bytes()),
+    ])
+    .await;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].compressed_size(), 0);
+
+    assert_eq!(reader.read_entry(0).await.unwrap(), first.as_bytes());
+    assert_eq!(reader.read_entry(1).await.unwrap(), second.as_bytes());
+}
+
+#[tokio::test]
+async fn zeroed_sizes_stored_test() {
+    // Stored data has no natural end, so all we can do is honour the recorded (zero) size.
+    let data = zeroed_sizes_zip(&[("foo.txt", Compression::Stored, b"foo bar")]).await;
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    let result = reader.read_entry(0).await;
+    assert!(matches!(result, Err(crate::error::ZipError::CRC32CheckError { .. })));
+}
+
+#[tokio::test]
+async fn data_descriptor_range_test() {
+    for force_no_zip64 in [false, true] {
+        let mut writer = ZipFileWriter::new(Vec::new());
+        if force_no_zip64 {
+            writer = writer.force_no_zip64();
+        }
+
+        for (filename, compression) in [("foo.txt", Compression::Deflate), ("bar.txt", Compression::Stored)] {
+            let mut entry_writer =
+                writer.write_entry_stream(ZipEntryBuilder::new(filename.into(), compression)).await.unwrap();
+            entry_writer.write_all(b"foo bar foo bar").await.unwrap();
+            entry_writer.close().await.unwrap();
+        }
+        writer.write_entry_whole(ZipEntryBuilder::new("baz.txt".into(), Compression::Stored), b"baz").await.unwrap();
+
+        let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+        let descriptor_length = if force_no_zip64 { 16 } else { 24 };
+
+        for index in 0..2 {
+            assert!(reader[index].has_data_descriptor());
+
+            let (data_start, data_end) = reader.compressed_data_range(index).await.unwrap();
+            assert_eq!(data_start, reader.data_offset(index).await.unwrap());
+            assert_eq!(data_end - data_start, reader[index].compressed_size());
+
+            // The descriptor immediately follows the entry's data, and the next entry immediately follows it.
+            let (start, end) = reader.data_descriptor_range(index).await.unwrap().unwrap();
+            assert_eq!((start, end - start), (data_end, descriptor_length), "for {index} ({force_no_zip64})");
+            assert_eq!(end, reader[index + 1].header_offset());
+        }
+
+        assert!(!reader[2].has_data_descriptor());
+        assert_eq!(reader.data_descriptor_range(2).await.unwrap(), None);
+        assert_eq!(reader.compressed_data_range(2).await.unwrap().1 - reader.data_offset(2).await.unwrap(), 3);
+    }
+}
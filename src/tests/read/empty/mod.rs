@@ -0,0 +1,75 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::{mem, seek, stream};
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_util::io::Cursor;
+
+/// Writes an archive of zero-byte entries, including ones whose compressed data is missing entirely (as some writers
+/// produce for empty files) and ones holding an empty compressed stream.
+async fn archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    writer.write_entry_whole(ZipEntryBuilder::new("stored.txt".into(), Compression::Stored), &[]).await.unwrap();
+
+    #[cfg(feature = "deflate")]
+    {
+        let entry = ZipEntryBuilder::new("deflate.txt".into(), Compression::Deflate);
+        writer.write_entry_whole(entry, &[]).await.unwrap();
+
+        let entry = ZipEntryBuilder::new("deflate-raw.txt".into(), Compression::Deflate).size(0u64, 0u64);
+        writer.write_entry_raw(entry, &[]).await.unwrap();
+    }
+
+    #[cfg(feature = "bzip2")]
+    {
+        let entry = ZipEntryBuilder::new("bzip2-raw.txt".into(), Compression::Bz).size(0u64, 0u64);
+        writer.write_entry_raw(entry, &[]).await.unwrap();
+    }
+
+    // A single non-empty entry after the empty ones ensures each empty entry's data was fully consumed.
+    writer.write_entry_whole(ZipEntryBuilder::new("last.txt".into(), Compression::Stored), b"data").await.unwrap();
+
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn empty_entries_mem_test() {
+    let reader = mem::ZipFileReader::new(archive().await).await.unwrap();
+    let last = reader.len() - 1;
+
+    for index in 0..last {
+        assert!(reader.read_entry(index).await.unwrap().is_empty());
+    }
+    assert_eq!(reader.read_entry(last).await.unwrap(), b"data");
+}
+
+#[tokio::test]
+async fn empty_entries_seek_test() {
+    let mut reader = seek::ZipFileReader::new(Cursor::new(archive().await)).await.unwrap();
+    let last = reader.len() - 1;
+
+    for index in 0..last {
+        assert!(reader.read_entry(index).await.unwrap().is_empty());
+    }
+    reader.validate().await.unwrap();
+}
+
+#[tokio::test]
+async fn empty_entries_stream_test() {
+    let mut zip = stream::ZipFileReader::new(Cursor::new(archive().await));
+    let mut entries = Vec::new();
+
+    while let Some(mut entry) = zip.next_with_entry().await.unwrap() {
+        let mut data = Vec::new();
+        entry.reader_mut().read_to_end_checked(&mut data).await.unwrap();
+        entries.push(data);
+        zip = entry.done().await.unwrap();
+    }
+
+    let (last, empty) = entries.split_last().unwrap();
+    assert!(empty.iter().all(|data| data.is_empty()));
+    assert_eq!(last, b"data");
+}
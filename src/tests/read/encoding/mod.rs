@@ -0,0 +1,72 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, StringEncoding, ZipEntryBuilder, ZipString};
+
+/// Writes a single stored entry with the given filename and returns the ZIP file's bytes.
+async fn single_entry(filename: ZipString) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new(filename, Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn cp437_filename_test() {
+    // "résumé.txt" encoded in CP437, written without the language encoding flag.
+    let raw = b"r\x82sum\x82.txt".to_vec();
+    let reader =
+        ZipFileReader::new(single_entry(ZipString::new(raw.clone(), StringEncoding::Raw)).await).await.unwrap();
+    let filename = reader.file().entries()[0].filename();
+
+    assert!(matches!(filename.encoding(), StringEncoding::Raw));
+    assert_eq!(filename.as_bytes(), raw);
+    assert_eq!(filename.as_str().unwrap(), "résumé.txt");
+}
+
+#[tokio::test]
+async fn utf8_filename_test() {
+    // The same filename encoded in UTF-8, written with the language encoding flag.
+    let reader = ZipFileReader::new(single_entry("résumé.txt".into()).await).await.unwrap();
+    let filename = reader.file().entries()[0].filename();
+
+    assert!(matches!(filename.encoding(), StringEncoding::Utf8));
+    assert_eq!(filename.as_str().unwrap(), "résumé.txt");
+}
+
+#[tokio::test]
+async fn filename_bytes_test() {
+    // Bytes which aren't valid UTF-8 fall back to the raw encoding, so are written without the language encoding flag.
+    let raw = b"foo\xFF\xFE.txt".to_vec();
+    let reader =
+        ZipFileReader::new(single_entry(ZipString::new(raw.clone(), StringEncoding::Utf8)).await).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert_eq!(entry.filename_bytes(), raw);
+    assert_eq!(reader.file().entry(&raw).map(|(index, _)| index), Some(0));
+    assert_eq!(reader.file().entry(entry.filename().as_str().unwrap()).map(|(index, _)| index), Some(0));
+    assert!(reader.file().entry("foo.txt").is_none());
+}
+
+#[tokio::test]
+async fn entry_comment_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).comment("Ünïcödé comment".into());
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new(ZipString::new(b"bar.txt".to_vec(), StringEncoding::Raw), Compression::Stored)
+        .comment(ZipString::new(b"r\x82sum\x82".to_vec(), StringEncoding::Raw));
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("baz.txt".into(), Compression::Stored), b"baz").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entries = reader.file().entries();
+
+    // Comments are decoded following the same language encoding flag as the filename.
+    assert!(matches!(entries[0].comment().encoding(), StringEncoding::Utf8));
+    assert_eq!(entries[0].comment().as_str().unwrap(), "Ünïcödé comment");
+    assert!(matches!(entries[1].comment().encoding(), StringEncoding::Raw));
+    assert_eq!(entries[1].comment().as_str().unwrap(), "résumé");
+    assert!(entries[2].comment().as_bytes().is_empty());
+}
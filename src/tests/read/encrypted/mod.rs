@@ -0,0 +1,31 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE};
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn encrypted_entries_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for filename in ["plain.txt", "secret.txt", "also_plain.txt"] {
+        let entry = ZipEntryBuilder::new(filename.into(), Compression::Stored);
+        writer.write_entry_whole(entry, b"foo").await.unwrap();
+    }
+    let mut data = writer.close().await.unwrap();
+
+    // Set the encryption bit within both headers of the second entry, as its data is never read.
+    let lfh_offset = data.windows(4).enumerate().filter(|(_, window)| *window == LFH_SIGNATURE.to_le_bytes());
+    let cdh_offset = data.windows(4).enumerate().filter(|(_, window)| *window == CDH_SIGNATURE.to_le_bytes());
+    let lfh_offset = lfh_offset.map(|(offset, _)| offset).nth(1).unwrap();
+    let cdh_offset = cdh_offset.map(|(offset, _)| offset).nth(1).unwrap();
+    for offset in [lfh_offset + 6, cdh_offset + 8] {
+        data[offset] |= 0x1;
+    }
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.encrypted_entries(), [1]);
+    assert_eq!(reader[1].filename().as_str().unwrap(), "secret.txt");
+    assert_eq!(reader[1].uncompressed_size(), 3);
+}
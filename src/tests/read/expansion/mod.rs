@@ -0,0 +1,47 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::error::ZipError;
+use crate::tests::zip_file;
+use crate::Compression;
+
+const ENTRY_SIZE: usize = 1024 * 1024;
+const THRESHOLD: u64 = 64 * 1024;
+
+/// Returns an archive holding a single entry of zeros, which deflates at a ratio of roughly 1000:1.
+async fn archive(compression: Compression) -> Vec<u8> {
+    zip_file(compression, [("zeros.bin", vec![0; ENTRY_SIZE])]).await
+}
+
+#[tokio::test]
+async fn expansion_limit_exceeded_test() {
+    let reader = ZipFileReader::new(archive(Compression::Deflate).await).await.unwrap();
+    assert!(reader.file().entries()[0].compressed_size() * 100 < ENTRY_SIZE as u64);
+
+    let reader = reader.with_expansion_limit(100, THRESHOLD);
+    assert!(matches!(reader.read_entry(0).await, Err(ZipError::SuspiciousExpansion(100))));
+}
+
+#[tokio::test]
+async fn expansion_limit_not_set_test() {
+    let reader = ZipFileReader::new(archive(Compression::Deflate).await).await.unwrap();
+    assert_eq!(reader.read_entry(0).await.unwrap(), vec![0; ENTRY_SIZE]);
+}
+
+#[tokio::test]
+async fn expansion_limit_within_ratio_test() {
+    let reader = ZipFileReader::new(archive(Compression::Deflate).await).await.unwrap();
+    let reader = reader.with_expansion_limit(u64::MAX, THRESHOLD);
+    assert_eq!(reader.read_entry(0).await.unwrap(), vec![0; ENTRY_SIZE]);
+
+    let reader = reader.with_expansion_limit(100, ENTRY_SIZE as u64);
+    assert_eq!(reader.read_entry(0).await.unwrap(), vec![0; ENTRY_SIZE]);
+}
+
+#[tokio::test]
+async fn expansion_limit_stored_test() {
+    let reader = ZipFileReader::new(archive(Compression::Stored).await).await.unwrap();
+    let reader = reader.with_expansion_limit(1, 0);
+    assert_eq!(reader.read_entry(0).await.unwrap(), vec![0; ENTRY_SIZE]);
+}
@@ -0,0 +1,23 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+#![cfg(any(feature = "sync", feature = "tokio-fs"))]
+
+use crate::error::ZipError;
+use crate::utils::sanitize_symlink_target;
+
+use std::path::Path;
+
+#[test]
+fn sanitize_symlink_target_test() {
+    let link = Path::new("foo/bar/link");
+
+    assert!(sanitize_symlink_target(link, "target.txt").is_ok());
+    assert!(sanitize_symlink_target(link, "../../target.txt").is_ok());
+    assert!(sanitize_symlink_target(link, "./baz/target.txt").is_ok());
+
+    // Escapes the extraction directory, is absolute, or steps through a (potential) link via a parent component.
+    for target in ["../../../target.txt", "/etc/passwd", "baz/../target.txt"] {
+        assert!(matches!(sanitize_symlink_target(link, target), Err(ZipError::UnsafePath(path)) if path == target));
+    }
+}
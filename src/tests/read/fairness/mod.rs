@@ -0,0 +1,51 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::tests::zip_file;
+use crate::Compression;
+
+use std::sync::Mutex;
+
+use futures_util::io::AsyncReadExt;
+
+const ENTRY_SIZE: usize = 4 * 1024 * 1024;
+
+async fn archive() -> ZipFileReader {
+    let data = zip_file(Compression::Stored, ["foo.bin", "bar.bin"].map(|filename| (filename, vec![0; ENTRY_SIZE])));
+    ZipFileReader::new(data.await).await.unwrap()
+}
+
+/// Reads an entry in full, logging the index of the entry after each read.
+async fn read_logged(reader: &ZipFileReader, index: usize, budget: u64, log: &Mutex<Vec<usize>>) {
+    let mut entry = reader.reader_with_entry(index).await.unwrap().with_yield_budget(budget);
+    let mut buffer = vec![0; 64 * 1024];
+
+    while entry.read(&mut buffer).await.unwrap() != 0 {
+        log.lock().unwrap().push(index);
+    }
+    entry.verify().await.unwrap();
+}
+
+/// Returns whether or not the second entry made progress before the first entry was fully read.
+async fn interleaved(budget: u64) -> bool {
+    let reader = archive().await;
+    let log = Mutex::new(Vec::new());
+
+    tokio::join!(read_logged(&reader, 0, budget, &log), read_logged(&reader, 1, budget, &log));
+
+    let log = log.into_inner().unwrap();
+    let first_of_second = log.iter().position(|index| *index == 1).unwrap();
+    let last_of_first = log.iter().rposition(|index| *index == 0).unwrap();
+    first_of_second < last_of_first
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn yield_budget_interleaves_test() {
+    assert!(interleaved(256 * 1024).await);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn yield_budget_disabled_test() {
+    assert!(!interleaved(0).await);
+}
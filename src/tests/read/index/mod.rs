@@ -0,0 +1,199 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn entry_lookup_many_test() {
+    const NUM_ENTRIES: usize = 10_000;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for index in 0..NUM_ENTRIES {
+        let entry = ZipEntryBuilder::new(format!("dir/{index}.txt").into(), Compression::Stored);
+        writer.write_entry_whole(entry, index.to_string().as_bytes()).await.unwrap();
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    for index in (0..NUM_ENTRIES).rev() {
+        let filename = format!("dir/{index}.txt");
+        let (found, entry) = reader.file().entry(&filename).expect("entry should be found");
+
+        assert_eq!(found, index);
+        assert_eq!(entry.filename().as_str().unwrap(), filename);
+    }
+
+    assert!(reader.file().entry("dir/10000.txt").is_none());
+}
+
+#[tokio::test]
+async fn name_index_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for (filename, data) in [("foo.txt", b"foo"), ("dir/bar.txt", b"bar"), ("foo.txt", b"dup")] {
+        writer.write_entry_whole(ZipEntryBuilder::new(filename.into(), Compression::Stored), data).await.unwrap();
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let index = reader.name_index();
+
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.get("foo.txt"), Some(&0));
+    assert_eq!(index.get("dir/bar.txt"), Some(&1));
+
+    for entry in reader.file().entries() {
+        assert!(index.contains_key(entry.filename().as_str().unwrap()));
+    }
+}
+
+#[tokio::test]
+async fn entry_lookup_duplicate_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for data in [b"first", b"other", b"again"] {
+        let filename = if data == b"other" { "bar.txt" } else { "foo.txt" };
+        writer.write_entry_whole(ZipEntryBuilder::new(filename.into(), Compression::Stored), data).await.unwrap();
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    assert_eq!(reader.file().entry("foo.txt").map(|(index, _)| index), Some(0));
+    assert_eq!(reader.file().entry("bar.txt").map(|(index, _)| index), Some(1));
+}
+
+#[tokio::test]
+async fn entry_lookup_backslash_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("dir\\".into(), Compression::Stored), b"").await.unwrap();
+    for (filename, data) in [("dir\\file.txt", b"backslash".as_slice()), ("dir/other.txt", b"forward")] {
+        writer.write_entry_whole(ZipEntryBuilder::new(filename.into(), Compression::Stored), data).await.unwrap();
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    let (index, entry) = reader.file().entry("dir/file.txt").expect("entry should be found");
+    assert_eq!(index, 1);
+    assert_eq!(entry.filename_bytes(), b"dir\\file.txt");
+    assert_eq!(entry.sanitized_path().unwrap(), std::path::Path::new("dir").join("file.txt"));
+    assert_eq!(reader.read_entry_by_name("dir/file.txt").await.unwrap(), b"backslash");
+
+    // Exact matches are unaffected, and either separator may be used within queries.
+    assert_eq!(reader.file().entry("dir\\file.txt").map(|(index, _)| index), Some(1));
+    assert_eq!(reader.file().entry("dir\\other.txt").map(|(index, _)| index), Some(2));
+    assert!(reader.file().entry("dir/file.txt/").is_none());
+
+    assert!(reader.file().entries()[0].is_dir());
+}
+
+#[tokio::test]
+async fn entry_lookup_raw_test() {
+    use crate::{StringEncoding, ZipString};
+
+    let raw = vec![b'd', b'i', b'r', b'\\', 0xFF, b'.', b't', b'x', b't'];
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new(ZipString::new(raw.clone(), StringEncoding::Raw), Compression::Stored);
+    writer.write_entry_whole(entry, b"raw").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    // Filenames which aren't valid UTF-8 are found via their raw bytes, with either separator.
+    assert_eq!(reader.file().entry(&raw).map(|(index, _)| index), Some(1));
+    assert_eq!(reader.file().entry(b"dir/\xFF.txt").map(|(index, _)| index), Some(1));
+    assert!(reader.file().entry(b"dir/\xFE.txt").is_none());
+}
+
+#[tokio::test]
+async fn entry_index_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".into(), Compression::Stored), b"bar").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    assert_eq!(reader[0].filename().as_str().unwrap(), "foo.txt");
+    assert_eq!(reader[1].filename().as_str().unwrap(), "bar.txt");
+    assert_eq!(reader.file()[1].filename().as_str().unwrap(), "bar.txt");
+    assert!(reader.get(2).is_none());
+
+    let result = std::panic::catch_unwind(|| reader[2].filename().clone());
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn entries_matching_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for filename in ["assets/", "assets/foo.png", "src/main.rs", "assets/bar/baz.png", "assets.txt"] {
+        writer.write_entry_whole(ZipEntryBuilder::new(filename.into(), Compression::Stored), b"").await.unwrap();
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    let indices: Vec<_> = reader.entries_matching("assets/").map(|(index, _)| index).collect();
+    assert_eq!(indices, [0, 1, 3]);
+
+    let (index, entry) = reader.entries_matching("src/").next().unwrap();
+    assert_eq!((index, entry.filename().as_str().unwrap()), (2, "src/main.rs"));
+
+    assert_eq!(reader.entries_matching("").count(), 5);
+    assert_eq!(reader.entries_matching("docs/").count(), 0);
+}
+
+#[tokio::test]
+async fn entry_at_offset_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for (filename, data) in [("foo.txt", b"foo data".as_slice()), ("bar.txt", b"bar data"), ("baz.txt", b"baz data")] {
+        writer.write_entry_whole(ZipEntryBuilder::new(filename.into(), Compression::Stored), data).await.unwrap();
+    }
+
+    // Prepended data shifts every entry, with offsets being relative to the start of the reader.
+    let mut data = vec![0; 16];
+    data.extend(writer.close().await.unwrap());
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    for (index, needle) in [(0, b"foo data"), (1, b"bar data"), (2, b"baz data")] {
+        let offset = reader.data().windows(needle.len()).position(|window| window == needle).unwrap() as u64;
+        for offset in [reader[index].header_offset(), offset, offset + needle.len() as u64 - 1] {
+            let (found, entry) = reader.entry_at_offset(offset).unwrap();
+            assert_eq!((found, entry.header_offset()), (index, reader[index].header_offset()));
+        }
+    }
+
+    // Neither the prepended data nor the central directory belong to an entry.
+    assert!(reader.entry_at_offset(0).is_none());
+    let directory_start = reader.metadata().directory_offset() + 16;
+    assert_eq!(reader.entry_at_offset(directory_start - 1).map(|(index, _)| index), Some(2));
+    assert!(reader.entry_at_offset(directory_start).is_none());
+    assert!(reader.entry_at_offset(u64::MAX).is_none());
+}
+
+#[tokio::test]
+async fn reader_for_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for (filename, data) in [("foo.txt", b"foo"), ("bar.txt", b"bar")] {
+        writer.write_entry_whole(ZipEntryBuilder::new(filename.into(), Compression::Stored), data).await.unwrap();
+    }
+    let data = writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    let (_, entry) = reader.file().entry("bar.txt").unwrap();
+    let mut buffer = Vec::new();
+    reader.reader_for(entry).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, b"bar");
+
+    // A clone is matched, whereas an entry from another ZIP file (at a differing offset) isn't.
+    let mut seek_reader =
+        crate::base::read::seek::ZipFileReader::new(futures_util::io::Cursor::new(data)).await.unwrap();
+    let entry = seek_reader.file().entry("foo.txt").unwrap().1.clone();
+    buffer.clear();
+    seek_reader.reader_for(&entry).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, b"foo");
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("other.txt".into(), Compression::Stored), b"").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".into(), Compression::Stored), b"bar").await.unwrap();
+    let other = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let (_, foreign) = other.file().entry("bar.txt").unwrap();
+    assert!(matches!(reader.reader_for(foreign).await, Err(crate::error::ZipError::ForeignEntry)));
+}
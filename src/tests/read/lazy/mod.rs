@@ -0,0 +1,34 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::LFH_SIGNATURE;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn open_without_local_headers_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for filename in ["foo.txt", "bar.txt", "baz.txt"] {
+        let entry = ZipEntryBuilder::new(filename.into(), Compression::Stored);
+        writer.write_entry_whole(entry, filename.as_bytes()).await.unwrap();
+    }
+    let mut data = writer.close().await.unwrap();
+
+    // Corrupt the signature of every local file header, so that any attempt to read one fails.
+    let offsets = data.windows(4).enumerate().filter(|(_, window)| *window == LFH_SIGNATURE.to_le_bytes());
+    let offsets: Vec<_> = offsets.map(|(offset, _)| offset).collect();
+    assert_eq!(offsets.len(), 3);
+    offsets.into_iter().for_each(|offset| data[offset] = 0);
+
+    // Opening the ZIP file only reads its central directory.
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let filenames: Vec<_> = reader.file().entries().iter().map(|entry| entry.filename().as_str().unwrap()).collect();
+    assert_eq!(filenames, ["foo.txt", "bar.txt", "baz.txt"]);
+    assert_eq!(reader[1].uncompressed_size(), 7);
+
+    // Local file headers are instead read and validated once an entry's data is accessed.
+    let result = reader.reader_without_entry(1).await;
+    assert!(matches!(result, Err(ZipError::UnexpectedHeaderError(_, LFH_SIGNATURE))));
+}
@@ -0,0 +1,69 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::EOCDR_SIGNATURE;
+use crate::spec::header::{ExtraField, HeaderId, UnknownExtraField};
+use crate::{Compression, ZipEntryBuilder};
+
+/// Writes a ZIP file with a single entry holding an unknown extra field, returning its data alongside the offset of
+/// the EOCDR.
+async fn single_entry_zip() -> (Vec<u8>, usize) {
+    let field = UnknownExtraField { header_id: HeaderId::Other(0xCAFE), data_size: 4, content: vec![1, 2, 3, 4] };
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored)
+        .extra_fields(vec![ExtraField::UnknownExtraField(field)]);
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+
+    let data = writer.close().await.unwrap();
+    let offset = data.windows(4).rposition(|window| window == EOCDR_SIGNATURE.to_le_bytes()).unwrap();
+
+    (data, offset)
+}
+
+#[tokio::test]
+async fn lenient_overrunning_extra_field_test() {
+    let (mut data, offset) = single_entry_zip().await;
+
+    // Declare the central directory's extra field as longer than the space given to it.
+    let cd_offset = u32::from_le_bytes(data[offset + 16..offset + 20].try_into().unwrap()) as usize;
+    let field_offset = cd_offset + 46 + "foo.txt".len();
+    data[field_offset + 2..field_offset + 4].copy_from_slice(&8u16.to_le_bytes());
+
+    let result = ZipFileReader::new(data.clone()).await;
+    assert!(matches!(result, Err(ZipError::InvalidExtraFieldHeader(8, 4))));
+
+    let reader = ZipFileReader::new_lenient(data).await.unwrap();
+    assert!(reader[0].extra_fields().is_empty());
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"bar");
+}
+
+#[tokio::test]
+async fn lenient_mismatched_entry_counts_test() {
+    let (mut data, offset) = single_entry_zip().await;
+
+    // Record a differing number of entries on this disk, despite the archive not spanning multiple disks.
+    data[offset + 8..offset + 10].copy_from_slice(&0u16.to_le_bytes());
+
+    let result = ZipFileReader::new(data.clone()).await;
+    assert!(matches!(result, Err(ZipError::SpannedArchiveUnsupported)));
+
+    let reader = ZipFileReader::new_lenient(data).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 1);
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"bar");
+}
+
+#[tokio::test]
+async fn lenient_spanned_archive_test() {
+    let (mut data, offset) = single_entry_zip().await;
+
+    // Archives which span multiple disks remain unsupported.
+    data[offset + 4..offset + 6].copy_from_slice(&1u16.to_le_bytes());
+    data[offset + 6..offset + 8].copy_from_slice(&1u16.to_le_bytes());
+
+    let result = ZipFileReader::new_lenient(data).await;
+    assert!(matches!(result, Err(ZipError::SpannedArchiveUnsupported)));
+}
@@ -0,0 +1,49 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::error::ZipError;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::tests::zip_file;
+use crate::Compression;
+
+const ENTRY_SIZE: usize = 1024 * 1024;
+
+/// Returns an archive holding a single highly-compressible entry, whose declared uncompressed size is overridden.
+async fn archive(declared_size: Option<u32>) -> Vec<u8> {
+    let mut data = zip_file(Compression::Deflate, [("zeros.bin", vec![0; ENTRY_SIZE])]).await;
+
+    if let Some(size) = declared_size {
+        let cdh_offset = data.windows(4).position(|window| window == CDH_SIGNATURE.to_le_bytes()).unwrap();
+        data[cdh_offset + 24..cdh_offset + 28].copy_from_slice(&size.to_le_bytes());
+    }
+
+    data
+}
+
+#[tokio::test]
+async fn read_entry_limited_test() {
+    let reader = ZipFileReader::new(archive(None).await).await.unwrap();
+    assert!(reader.file().entries()[0].compressed_size() < 64 * 1024);
+
+    assert_eq!(reader.read_entry_limited(0, ENTRY_SIZE as u64).await.unwrap(), vec![0; ENTRY_SIZE]);
+    assert_eq!(reader.read_entry_limited(0, u64::MAX).await.unwrap().len(), ENTRY_SIZE);
+}
+
+#[tokio::test]
+async fn read_entry_limited_declared_size_test() {
+    let reader = ZipFileReader::new(archive(None).await).await.unwrap();
+
+    let result = reader.read_entry_limited(0, ENTRY_SIZE as u64 - 1).await;
+    assert!(matches!(result, Err(ZipError::EntryTooLarge(limit)) if limit == ENTRY_SIZE as u64 - 1));
+}
+
+#[tokio::test]
+async fn read_entry_limited_decompressed_size_test() {
+    // The declared size is within the limit, but the entry's data decompresses to exceed it.
+    let reader = ZipFileReader::new(archive(Some(100)).await).await.unwrap();
+    assert_eq!(reader.file().entries()[0].uncompressed_size(), 100);
+
+    let result = reader.read_entry_limited(0, 1000).await;
+    assert!(matches!(result, Err(ZipError::EntryTooLarge(1000))));
+}
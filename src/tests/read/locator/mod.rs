@@ -1,6 +1,8 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+use crate::base::read::io::locator::EOCDR_LOWER_BOUND;
+
 #[test]
 fn search_one_byte_test() {
     let buffer: &[u8] = &[0x0, 0x0, 0x0, 0x0, 0x0, 0x0];
@@ -33,7 +35,7 @@ async fn locator_empty_test() {
 
     let data = &include_bytes!("empty.zip");
     let mut cursor = Cursor::new(data);
-    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor).await;
+    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor, EOCDR_LOWER_BOUND).await;
 
     assert!(eocdr.is_ok());
     assert_eq!(eocdr.unwrap(), 4);
@@ -45,7 +47,7 @@ async fn locator_empty_max_comment_test() {
 
     let data = &include_bytes!("empty-with-max-comment.zip");
     let mut cursor = Cursor::new(data);
-    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor).await;
+    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor, EOCDR_LOWER_BOUND).await;
 
     assert!(eocdr.is_ok());
     assert_eq!(eocdr.unwrap(), 4);
@@ -57,8 +59,96 @@ async fn locator_buffer_boundary_test() {
 
     let data = &include_bytes!("empty-buffer-boundary.zip");
     let mut cursor = Cursor::new(data);
-    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor).await;
+    let eocdr = crate::base::read::io::locator::eocdr(&mut cursor, EOCDR_LOWER_BOUND).await;
 
     assert!(eocdr.is_ok());
     assert_eq!(eocdr.unwrap(), 4);
 }
+
+/// Writes a ZIP file with a single entry and the given archive comment, returning the file's bytes.
+async fn commented_zip(comment: &str) -> Vec<u8> {
+    use crate::base::write::ZipFileWriter;
+    use crate::{Compression, ZipEntryBuilder};
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.comment(comment.to_string());
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn locator_max_comment_with_entry_test() {
+    let comment = "a".repeat(u16::MAX as usize);
+    let reader = crate::base::read::mem::ZipFileReader::new(commented_zip(&comment).await).await.unwrap();
+
+    assert_eq!(reader.file().entries().len(), 1);
+    assert_eq!(reader.file().comment().as_str().unwrap(), comment);
+}
+
+#[tokio::test]
+async fn locator_comment_containing_signature_test() {
+    // A comment which embeds what looks like another (empty) EOCDR, which the reverse search reaches first.
+    let mut comment = "PK\x05\x06".to_string();
+    comment.push_str(&"\0".repeat(crate::spec::consts::EOCDR_LENGTH));
+    comment.push_str(" trailing text");
+
+    let data = commented_zip(&comment).await;
+    let eocdr =
+        crate::base::read::io::locator::eocdr(futures_util::io::Cursor::new(&data), EOCDR_LOWER_BOUND).await.unwrap();
+    assert_eq!(eocdr as usize, data.len() - comment.len() - crate::spec::consts::EOCDR_LENGTH);
+
+    let reader = crate::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 1);
+    assert_eq!(reader.file().comment().as_str().unwrap(), comment);
+}
+
+#[tokio::test]
+async fn locator_large_comment_test() {
+    // Spans many of the locator's buffers, so the signature is only found after a number of backward reads.
+    let comment = "0123456789".repeat(4 * 1024);
+    let data = commented_zip(&comment).await;
+
+    let eocdr =
+        crate::base::read::io::locator::eocdr(futures_util::io::Cursor::new(&data), EOCDR_LOWER_BOUND).await.unwrap();
+    assert_eq!(eocdr as usize, data.len() - comment.len() - crate::spec::consts::EOCDR_LENGTH);
+
+    let reader = crate::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().comment().as_str().unwrap(), comment);
+}
+
+#[tokio::test]
+async fn locator_not_found_test() {
+    use crate::error::ZipError;
+
+    // Larger than the maximum comment window, so the locator gives up before reaching the start of the data.
+    let data = vec![0; u16::MAX as usize * 2];
+    let eocdr = crate::base::read::io::locator::eocdr(futures_util::io::Cursor::new(&data), EOCDR_LOWER_BOUND).await;
+    assert!(matches!(eocdr, Err(ZipError::NotAZipFile)));
+
+    let eocdr =
+        crate::base::read::io::locator::eocdr(futures_util::io::Cursor::new(&data[..10]), EOCDR_LOWER_BOUND).await;
+    assert!(matches!(eocdr, Err(ZipError::NotAZipFile)));
+}
+
+#[tokio::test]
+async fn locator_search_window_test() {
+    use crate::base::read::ReadOptions;
+    use crate::error::ZipError;
+
+    // Trailing garbage beyond the maximum comment length buries the EOCDR deeper than the default search window.
+    let mut data = commented_zip("").await;
+    data.resize(data.len() + u16::MAX as usize * 2, 0);
+
+    let result = crate::base::read::mem::ZipFileReader::new(data.clone()).await;
+    assert!(matches!(result, Err(ZipError::UnexpectedEof)));
+
+    let options = ReadOptions { max_eocd_search: 4 * u16::MAX as u64, ..Default::default() };
+    let reader = crate::base::read::mem::ZipFileReader::new_with_options(data.clone(), options).await.unwrap();
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"foo");
+
+    // A window smaller than the default rejects EOCDRs which would otherwise be found.
+    let options = ReadOptions { max_eocd_search: 16, ..Default::default() };
+    let data = commented_zip("").await;
+    let result = crate::base::read::mem::ZipFileReader::new_with_options(data, options).await;
+    assert!(matches!(result, Err(ZipError::UnexpectedEof)));
+}
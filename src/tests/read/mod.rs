@@ -1,6 +1,38 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+pub(crate) mod buffer;
+#[cfg(feature = "tokio-fs")]
+pub(crate) mod cache;
+#[cfg(feature = "deflate")]
+pub(crate) mod cancel;
 pub(crate) mod compression;
+#[cfg(feature = "deflate")]
+pub(crate) mod concatenated;
+pub(crate) mod copy;
+pub(crate) mod crc;
+pub(crate) mod descriptor;
+pub(crate) mod empty;
+pub(crate) mod encoding;
+pub(crate) mod encrypted;
+#[cfg(feature = "deflate")]
+pub(crate) mod expansion;
+pub(crate) mod extract;
+pub(crate) mod fairness;
+pub(crate) mod index;
+pub(crate) mod lazy;
+pub(crate) mod lenient;
+#[cfg(feature = "deflate")]
+pub(crate) mod limited;
 pub(crate) mod locator;
+pub(crate) mod order;
+#[cfg(feature = "tokio-fs")]
+pub(crate) mod pool;
+pub(crate) mod prefix;
+pub(crate) mod raw;
+pub(crate) mod refresh;
+pub(crate) mod seek;
+pub(crate) mod spanned;
+pub(crate) mod stream;
+pub(crate) mod truncated;
 pub(crate) mod zip64;
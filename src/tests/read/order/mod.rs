@@ -0,0 +1,57 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::file::ZipFile;
+use crate::{Compression, ZipEntryBuilder};
+
+const ENTRY_NAMES: [&str; 4] = ["a.txt", "b.txt", "c.txt", "d.txt"];
+
+/// Writes a stored entry per name and returns the ZIP file's bytes.
+async fn stored_entries() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for name in ENTRY_NAMES {
+        let entry = ZipEntryBuilder::new(name.into(), Compression::Stored);
+        writer.write_entry_whole(entry, name.as_bytes()).await.unwrap();
+    }
+
+    writer.close().await.unwrap()
+}
+
+/// Asserts that the data offsets of the provided entries are strictly increasing.
+async fn assert_monotonic(reader: &ZipFileReader, indices: &[usize]) {
+    let mut previous = None;
+
+    for &index in indices {
+        let offset = reader.data_offset(index).await.unwrap();
+        assert!(previous.is_none_or(|previous| previous < offset));
+        previous = Some(offset);
+    }
+}
+
+#[tokio::test]
+async fn order_matches_central_directory_test() {
+    let reader = ZipFileReader::new(stored_entries().await).await.unwrap();
+    let order = reader.entries_in_disk_order();
+
+    assert_eq!(order, vec![0, 1, 2, 3]);
+    assert_monotonic(&reader, &order).await;
+}
+
+#[tokio::test]
+async fn order_differs_from_central_directory_test() {
+    let data = stored_entries().await;
+    let file = ZipFileReader::new(data.clone()).await.unwrap().file().clone();
+
+    // Reverse the order in which the central directory lists the entries, leaving their data in place.
+    let mut entries = file.entries.clone();
+    entries.reverse();
+    let reader = ZipFileReader::from_raw_parts(data, ZipFile::new(entries, file.zip64, file.comment.clone()));
+    let order = reader.entries_in_disk_order();
+
+    assert_eq!(order, vec![3, 2, 1, 0]);
+    assert_monotonic(&reader, &order).await;
+    assert_eq!(reader.file().entries()[order[0]].filename().as_str().unwrap(), "a.txt");
+}
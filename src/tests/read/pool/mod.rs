@@ -0,0 +1,115 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::tests::{numbered_entries, temp_zip_file, zip_file};
+use crate::tokio::read::fs::ZipFileReader;
+use crate::Compression;
+
+use std::path::PathBuf;
+
+const ENTRIES: usize = 8;
+
+async fn archive(name: &str) -> PathBuf {
+    let data = zip_file(Compression::Stored, numbered_entries([100; ENTRIES])).await;
+    temp_zip_file(&format!("pool_{name}"), data).await
+}
+
+/// Reads every entry concurrently, in reverse order, and checks their contents.
+async fn read_all(reader: &ZipFileReader) {
+    let reads = (0..ENTRIES).rev().map(|index| async move { (index, reader.read_entry(index).await.unwrap()) });
+    for (index, data) in futures_util::future::join_all(reads).await {
+        assert_eq!(data, index.to_string().repeat(100).as_bytes());
+    }
+}
+
+#[tokio::test]
+async fn pool_reuses_handles_test() {
+    let path = archive("reuse").await;
+    let reader = ZipFileReader::new(&path).await.unwrap().with_pool_size(2);
+
+    for index in 0..ENTRIES {
+        reader.read_entry(index).await.unwrap();
+    }
+    assert_eq!(reader.files_opened(), 1);
+
+    // Concurrent readers each need a handle, but any beyond the pool's size are closed once dropped. The readers are
+    // held at once, as reads which happen to complete in turn would otherwise reuse the same handle.
+    let concurrent = || futures_util::future::try_join_all((0..ENTRIES).map(|index| reader.raw_reader(index)));
+    drop(concurrent().await.unwrap());
+    assert_eq!(reader.files_opened(), ENTRIES);
+
+    drop(concurrent().await.unwrap());
+    assert_eq!(reader.files_opened(), ENTRIES + ENTRIES - 2);
+    read_all(&reader).await;
+
+    // Clones share the pool.
+    let clone = reader.clone();
+    clone.read_entry(0).await.unwrap();
+    drop(clone.raw_reader(1).await.unwrap());
+    assert_eq!(clone.files_opened(), reader.files_opened());
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn pool_disabled_test() {
+    let path = archive("disabled").await;
+    let reader = ZipFileReader::new(&path).await.unwrap();
+
+    for index in 0..ENTRIES {
+        reader.read_entry(index).await.unwrap();
+    }
+    assert_eq!(reader.files_opened(), ENTRIES);
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn pool_refresh_test() {
+    let path = archive("refresh").await;
+    let mut reader = ZipFileReader::new(&path).await.unwrap().with_pool_size(4);
+    reader.read_entry(0).await.unwrap();
+
+    // Refreshing replaces the pool, as the file may have been replaced, so its handle count starts afresh.
+    reader.refresh().await.unwrap();
+    reader.read_entry(0).await.unwrap();
+    reader.read_entry(1).await.unwrap();
+    assert_eq!(reader.files_opened(), 1);
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn pool_stream_test() {
+    use futures_util::io::AsyncReadExt;
+    use futures_util::StreamExt;
+
+    let path = archive("stream").await;
+    let reader = ZipFileReader::new(&path).await.unwrap().with_pool_size(1);
+    let clone = reader.clone();
+
+    // Each entry reader's handle is reused by the next once dropped.
+    let mut entries = Box::pin(reader.into_stream());
+    while let Some(result) = entries.next().await {
+        let (entry, mut entry_reader) = result.unwrap();
+        let mut data = String::new();
+        entry_reader.read_to_string(&mut data).await.unwrap();
+        assert_eq!(format!("{}.txt", &data[..1]), entry.filename().as_str().unwrap());
+    }
+    assert_eq!(clone.files_opened(), 1);
+
+    // Entry readers which are held at once are given their own handles, so don't share a cursor.
+    let mut entries = Box::pin(clone.clone().into_stream());
+    let (_, mut first) = entries.next().await.unwrap().unwrap();
+    let (_, mut second) = entries.next().await.unwrap().unwrap();
+    let mut buffer = [0; 50];
+    for _ in 0..2 {
+        first.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(buffer, [b'0'; 50]);
+        second.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(buffer, [b'1'; 50]);
+    }
+    assert_eq!(clone.files_opened(), 2);
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
@@ -0,0 +1,45 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::error::ZipError;
+use crate::tests::zip_file;
+use crate::Compression;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+fn png_data() -> Vec<u8> {
+    let mut data = PNG_SIGNATURE.to_vec();
+    data.extend((0..64 * 1024).map(|index| (index % 7) as u8));
+    data
+}
+
+async fn archive(compression: Compression) -> ZipFileReader {
+    let entries = [("image.png", png_data()), ("short.txt", b"abc".to_vec()), ("empty.txt", Vec::new())];
+    ZipFileReader::new(zip_file(compression, entries).await).await.unwrap()
+}
+
+async fn check_read_entry_prefix(compression: Compression) {
+    let reader = archive(compression).await;
+
+    assert_eq!(reader.read_entry_prefix(0, PNG_SIGNATURE.len()).await.unwrap(), PNG_SIGNATURE);
+    assert_eq!(reader.read_entry_prefix(0, 0).await.unwrap(), b"");
+    assert_eq!(reader.read_entry_prefix(0, 1000).await.unwrap(), &png_data()[..1000]);
+
+    // Entries shorter than the requested length return all of their data.
+    assert_eq!(reader.read_entry_prefix(1, PNG_SIGNATURE.len()).await.unwrap(), b"abc");
+    assert_eq!(reader.read_entry_prefix(2, PNG_SIGNATURE.len()).await.unwrap(), b"");
+
+    assert!(matches!(reader.read_entry_prefix(3, 8).await, Err(ZipError::EntryIndexOutOfBounds)));
+}
+
+#[tokio::test]
+async fn read_entry_prefix_stored_test() {
+    check_read_entry_prefix(Compression::Stored).await;
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn read_entry_prefix_deflate_test() {
+    check_read_entry_prefix(Compression::Deflate).await;
+}
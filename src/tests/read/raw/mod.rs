@@ -0,0 +1,57 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem;
+use crate::base::read::seek;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_util::io::{AsyncReadExt, Cursor};
+
+async fn zip_with_entry(compression: Compression, data: &[u8]) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("data.txt".into(), compression), data).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("next.txt".into(), Compression::Stored), b"next").await.unwrap();
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn raw_reader_stored_test() {
+    let zip = zip_with_entry(Compression::Stored, b"foo bar").await;
+    let mut reader = seek::ZipFileReader::new(Cursor::new(zip)).await.unwrap();
+
+    let mut raw = Vec::new();
+    reader.raw_reader(0).await.unwrap().read_to_end(&mut raw).await.unwrap();
+    assert_eq!(raw, b"foo bar");
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn raw_reader_deflate_test() {
+    use async_compression::futures::bufread::DeflateDecoder;
+    use futures_util::io::BufReader;
+
+    let data = "lorem ipsum dolor sit amet\n".repeat(512);
+    let zip = zip_with_entry(Compression::Deflate, data.as_bytes()).await;
+    let reader = mem::ZipFileReader::new(zip).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    // The raw bytes are exactly the compressed data, which decompress to the original content.
+    let mut raw = Vec::new();
+    reader.raw_reader(0).await.unwrap().read_to_end(&mut raw).await.unwrap();
+    assert_eq!(entry.compression(), Compression::Deflate);
+    assert_eq!(raw.len() as u64, entry.compressed_size());
+
+    let mut decompressed = Vec::new();
+    DeflateDecoder::new(BufReader::new(Cursor::new(raw))).read_to_end(&mut decompressed).await.unwrap();
+    assert_eq!(decompressed, data.as_bytes());
+    assert_eq!(crc32fast::hash(&decompressed), entry.crc32());
+}
+
+#[tokio::test]
+async fn raw_reader_out_of_bounds_test() {
+    let zip = zip_with_entry(Compression::Stored, b"foo bar").await;
+    let reader = mem::ZipFileReader::new(zip).await.unwrap();
+
+    assert!(matches!(reader.raw_reader(2).await, Err(crate::error::ZipError::EntryIndexOutOfBounds)));
+}
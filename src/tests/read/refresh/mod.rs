@@ -0,0 +1,50 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::tests::{numbered_entries, zip_file};
+use crate::Compression;
+
+use futures_util::io::Cursor;
+
+async fn archive(entries: usize) -> Vec<u8> {
+    zip_file(Compression::Stored, numbered_entries(vec![1; entries])).await
+}
+
+#[tokio::test]
+async fn refresh_seek_test() {
+    let mut reader = ZipFileReader::new(Cursor::new(archive(1).await)).await.unwrap();
+    assert_eq!(reader.len(), 1);
+
+    *reader.inner_mut() = Cursor::new(archive(3).await);
+    reader.refresh().await.unwrap();
+    assert_eq!(reader.len(), 3);
+    assert_eq!(reader[2].filename().as_str().unwrap(), "2.txt");
+
+    // A truncated source is rejected, leaving the existing entries intact.
+    let mut truncated = archive(4).await;
+    truncated.truncate(truncated.len() - 10);
+    *reader.inner_mut() = Cursor::new(truncated);
+    assert!(reader.refresh().await.is_err());
+    assert_eq!(reader.len(), 3);
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn refresh_fs_test() {
+    let path = crate::tests::temp_zip_file("refresh", archive(1).await).await;
+
+    let mut reader = crate::tokio::read::fs::ZipFileReader::new(&path).await.unwrap();
+    assert_eq!(reader.len(), 1);
+
+    tokio::fs::write(&path, archive(2).await).await.unwrap();
+    reader.refresh().await.unwrap();
+    assert_eq!(reader.len(), 2);
+    assert_eq!(reader.read_entry(1).await.unwrap(), b"1");
+
+    tokio::fs::write(&path, b"PK\x03\x04").await.unwrap();
+    assert!(reader.refresh().await.is_err());
+    assert_eq!(reader.len(), 2);
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
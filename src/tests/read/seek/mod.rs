@@ -0,0 +1,65 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_util::io::{AsyncReadExt, AsyncSeekExt, Cursor, SeekFrom};
+
+async fn single_entry_zip(compression: Compression, data: &[u8]) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("data.bin".into(), compression), data).await.unwrap();
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn seek_stored_entry_test() {
+    let data: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+    let zip = single_entry_zip(Compression::Stored, &data).await;
+
+    let mut reader = ZipFileReader::new(Cursor::new(zip)).await.unwrap();
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+
+    let mut buffer = [0; 16];
+    assert_eq!(entry_reader.seek(SeekFrom::Start(5_000)).await.unwrap(), 5_000);
+    entry_reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data[5_000..5_016]);
+
+    assert_eq!(entry_reader.seek(SeekFrom::Current(-32)).await.unwrap(), 4_984);
+    entry_reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data[4_984..5_000]);
+
+    // Checking for EOF mid-entry doesn't move the reader's position.
+    assert!(entry_reader.verify().await.is_err());
+    assert_eq!(entry_reader.seek(SeekFrom::Current(0)).await.unwrap(), 5_000);
+
+    assert_eq!(entry_reader.seek(SeekFrom::End(-16)).await.unwrap(), 9_984);
+    let mut remaining = Vec::new();
+    entry_reader.read_to_end(&mut remaining).await.unwrap();
+    assert_eq!(remaining, data[9_984..]);
+
+    // Seeks are clamped to the entry's size, so never reach into the data which follows.
+    assert_eq!(entry_reader.seek(SeekFrom::Start(20_000)).await.unwrap(), 10_000);
+    assert_eq!(entry_reader.read(&mut buffer).await.unwrap(), 0);
+    assert!(entry_reader.seek(SeekFrom::Current(-20_000)).await.is_err());
+
+    // Rewinding to the start allows the entry to be read & verified in full.
+    entry_reader.seek(SeekFrom::Start(0)).await.unwrap();
+    entry_reader.compute_hash();
+    let mut full = Vec::new();
+    entry_reader.read_to_end_checked(&mut full).await.unwrap();
+    assert_eq!(full, data);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn seek_compressed_entry_test() {
+    let zip = single_entry_zip(Compression::Deflate, "foo bar ".repeat(256).as_bytes()).await;
+
+    let mut reader = ZipFileReader::new(Cursor::new(zip)).await.unwrap();
+    let mut entry_reader = reader.reader_without_entry(0).await.unwrap();
+
+    let err = entry_reader.seek(SeekFrom::Start(8)).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+}
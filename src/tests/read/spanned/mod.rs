@@ -0,0 +1,59 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::{EOCDR_SIGNATURE, ZIP64_EOCDL_SIGNATURE};
+use crate::{Compression, ZipEntryBuilder};
+
+/// Writes a ZIP file with a single entry, returning its data alongside the offset of the EOCDR.
+async fn single_entry_zip(force_zip64: bool) -> (Vec<u8>, usize) {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    if force_zip64 {
+        writer = writer.force_zip64();
+    }
+
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"bar").await.unwrap();
+
+    let data = writer.close().await.unwrap();
+    let offset = data.windows(4).rposition(|window| window == EOCDR_SIGNATURE.to_le_bytes()).unwrap();
+
+    (data, offset)
+}
+
+#[tokio::test]
+async fn spanned_disk_number_test() {
+    let (mut data, offset) = single_entry_zip(false).await;
+
+    // Declare this as the last of three disks, with the central directory starting on the second.
+    data[offset + 4..offset + 6].copy_from_slice(&2u16.to_le_bytes());
+    data[offset + 6..offset + 8].copy_from_slice(&1u16.to_le_bytes());
+
+    let result = ZipFileReader::new(data).await;
+    assert!(matches!(result, Err(ZipError::SpannedArchiveUnsupported)));
+}
+
+#[tokio::test]
+async fn spanned_same_disk_test() {
+    let (mut data, offset) = single_entry_zip(false).await;
+
+    // The central directory starting on the same (non-zero) disk as the EOCDR still implies prior disks exist.
+    data[offset + 4..offset + 6].copy_from_slice(&1u16.to_le_bytes());
+    data[offset + 6..offset + 8].copy_from_slice(&1u16.to_le_bytes());
+
+    let result = ZipFileReader::new(data).await;
+    assert!(matches!(result, Err(ZipError::SpannedArchiveUnsupported)));
+}
+
+#[tokio::test]
+async fn spanned_zip64_locator_test() {
+    let (mut data, _) = single_entry_zip(true).await;
+    assert!(ZipFileReader::new(data.clone()).await.is_ok());
+
+    let offset = data.windows(4).rposition(|window| window == ZIP64_EOCDL_SIGNATURE.to_le_bytes()).unwrap();
+    data[offset + 16..offset + 20].copy_from_slice(&3u32.to_le_bytes());
+
+    let result = ZipFileReader::new(data).await;
+    assert!(matches!(result, Err(ZipError::SpannedArchiveUnsupported)));
+}
@@ -0,0 +1,111 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::stream::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_util::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio_util::compat::TokioAsyncReadCompatExt;
+
+const ENTRIES: &[(&str, &[u8])] = &[
+    ("foo.txt", b"The quick brown fox jumps over the lazy dog."),
+    ("bar/baz.txt", b"Lorem ipsum dolor sit amet, consectetur adipiscing elit."),
+    ("empty.txt", b""),
+];
+
+/// Writes the given ZIP file's bytes into one end of a small in-memory pipe (as a socket would deliver them) and reads
+/// every entry back out of the other end with the streaming reader.
+async fn read_through_pipe(data: Vec<u8>) -> Vec<(String, Vec<u8>)> {
+    let (mut tx, rx) = tokio::io::duplex(64);
+    let producer = tokio::spawn(async move {
+        // The reader stops (and drops its end of the pipe) upon reaching the central directory.
+        let _ = tx.write_all(&data).await;
+    });
+
+    let mut entries = Vec::new();
+    let mut zip = ZipFileReader::new(rx.compat());
+
+    while let Some(mut entry) = zip.next_with_entry().await.unwrap() {
+        let filename = entry.reader().entry().filename().as_str().unwrap().to_string();
+        let mut buffer = Vec::new();
+        entry.reader_mut().read_to_end(&mut buffer).await.unwrap();
+
+        entries.push((filename, buffer));
+        zip = entry.done().await.unwrap();
+    }
+
+    producer.await.unwrap();
+    entries
+}
+
+fn assert_entries_match(actual: Vec<(String, Vec<u8>)>) {
+    assert_eq!(actual.len(), ENTRIES.len());
+
+    for ((filename, data), (expected_filename, expected_data)) in actual.iter().zip(ENTRIES) {
+        assert_eq!(filename, expected_filename);
+        assert_eq!(data, expected_data);
+    }
+}
+
+#[tokio::test]
+async fn stream_read_stored_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in ENTRIES {
+        let entry = ZipEntryBuilder::new((*filename).into(), Compression::Stored);
+        writer.write_entry_whole(entry, data).await.unwrap();
+    }
+
+    assert_entries_match(read_through_pipe(writer.close().await.unwrap()).await);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stream_read_data_descriptor_test() {
+    use futures_util::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in ENTRIES {
+        let entry = ZipEntryBuilder::new((*filename).into(), Compression::Deflate);
+        let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+        entry_writer.write_all(data).await.unwrap();
+        entry_writer.close().await.unwrap();
+    }
+
+    assert_entries_match(read_through_pipe(writer.close().await.unwrap()).await);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stream_read_data_descriptor_without_zip64_test() {
+    use futures_util::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new()).force_no_zip64();
+
+    for (filename, data) in ENTRIES {
+        let entry = ZipEntryBuilder::new((*filename).into(), Compression::Deflate);
+        let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+        entry_writer.write_all(data).await.unwrap();
+        entry_writer.close().await.unwrap();
+    }
+
+    assert_entries_match(read_through_pipe(writer.close().await.unwrap()).await);
+}
+
+#[tokio::test]
+async fn stream_read_stored_data_descriptor_unsupported_test() {
+    use futures_util::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(ENTRIES[0].1).await.unwrap();
+    entry_writer.close().await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let zip = ZipFileReader::new(futures_util::io::Cursor::new(data));
+    assert!(matches!(zip.next_with_entry().await, Err(crate::error::ZipError::FeatureNotSupported(_))));
+}
@@ -0,0 +1,63 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::LFH_LENGTH;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn truncated_entry_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar baz").await.unwrap();
+    let data = writer.close().await.unwrap();
+    let file = ZipFileReader::new(data.clone()).await.unwrap().file().clone();
+
+    // Truncate the data in the middle of the entry's data, whilst retaining the (previously read) central directory.
+    let data_offset = 4 + LFH_LENGTH + "foo.txt".len();
+    let reader = ZipFileReader::from_raw_parts(data[..data_offset + 4].to_vec(), file);
+
+    let expected = (data_offset as u64 + 11, data_offset as u64 + 4);
+    let result = reader.reader_without_entry(0).await;
+    assert!(matches!(result, Err(ZipError::TruncatedEntry(end, length)) if (end, length) == expected));
+    assert!(matches!(reader.raw_reader(0).await, Err(ZipError::TruncatedEntry(..))));
+}
+
+async fn archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for filename in ["foo.txt", "bar.txt"] {
+        let entry = ZipEntryBuilder::new(filename.into(), Compression::Stored);
+        writer.write_entry_whole(entry, b"foo bar baz").await.unwrap();
+    }
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn not_a_zip_file_test() {
+    let data: Vec<u8> = crate::tests::xorshift().take(4096).map(|state| state as u8).collect();
+
+    assert!(matches!(ZipFileReader::new(data).await, Err(ZipError::NotAZipFile)));
+    assert!(matches!(ZipFileReader::new(Vec::new()).await, Err(ZipError::NotAZipFile)));
+}
+
+#[tokio::test]
+async fn truncated_archive_test() {
+    let data = archive().await;
+
+    // Truncating the end of the file removes the EOCDR, but the data still starts with a local file header.
+    for length in [data.len() - 10, data.len() / 2, 4] {
+        let result = ZipFileReader::new(data[..length].to_vec()).await;
+        assert!(matches!(result, Err(ZipError::UnexpectedEof)), "{length}: {:?}", result.err());
+    }
+
+    // The EOCDR remains intact, but the central directory it declares ends prematurely.
+    let file = ZipFileReader::new(data.clone()).await.unwrap().file().clone();
+    let directory_offset = file.metadata().directory_offset() as usize;
+    let mut truncated = data[..directory_offset + 10].to_vec();
+    truncated.extend_from_slice(&data[data.len() - 22..]);
+
+    let result = ZipFileReader::new(truncated).await;
+    assert!(matches!(result, Err(ZipError::UnexpectedEof)), "{:?}", result.err());
+}
@@ -105,3 +105,211 @@ async fn test_read_zip64_archive_many_entries() {
         assert_eq!(contents, "\n");
     }
 }
+
+/// Tests parsing a stubbed zip64 archive whose single entry's local file header is located beyond 4 GiB.
+///
+/// Only the relative header offset is set to the zip64 sentinel value within the central directory record, so the
+/// zip64 extended information extra field only holds that offset.
+#[tokio::test]
+async fn test_read_zip64_stubbed_large_offset() {
+    use crate::spec::consts::{
+        CDH_SIGNATURE, EOCDR_SIGNATURE, NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE, ZIP64_EOCDL_SIGNATURE,
+        ZIP64_EOCDR_SIGNATURE,
+    };
+    use crate::spec::header::{
+        CentralDirectoryRecord, EndOfCentralDirectoryHeader, GeneralPurposeFlag, Zip64EndOfCentralDirectoryLocator,
+        Zip64EndOfCentralDirectoryRecord,
+    };
+    use futures_util::io::Cursor;
+
+    init_logger();
+
+    const LARGE_OFFSET: u64 = 5 * 1024 * 1024 * 1024;
+    let filename = b"large.txt";
+
+    let mut extra_field = Vec::new();
+    extra_field.extend_from_slice(&0x0001u16.to_le_bytes());
+    extra_field.extend_from_slice(&8u16.to_le_bytes());
+    extra_field.extend_from_slice(&LARGE_OFFSET.to_le_bytes());
+
+    let header = CentralDirectoryRecord {
+        v_made_by: 0,
+        v_needed: 45,
+        flags: GeneralPurposeFlag { encrypted: false, data_descriptor: false, filename_unicode: false, other: 0 },
+        compression: 0,
+        mod_time: 0,
+        mod_date: 0,
+        crc: 0,
+        compressed_size: 13,
+        uncompressed_size: 13,
+        file_name_length: filename.len() as u16,
+        extra_field_length: extra_field.len() as u16,
+        file_comment_length: 0,
+        disk_start: 0,
+        inter_attr: 0,
+        exter_attr: 0,
+        lh_offset: NON_ZIP64_MAX_SIZE,
+    };
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&CDH_SIGNATURE.to_le_bytes());
+    data.extend_from_slice(&header.as_slice());
+    data.extend_from_slice(filename);
+    data.extend_from_slice(&extra_field);
+
+    let directory_size = data.len() as u64;
+    let zip64_eocdr = Zip64EndOfCentralDirectoryRecord {
+        size_of_zip64_end_of_cd_record: 44,
+        version_made_by: 45,
+        version_needed_to_extract: 45,
+        disk_number: 0,
+        disk_number_start_of_cd: 0,
+        num_entries_in_directory_on_disk: 1,
+        num_entries_in_directory: 1,
+        directory_size,
+        offset_of_start_of_directory: 0,
+    };
+    let zip64_eocdl = Zip64EndOfCentralDirectoryLocator {
+        number_of_disk_with_start_of_zip64_end_of_central_directory: 0,
+        relative_offset: data.len() as u64,
+        total_number_of_disks: 1,
+    };
+    let eocdr = EndOfCentralDirectoryHeader {
+        disk_num: 0,
+        start_cent_dir_disk: 0,
+        num_of_entries_disk: NON_ZIP64_MAX_NUM_FILES,
+        num_of_entries: NON_ZIP64_MAX_NUM_FILES,
+        size_cent_dir: NON_ZIP64_MAX_SIZE,
+        cent_dir_offset: NON_ZIP64_MAX_SIZE,
+        file_comm_length: 0,
+    };
+
+    data.extend_from_slice(&ZIP64_EOCDR_SIGNATURE.to_le_bytes());
+    data.extend_from_slice(&zip64_eocdr.as_bytes());
+    data.extend_from_slice(&ZIP64_EOCDL_SIGNATURE.to_le_bytes());
+    data.extend_from_slice(&zip64_eocdl.as_bytes());
+    data.extend_from_slice(&EOCDR_SIGNATURE.to_le_bytes());
+    data.extend_from_slice(&eocdr.as_slice());
+
+    let file = crate::base::read::file(Cursor::new(data), Default::default())
+        .await
+        .expect("failed to parse stubbed zip64 archive");
+
+    assert!(file.zip64());
+    assert_eq!(file.entries().len(), 1);
+
+    let entry = &file.entries()[0];
+    assert_eq!(entry.filename().as_str().unwrap(), "large.txt");
+    assert_eq!(entry.header_offset(), LARGE_OFFSET);
+    assert_eq!(entry.compressed_size(), 13);
+    assert_eq!(entry.uncompressed_size(), 13);
+}
+
+/// Builds a non-zip64 archive holding a single Stored entry whose central directory record has the provided sizes &
+/// offset, alongside the provided zip64 extended information extra field values.
+fn stubbed_zip64_field_archive(data: &[u8], compressed_size: u32, lh_offset: u32, extra_values: &[u64]) -> Vec<u8> {
+    use crate::spec::consts::{CDH_SIGNATURE, EOCDR_SIGNATURE, LFH_SIGNATURE};
+    use crate::spec::header::{
+        CentralDirectoryRecord, EndOfCentralDirectoryHeader, GeneralPurposeFlag, LocalFileHeader,
+    };
+
+    let filename = b"data.txt";
+    let flags = GeneralPurposeFlag { encrypted: false, data_descriptor: false, filename_unicode: false, other: 0 };
+    let crc = crc32fast::hash(data);
+
+    let mut extra_field = Vec::new();
+    extra_field.extend_from_slice(&0x0001u16.to_le_bytes());
+    extra_field.extend_from_slice(&(extra_values.len() as u16 * 8).to_le_bytes());
+    extra_values.iter().for_each(|value| extra_field.extend_from_slice(&value.to_le_bytes()));
+
+    let lfh = LocalFileHeader {
+        version: 10,
+        flags,
+        compression: 0,
+        mod_time: 0,
+        mod_date: 0,
+        crc,
+        compressed_size: data.len() as u32,
+        uncompressed_size: data.len() as u32,
+        file_name_length: filename.len() as u16,
+        extra_field_length: 0,
+    };
+
+    let mut archive = Vec::new();
+    archive.extend_from_slice(&LFH_SIGNATURE.to_le_bytes());
+    archive.extend_from_slice(&lfh.as_slice());
+    archive.extend_from_slice(filename);
+    archive.extend_from_slice(data);
+
+    let header = CentralDirectoryRecord {
+        v_made_by: 0,
+        v_needed: 45,
+        flags,
+        compression: 0,
+        mod_time: 0,
+        mod_date: 0,
+        crc,
+        compressed_size,
+        uncompressed_size: data.len() as u32,
+        file_name_length: filename.len() as u16,
+        extra_field_length: extra_field.len() as u16,
+        file_comment_length: 0,
+        disk_start: 0,
+        inter_attr: 0,
+        exter_attr: 0,
+        lh_offset,
+    };
+
+    let cd_offset = archive.len();
+    archive.extend_from_slice(&CDH_SIGNATURE.to_le_bytes());
+    archive.extend_from_slice(&header.as_slice());
+    archive.extend_from_slice(filename);
+    archive.extend_from_slice(&extra_field);
+
+    let eocdr = EndOfCentralDirectoryHeader {
+        disk_num: 0,
+        start_cent_dir_disk: 0,
+        num_of_entries_disk: 1,
+        num_of_entries: 1,
+        size_cent_dir: (archive.len() - cd_offset) as u32,
+        cent_dir_offset: cd_offset as u32,
+        file_comm_length: 0,
+    };
+
+    archive.extend_from_slice(&EOCDR_SIGNATURE.to_le_bytes());
+    archive.extend_from_slice(&eocdr.as_slice());
+    archive
+}
+
+/// Tests reading an entry whose compressed size is only stored within the zip64 extended information extra field,
+/// despite the archive itself not being zip64.
+#[tokio::test]
+async fn test_read_zip64_field_compressed_size() {
+    use crate::spec::consts::NON_ZIP64_MAX_SIZE;
+
+    init_logger();
+
+    let archive = stubbed_zip64_field_archive(ZIP64_ZIP_CONTENTS.as_bytes(), NON_ZIP64_MAX_SIZE, 0, &[13]);
+    let reader = crate::base::read::mem::ZipFileReader::new(archive).await.unwrap();
+
+    assert!(!reader.file().zip64());
+    assert_eq!(reader.file().entries()[0].compressed_size(), 13);
+    assert_eq!(reader.file().entries()[0].uncompressed_size(), 13);
+    assert_eq!(reader.read_entry(0).await.unwrap(), ZIP64_ZIP_CONTENTS.as_bytes());
+}
+
+/// Tests that a sentinel relative header offset without a corresponding value in the zip64 extended information extra
+/// field is rejected, rather than being taken literally.
+#[tokio::test]
+async fn test_read_zip64_field_missing_offset() {
+    use crate::error::ZipError;
+    use crate::spec::consts::NON_ZIP64_MAX_SIZE;
+
+    init_logger();
+
+    let archive =
+        stubbed_zip64_field_archive(ZIP64_ZIP_CONTENTS.as_bytes(), NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE, &[13]);
+    let result = crate::base::read::mem::ZipFileReader::new(archive).await;
+
+    assert!(matches!(result, Err(ZipError::Zip64ExtendedFieldIncomplete)));
+}
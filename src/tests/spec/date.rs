@@ -12,3 +12,103 @@ fn date_conversion_test() {
     let result_dt = zip_dt.as_chrono().single().expect("expected single unique result");
     assert_eq!(result_dt, original_dt);
 }
+
+#[test]
+#[cfg(feature = "chrono")]
+fn date_conversion_clamped_test() {
+    let before_dt = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+    let zip_dt = crate::ZipDateTime::from_chrono(&before_dt);
+    assert_eq!(zip_dt.as_chrono().single(), Utc.with_ymd_and_hms(1980, 1, 1, 0, 0, 0).single());
+
+    let after_dt = Utc.with_ymd_and_hms(2200, 1, 1, 0, 0, 0).unwrap();
+    let zip_dt = crate::ZipDateTime::from_chrono(&after_dt);
+    assert_eq!(zip_dt.as_chrono().single(), Utc.with_ymd_and_hms(2107, 12, 31, 23, 59, 58).single());
+}
+
+/// Writes a single stored entry built with the given extra fields and returns it as read back from the central
+/// directory.
+#[cfg(feature = "chrono")]
+async fn read_back_entry(builder: crate::ZipEntryBuilder) -> crate::ZipEntry {
+    let mut writer = crate::base::write::ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(builder, b"foo").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = crate::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    reader.file().entries()[0].entry.clone()
+}
+
+#[tokio::test]
+#[cfg(feature = "chrono")]
+async fn last_modified_dos_test() {
+    let original_dt = Utc.with_ymd_and_hms(2023, 3, 14, 15, 9, 26).unwrap();
+    let builder = crate::ZipEntryBuilder::new("foo.txt".into(), crate::Compression::Stored)
+        .last_modification_date(crate::ZipDateTime::from_chrono(&original_dt));
+
+    let entry = read_back_entry(builder).await;
+    assert_eq!(entry.last_modified(), Some(original_dt));
+}
+
+#[tokio::test]
+#[cfg(feature = "chrono")]
+async fn last_modified_extended_timestamp_test() {
+    use crate::spec::header::{ExtraField, HeaderId, UnknownExtraField};
+
+    // The DOS date & time can only represent even seconds, whereas the extended timestamp is precise to the second.
+    let dos_dt = Utc.with_ymd_and_hms(2023, 3, 14, 15, 9, 26).unwrap();
+    let precise_dt = Utc.with_ymd_and_hms(2023, 3, 14, 15, 9, 27).unwrap();
+
+    let mut content = vec![0x01];
+    content.extend_from_slice(&(precise_dt.timestamp() as i32).to_le_bytes());
+    let field = UnknownExtraField { header_id: HeaderId::Other(0x5455), data_size: 5, content };
+
+    let builder = crate::ZipEntryBuilder::new("foo.txt".into(), crate::Compression::Stored)
+        .last_modification_date(crate::ZipDateTime::from_chrono(&dos_dt))
+        .extra_fields(vec![ExtraField::UnknownExtraField(field)]);
+
+    let entry = read_back_entry(builder).await;
+    assert_eq!(entry.last_modified(), Some(precise_dt));
+}
+
+#[tokio::test]
+#[cfg(feature = "chrono")]
+async fn last_modified_ntfs_test() {
+    use crate::spec::header::{ExtraField, HeaderId, UnknownExtraField};
+
+    let precise_dt = Utc.timestamp_opt(1678806567, 123_456_700).unwrap();
+    let ticks = (precise_dt.timestamp() + 11_644_473_600) as u64 * 10_000_000 + 1_234_567;
+
+    let mut content = vec![0; 4];
+    content.extend_from_slice(&0x0001u16.to_le_bytes());
+    content.extend_from_slice(&24u16.to_le_bytes());
+    content.extend_from_slice(&ticks.to_le_bytes());
+    content.extend_from_slice(&[0; 16]);
+    let field = UnknownExtraField { header_id: HeaderId::Other(0x000A), data_size: 32, content };
+
+    let builder = crate::ZipEntryBuilder::new("foo.txt".into(), crate::Compression::Stored)
+        .extra_fields(vec![ExtraField::UnknownExtraField(field)]);
+
+    let entry = read_back_entry(builder).await;
+    assert_eq!(entry.last_modified(), Some(precise_dt));
+}
+
+#[tokio::test]
+#[cfg(feature = "chrono")]
+async fn last_modified_builder_test() {
+    let original_dt = Utc.with_ymd_and_hms(2023, 3, 14, 15, 9, 27).unwrap();
+    let builder = crate::ZipEntryBuilder::new("foo.txt".into(), crate::Compression::Stored).last_modified(original_dt);
+
+    // The extended timestamp holds the precise time, whilst the DOS date & time is within its 2-second resolution.
+    let entry = read_back_entry(builder).await;
+    assert_eq!(entry.last_modified(), Some(original_dt));
+
+    let dos_dt = entry.last_modification_date().as_chrono().unwrap();
+    assert!((original_dt - dos_dt).num_seconds().abs() < 2);
+
+    // A timestamp before 1980 (eg. the Unix epoch) is only precisely represented by the extended timestamp.
+    let epoch_dt = Utc.timestamp_opt(0, 0).unwrap();
+    let builder = crate::ZipEntryBuilder::new("foo.txt".into(), crate::Compression::Stored).last_modified(epoch_dt);
+
+    let entry = read_back_entry(builder).await;
+    assert_eq!(entry.last_modified(), Some(epoch_dt));
+    assert_eq!(entry.extra_fields().len(), 1);
+}
@@ -0,0 +1,38 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::extra_field::raw_extra_fields;
+use crate::spec::header::{ExtraField, HeaderId, UnknownExtraField};
+use crate::{Compression, ZipEntryBuilder};
+
+#[test]
+fn raw_extra_fields_test() {
+    let data = [0x55, 0x54, 0x01, 0x00, 0x01, 0xFE, 0xCA, 0x00, 0x00, 0x01, 0x00, 0x04, 0x00, 0xFF];
+    let fields: Vec<_> = raw_extra_fields(&data).collect();
+
+    // The final field claims four bytes of data but only one remains, so is skipped.
+    assert_eq!(fields, [(0x5455, [0x01].as_slice()), (0xCAFE, &[])]);
+    assert_eq!(raw_extra_fields(&[0x01]).count(), 0);
+}
+
+#[tokio::test]
+async fn extra_field_raw_round_trip_test() {
+    let field = UnknownExtraField { header_id: HeaderId::Other(0xCAFE), data_size: 3, content: vec![1, 2, 3] };
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored)
+        .extra_fields(vec![ExtraField::UnknownExtraField(field)])
+        .build();
+
+    // Entries which haven't been read from a ZIP file have no raw extra field data.
+    assert!(entry.extra_field_raw().is_empty());
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(entry, b"data").await.unwrap();
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    assert_eq!(reader[0].extra_field_raw(), [0xFE, 0xCA, 0x03, 0x00, 1, 2, 3]);
+
+    let rebuilt = ZipEntryBuilder::from(reader[0].entry.clone()).extra_fields(Vec::new()).build();
+    assert!(rebuilt.extra_field_raw().is_empty());
+}
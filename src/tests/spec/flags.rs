@@ -0,0 +1,38 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE};
+use crate::spec::header::GeneralPurposeFlag;
+use crate::{Compression, ZipEntryBuilder};
+
+#[test]
+fn general_purpose_flag_round_trip_test() {
+    for value in [0x0000, 0x0001, 0x0008, 0x0800, 0x0806, 0xFFFF] {
+        assert_eq!(GeneralPurposeFlag::from(value).as_u16(), value);
+    }
+}
+
+#[tokio::test]
+async fn entry_flags_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    // Craft the flags within both headers to also set the (uninterpreted) compression option bits 1 & 2.
+    let cdh_offset = data.windows(4).position(|window| window == CDH_SIGNATURE.to_le_bytes()).unwrap();
+    assert_eq!(data[..4], LFH_SIGNATURE.to_le_bytes());
+    for offset in [6, cdh_offset + 8] {
+        data[offset..offset + 2].copy_from_slice(&0x0806u16.to_le_bytes());
+    }
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert_eq!(entry.flags(), 0x0806);
+    assert!(entry.is_utf8_name());
+    assert!(!entry.is_encrypted());
+    assert!(!entry.has_data_descriptor());
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"foo");
+}
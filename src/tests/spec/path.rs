@@ -0,0 +1,33 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::path::PathBuf;
+
+fn entry(filename: &str) -> ZipEntry {
+    ZipEntryBuilder::new(filename.into(), Compression::Stored).build()
+}
+
+#[test]
+fn entry_components_test() {
+    assert_eq!(entry("foo/./bar.txt").components().collect::<Vec<_>>(), ["foo", "bar.txt"]);
+    assert_eq!(entry("foo\\bar\\baz.txt").components().collect::<Vec<_>>(), ["foo", "bar", "baz.txt"]);
+    assert_eq!(entry("foo//bar/").components().collect::<Vec<_>>(), ["foo", "bar"]);
+    assert_eq!(entry("foo/../bar").components().collect::<Vec<_>>(), ["foo", "..", "bar"]);
+}
+
+#[test]
+fn entry_sanitized_path_test() {
+    assert_eq!(entry("foo/./bar.txt").sanitized_path().unwrap(), PathBuf::from("foo/bar.txt"));
+    assert_eq!(entry("foo\\bar.txt").sanitized_path().unwrap(), PathBuf::from("foo").join("bar.txt"));
+    assert_eq!(entry("foo/").sanitized_path().unwrap(), PathBuf::from("foo"));
+
+    for filename in
+        ["../../etc/passwd", "foo/../../bar", "foo\\..\\bar", "/etc/passwd", "\\etc\\passwd", "C:\\foo", "foo/.."]
+    {
+        assert!(matches!(entry(filename).sanitized_path(), Err(ZipError::UnsafePath(path)) if path == filename));
+    }
+}
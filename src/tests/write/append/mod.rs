@@ -0,0 +1,151 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_util::io::{AsyncWriteExt, Cursor};
+
+const EXISTING: [&str; 3] = ["foo.txt", "bar.txt", "baz.txt"];
+const APPENDED: [&str; 2] = ["qux.txt", "quux.txt"];
+
+/// Writes a ZIP file holding an entry for each of the existing filenames, each containing its own filename.
+async fn existing_zip(force_zip64: bool) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    if force_zip64 {
+        writer = writer.force_zip64();
+    }
+
+    for filename in EXISTING {
+        let entry = ZipEntryBuilder::new(filename.into(), Compression::Stored);
+        writer.write_entry_whole(entry, filename.as_bytes()).await.unwrap();
+    }
+
+    writer.comment("existing".to_string());
+    writer.close().await.unwrap()
+}
+
+/// Appends an entry for each of the appended filenames to the ZIP file, returning its data.
+async fn append(data: Vec<u8>) -> Vec<u8> {
+    let mut writer = ZipFileWriter::append(Cursor::new(data)).await.unwrap();
+    for filename in APPENDED {
+        let entry = ZipEntryBuilder::new(filename.into(), Compression::Stored);
+        writer.write_entry_whole(entry, filename.as_bytes()).await.unwrap();
+    }
+
+    writer.close().await.unwrap().into_inner()
+}
+
+/// Checks that every existing and appended entry can be read back.
+async fn check_entries(reader: &ZipFileReader) {
+    let filenames: Vec<_> = reader.file().entries().iter().map(|entry| entry.filename().as_str().unwrap()).collect();
+    assert_eq!(filenames, [EXISTING.as_slice(), APPENDED.as_slice()].concat());
+
+    for (index, filename) in filenames.iter().enumerate() {
+        assert_eq!(reader.read_entry(index).await.unwrap(), filename.as_bytes());
+    }
+
+    assert_eq!(reader.file().comment().as_str().unwrap(), "existing");
+}
+
+#[tokio::test]
+async fn append_entries_test() {
+    let reader = ZipFileReader::new(append(existing_zip(false).await).await).await.unwrap();
+    assert!(!reader.file().zip64());
+    check_entries(&reader).await;
+}
+
+#[tokio::test]
+async fn append_entries_zip64_test() {
+    let reader = ZipFileReader::new(append(existing_zip(true).await).await).await.unwrap();
+    assert!(reader.file().zip64());
+    check_entries(&reader).await;
+}
+
+#[tokio::test]
+async fn append_entries_prepended_test() {
+    let mut data = vec![0x90; 1024];
+    data.extend(existing_zip(false).await);
+
+    let reader = ZipFileReader::new(append(data).await).await.unwrap();
+    assert_eq!(reader.file().entries()[0].header_offset(), 1024);
+    check_entries(&reader).await;
+}
+
+#[tokio::test]
+async fn append_then_overwrite_existing_test() {
+    let mut writer = ZipFileWriter::append(Cursor::new(existing_zip(false).await)).await.unwrap();
+    writer.overwrite_entry_data(1, b"BAR.txt").await.unwrap();
+    let data = writer.close().await.unwrap().into_inner();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.read_entry(1).await.unwrap(), b"BAR.txt");
+}
+
+#[tokio::test]
+async fn append_keeps_existing_central_directory_test() {
+    let mut data = existing_zip(false).await;
+    let eocdr_offset = data.len() - 22 - "existing".len();
+    let cd_offset = u32::from_le_bytes(data[eocdr_offset + 16..eocdr_offset + 20].try_into().unwrap()) as usize;
+
+    // Use a different specification version within each header, and a comment which isn't valid UTF-8.
+    let headers: Vec<_> =
+        (cd_offset..eocdr_offset).filter(|offset| data[*offset..].starts_with(b"PK\x01\x02")).collect();
+    for offset in headers {
+        data[offset + 4] = 0x14;
+    }
+    let comment_offset = data.len() - "existing".len();
+    data[comment_offset] = 0xFF;
+    let directory = data[cd_offset..eocdr_offset].to_vec();
+
+    let appended = append(data).await;
+    assert!(appended.windows(directory.len()).any(|window| window == directory));
+
+    let reader = ZipFileReader::new(appended).await.unwrap();
+    assert_eq!(reader.file().comment().as_bytes(), b"\xFFxisting");
+    assert!(reader.file().comment().as_str().is_err());
+    assert_eq!(reader.file().entries().len(), EXISTING.len() + APPENDED.len());
+}
+
+#[tokio::test]
+async fn append_then_overwrite_without_descriptor_signature_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let mut entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new("a.txt".into(), Compression::Stored)).await.unwrap();
+    entry_writer.write_all(b"foo").await.unwrap();
+    entry_writer.close().await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    // Other writers may omit the data descriptor's signature, which shifts the central directory back.
+    let descriptor = data.windows(3).position(|window| window == b"foo").unwrap() + 3;
+    assert_eq!(data[descriptor..descriptor + 4], 0x08074b50u32.to_le_bytes());
+    data.drain(descriptor..descriptor + 4);
+    let eocdr_offset = data.len() - 22;
+    let cd_offset = u32::from_le_bytes(data[eocdr_offset + 16..eocdr_offset + 20].try_into().unwrap()) - 4;
+    data[eocdr_offset + 16..eocdr_offset + 20].copy_from_slice(&cd_offset.to_le_bytes());
+
+    let mut writer = ZipFileWriter::append(Cursor::new(data)).await.unwrap();
+    writer.overwrite_entry_data(0, b"qux").await.unwrap();
+    let data = writer.close().await.unwrap().into_inner();
+
+    // The CRC is written at the start of the descriptor, leaving the sizes which follow it intact.
+    assert_eq!(data[descriptor..descriptor + 4], crc32fast::hash(b"qux").to_le_bytes());
+    assert_eq!(data[descriptor + 4..descriptor + 20], [3, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0]);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"qux");
+}
+
+#[tokio::test]
+async fn append_then_overwrite_encrypted_test() {
+    let data = include_bytes!("../../../../tests/test_inputs/zipcrypto.store.zip").to_vec();
+    let mut writer = ZipFileWriter::append(Cursor::new(data)).await.unwrap();
+
+    // The new data would otherwise be written in place of the entry's encrypted data.
+    let length = writer.cd_entries[0].data_length as usize;
+    let result = writer.overwrite_entry_data(0, &vec![0; length]).await;
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+    writer.close().await.unwrap();
+}
@@ -0,0 +1,45 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::attribute::AttributeCompatibility;
+use crate::{Compression, ZipDateTime, ZipEntryBuilder};
+
+#[test]
+fn builder_defaults_test() {
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).build();
+
+    assert_eq!(entry.filename().as_str().unwrap(), "foo.txt");
+    assert_eq!(entry.compression(), Compression::Stored);
+    assert_eq!(entry.comment().as_str().unwrap(), "");
+    assert_eq!(entry.last_modification_date(), &ZipDateTime::default());
+    assert_eq!(entry.attribute_compatibility(), AttributeCompatibility::Unix);
+    assert_eq!(entry.internal_file_attribute(), 0);
+    assert_eq!(entry.external_file_attribute(), 0);
+    assert!(entry.extra_fields().is_empty());
+}
+
+#[tokio::test]
+async fn builder_subset_test() {
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored)
+        .unix_permissions(0o644)
+        .comment("bar".to_string().into())
+        .build();
+
+    // Only the fields which were set differ from a minimal entry.
+    let minimal = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).build();
+    assert_eq!(entry.unix_permissions(), Some(0o644));
+    assert_eq!(entry.comment().as_str().unwrap(), "bar");
+    assert_eq!(entry.last_modification_date(), minimal.last_modification_date());
+    assert_eq!(entry.internal_file_attribute(), minimal.internal_file_attribute());
+    assert_eq!(entry.extra_fields(), minimal.extra_fields());
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(entry, b"baz").await.unwrap();
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    assert_eq!(reader[0].unix_permissions(), Some(0o644));
+    assert_eq!(reader[0].comment().as_str().unwrap(), "bar");
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"baz");
+}
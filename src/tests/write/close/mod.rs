@@ -0,0 +1,31 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn close_writes_central_directory_test() {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"bar").await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"bar");
+}
+
+#[tokio::test]
+async fn dropped_without_close_test() {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"bar").await.unwrap();
+    drop(writer);
+
+    // The entry was written, but without a central directory the data appears to be a truncated ZIP file.
+    assert!(!data.is_empty());
+    let result = ZipFileReader::new(data).await;
+    assert!(matches!(result, Err(ZipError::UnexpectedEof)));
+}
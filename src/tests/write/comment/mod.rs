@@ -0,0 +1,34 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn archive_comment_test() {
+    let comment = "signature: 0123456789abcdef";
+
+    let mut writer = ZipFileWriter::new(Vec::new()).with_comment(comment.to_string());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(reader.comment().as_str().unwrap(), comment);
+    assert!(data.ends_with(comment.as_bytes()));
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment(comment.to_string());
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.comment().as_str().unwrap(), comment);
+}
+
+#[tokio::test]
+async fn archive_comment_too_large_test() {
+    let writer = ZipFileWriter::new(Vec::new()).with_comment("a".repeat(u16::MAX as usize));
+    assert!(writer.close().await.is_ok());
+
+    let writer = ZipFileWriter::new(Vec::new()).with_comment("a".repeat(u16::MAX as usize + 1));
+    assert!(matches!(writer.close().await, Err(ZipError::CommentTooLarge)));
+}
@@ -0,0 +1,51 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+async fn archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_directory("foo".into()).await.unwrap();
+    writer.write_directory("foo/bar/".into()).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("foo/empty.txt".into(), Compression::Stored), &[]).await.unwrap();
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn write_directory_test() {
+    let reader = ZipFileReader::new(archive().await).await.unwrap();
+
+    for (index, filename) in [(0, "foo/"), (1, "foo/bar/")] {
+        let entry = &reader[index];
+        assert_eq!(entry.filename().as_str().unwrap(), filename);
+        assert!(entry.is_dir());
+        assert_eq!(entry.compression(), Compression::Stored);
+        assert_eq!((entry.compressed_size(), entry.uncompressed_size()), (0, 0));
+        assert_eq!(entry.dos_attributes() & 0x10, 0x10);
+        assert_eq!(entry.unix_mode(), Some(0o040755));
+    }
+
+    let entry = &reader[2];
+    assert!(!entry.is_dir());
+    assert_eq!(entry.uncompressed_size(), 0);
+    assert!(reader.read_entry(2).await.unwrap().is_empty());
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn write_directory_extract_test() {
+    let base_dir = std::env::temp_dir().join(format!("async_zip_write_directory_{}", std::process::id()));
+    let path = base_dir.with_extension("zip");
+    tokio::fs::write(&path, archive().await).await.unwrap();
+
+    let reader = crate::tokio::read::fs::ZipFileReader::new(&path).await.unwrap();
+    reader.extract_to(&base_dir).await.unwrap();
+
+    assert!(base_dir.join("foo/bar").is_dir());
+    assert_eq!(std::fs::read(base_dir.join("foo/empty.txt")).unwrap(), b"");
+
+    std::fs::remove_dir_all(&base_dir).unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,60 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+#![cfg(feature = "deflate")]
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, DeflateOption, ZipEntryBuilder};
+
+use futures_util::io::AsyncWriteExt;
+
+/// Generates compressible (but not trivially so) text from a small dictionary of words.
+fn compressible_data() -> Vec<u8> {
+    const WORDS: &[&str] = &["lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit", "sed"];
+
+    let mut states = crate::tests::xorshift();
+    let mut data = Vec::new();
+
+    while data.len() < 256 * 1024 {
+        let state = states.next().unwrap();
+        data.extend_from_slice(WORDS[state as usize % WORDS.len()].as_bytes());
+        data.push(if state.is_multiple_of(11) { b'\n' } else { b' ' });
+    }
+
+    data
+}
+
+/// Writes the data as a single deflate entry at the given level and returns its compressed size.
+async fn compressed_size(data: &[u8], level: i32, stream: bool) -> u64 {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry =
+        ZipEntryBuilder::new("foo.txt".into(), Compression::Deflate).deflate_option(DeflateOption::Other(level));
+
+    if stream {
+        let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+        entry_writer.write_all(data).await.unwrap();
+        entry_writer.close().await.unwrap();
+    } else {
+        writer.write_entry_whole(entry, data).await.unwrap();
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let mut output = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut output).await.unwrap();
+    assert_eq!(output, data);
+
+    reader.file().entries()[0].compressed_size()
+}
+
+#[tokio::test]
+async fn deflate_level_whole_test() {
+    let data = compressible_data();
+    assert!(compressed_size(&data, 9, false).await < compressed_size(&data, 1, false).await);
+}
+
+#[tokio::test]
+async fn deflate_level_stream_test() {
+    let data = compressible_data();
+    assert!(compressed_size(&data, 9, true).await < compressed_size(&data, 1, true).await);
+}
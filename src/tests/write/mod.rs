@@ -6,7 +6,20 @@ use std::io::Error;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+pub(crate) mod append;
+pub(crate) mod builder;
+pub(crate) mod close;
+pub(crate) mod comment;
+pub(crate) mod directory;
+pub(crate) mod level;
 pub(crate) mod offset;
+pub(crate) mod overwrite;
+pub(crate) mod reproducible;
+pub(crate) mod stored;
+#[cfg(feature = "deflate")]
+pub(crate) mod stream;
+pub(crate) mod unix;
+pub(crate) mod utf8;
 mod zip64;
 
 /// /dev/null for AsyncWrite.
@@ -0,0 +1,97 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_util::io::{AsyncWriteExt, Cursor};
+
+#[tokio::test]
+async fn overwrite_entry_data_test() {
+    let mut writer = ZipFileWriter::new(Cursor::new(Vec::new()));
+    for (filename, data) in [("a.txt", b"foo"), ("b.txt", b"bar"), ("c.txt", b"baz")] {
+        writer.write_entry_whole(ZipEntryBuilder::new(filename.into(), Compression::Stored), data).await.unwrap();
+    }
+
+    writer.overwrite_entry_data(1, b"qux").await.unwrap();
+    let data = writer.close().await.unwrap().into_inner();
+
+    // Reading each entry in full verifies its CRC, which is only read from the central directory.
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"foo");
+    assert_eq!(reader.read_entry(1).await.unwrap(), b"qux");
+    assert_eq!(reader.read_entry(2).await.unwrap(), b"baz");
+    assert_eq!(reader[1].crc32(), crc32fast::hash(b"qux"));
+
+    // The local file header's CRC is also updated, which the stream reader relies upon.
+    let mut reader = crate::base::read::stream::ZipFileReader::new(reader.data());
+    let mut entries = Vec::new();
+    while let Some(mut entry) = reader.next_with_entry().await.unwrap() {
+        let mut data = Vec::new();
+        entry.reader_mut().read_to_end_checked(&mut data).await.unwrap();
+        entries.push(data);
+        reader = entry.done().await.unwrap();
+    }
+    assert_eq!(entries, [b"foo", b"qux", b"baz"]);
+}
+
+#[tokio::test]
+async fn overwrite_entry_data_stream_test() {
+    let mut writer = ZipFileWriter::new(Cursor::new(Vec::new()));
+    let mut entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new("a.txt".into(), Compression::Stored)).await.unwrap();
+    entry_writer.write_all(b"foo").await.unwrap();
+    entry_writer.close().await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("b.txt".into(), Compression::Stored), b"bar").await.unwrap();
+
+    writer.overwrite_entry_data(0, b"qux").await.unwrap();
+    let data = writer.close().await.unwrap().into_inner();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"qux");
+    assert_eq!(reader.read_entry(1).await.unwrap(), b"bar");
+
+    // The data descriptor's CRC follows its signature, which follows the entry's data.
+    let descriptor = reader.data().windows(3).position(|window| window == b"qux").unwrap() + 3;
+    assert_eq!(reader.data()[descriptor + 4..descriptor + 8], crc32fast::hash(b"qux").to_le_bytes());
+}
+
+#[tokio::test]
+async fn overwrite_entry_data_offset_writer_test() {
+    // The writer is constructed part way through its inner writer, so its offsets are relative to that position.
+    let mut cursor = Cursor::new(vec![0x90; 16]);
+    cursor.set_position(16);
+
+    let mut writer = ZipFileWriter::new(cursor);
+    writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.overwrite_entry_data(0, b"bar").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("b.txt".into(), Compression::Stored), b"baz").await.unwrap();
+    let data = writer.close().await.unwrap().into_inner();
+
+    assert_eq!(data[..16], [0x90; 16]);
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"bar");
+    assert_eq!(reader.read_entry(1).await.unwrap(), b"baz");
+}
+
+#[tokio::test]
+async fn overwrite_entry_data_errors_test() {
+    let mut writer = ZipFileWriter::new(Cursor::new(Vec::new()));
+    writer.write_entry_whole(ZipEntryBuilder::new("a.txt".into(), Compression::Stored), b"foo").await.unwrap();
+
+    let result = writer.overwrite_entry_data(0, b"foobar").await;
+    assert!(matches!(result, Err(ZipError::EntryDataLengthMismatch(3, 6))));
+    assert!(matches!(writer.overwrite_entry_data(1, b"foo").await, Err(ZipError::EntryIndexOutOfBounds)));
+
+    #[cfg(feature = "deflate")]
+    {
+        let entry = ZipEntryBuilder::new("b.txt".into(), Compression::Deflate);
+        writer.write_entry_whole(entry, b"bar").await.unwrap();
+        assert!(matches!(writer.overwrite_entry_data(1, b"baz").await, Err(ZipError::FeatureNotSupported(_))));
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap().into_inner()).await.unwrap();
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"foo");
+}
@@ -0,0 +1,74 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::date::ZipDateTime;
+use crate::spec::header::{ExtraField, HeaderId, UnknownExtraField};
+use crate::{Compression, ZipEntryBuilder};
+
+fn extra_field(header_id: u16, content: Vec<u8>) -> ExtraField {
+    ExtraField::UnknownExtraField(UnknownExtraField {
+        header_id: HeaderId::Other(header_id),
+        data_size: content.len() as u16,
+        content,
+    })
+}
+
+/// Writes the same set of entries, with the given timestamp-related metadata, in reproducible mode.
+async fn write_archive(date: ZipDateTime, mtime: u32, uid: u32) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new()).reproducible();
+
+    let mut ut = vec![0x01];
+    ut.extend_from_slice(&mtime.to_le_bytes());
+    let mut ux = vec![0x01, 0x04];
+    ux.extend_from_slice(&uid.to_le_bytes());
+    ux.extend_from_slice(&[0x04, 0xE8, 0x03, 0x00, 0x00]);
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored)
+        .last_modification_date(date)
+        .unix_permissions(0o644)
+        .extra_fields(vec![extra_field(0x5455, ut), extra_field(0x7875, ux), extra_field(0xCAFE, vec![1, 2, 3])]);
+    writer.write_entry_whole(entry, b"Hello, world!").await.unwrap();
+
+    let entry = ZipEntryBuilder::new("bar.txt".into(), Compression::Stored).last_modification_date(date);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    futures_util::io::AsyncWriteExt::write_all(&mut entry_writer, b"Streamed data.").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    writer.write_directory("baz".into()).await.unwrap();
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn reproducible_output_test() {
+    let first = write_archive(ZipDateTime { date: 0x5721, time: 0x6000 }, 1_700_000_000, 1000).await;
+    let second = write_archive(ZipDateTime { date: 0x5A4F, time: 0x8C2A }, 1_800_000_000, 501).await;
+    assert_eq!(first, second);
+
+    let reader = ZipFileReader::new(first).await.unwrap();
+    let names: Vec<_> = reader.file().entries().iter().map(|e| e.filename().as_str().unwrap()).collect();
+    assert_eq!(names, ["foo.txt", "bar.txt", "baz/"]);
+
+    for entry in reader.file().entries() {
+        assert_eq!(entry.last_modification_date(), &ZipDateTime::DOS_EPOCH);
+        assert_eq!((entry.last_modification_date().year(), entry.last_modification_date().month()), (1980, 1));
+    }
+
+    // Only the host-agnostic extra field is preserved.
+    assert_eq!(reader[0].extra_fields().len(), 1);
+    assert!(matches!(&reader[0].extra_fields()[0], ExtraField::UnknownExtraField(field)
+        if u16::from(field.header_id) == 0xCAFE));
+}
+
+#[tokio::test]
+async fn non_reproducible_output_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let date = ZipDateTime { date: 0x5721, time: 0x6000 };
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).last_modification_date(date);
+    writer.write_entry_whole(entry, b"Hello, world!").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader[0].last_modification_date(), &date);
+}
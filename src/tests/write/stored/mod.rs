@@ -0,0 +1,47 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+#![cfg(feature = "deflate")]
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+/// Generates incompressible data via a xorshift generator.
+fn random_data() -> Vec<u8> {
+    crate::tests::xorshift().take(16 * 1024).flat_map(u32::to_le_bytes).collect()
+}
+
+/// Writes the data as a single deflate entry and reads it back, returning the reader.
+async fn write_and_read(data: &[u8], auto_stored: bool) -> ZipFileReader {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.bin".into(), Compression::Deflate).auto_stored(auto_stored);
+    writer.write_entry_whole(entry, data).await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.read_entry(0).await.unwrap(), data);
+    reader
+}
+
+#[tokio::test]
+async fn auto_stored_incompressible_test() {
+    let data = random_data();
+
+    // Deflating random data only adds overhead.
+    let reader = write_and_read(&data, false).await;
+    assert_eq!(reader.file().entries()[0].compression(), Compression::Deflate);
+    assert!(reader.file().entries()[0].compressed_size() > data.len() as u64);
+
+    let reader = write_and_read(&data, true).await;
+    assert_eq!(reader.file().entries()[0].compression(), Compression::Stored);
+    assert_eq!(reader.file().entries()[0].compressed_size(), data.len() as u64);
+}
+
+#[tokio::test]
+async fn auto_stored_compressible_test() {
+    let data = b"foo bar ".repeat(8 * 1024);
+
+    let reader = write_and_read(&data, true).await;
+    assert_eq!(reader.file().entries()[0].compression(), Compression::Deflate);
+    assert!(reader.file().entries()[0].compressed_size() < data.len() as u64);
+}
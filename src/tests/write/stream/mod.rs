@@ -0,0 +1,50 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::stream::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_util::io::{AsyncReadExt, Cursor};
+
+/// The size of the simulated entry, which is generated on the fly rather than being held in memory.
+const ENTRY_SIZE: u64 = 64 * 1024 * 1024;
+
+#[tokio::test]
+async fn write_entry_stream_from_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let source = futures_util::io::repeat(b'a').take(ENTRY_SIZE);
+    let entry = ZipEntryBuilder::new("large.bin".into(), Compression::Deflate);
+    assert_eq!(writer.write_entry_stream_from(entry, source).await.unwrap(), ENTRY_SIZE);
+
+    let entry = ZipEntryBuilder::new("small.txt".into(), Compression::Deflate);
+    writer.write_entry_stream_from(entry, &b"Some trailing data."[..]).await.unwrap();
+
+    let data = writer.close().await.unwrap();
+    assert!((data.len() as u64) < ENTRY_SIZE / 100);
+
+    // The streaming reader locates the end of the entry via its compressed stream, then consumes the data descriptor.
+    let mut zip = ZipFileReader::new(Cursor::new(data));
+    let mut entry = zip.next_with_entry().await.unwrap().unwrap();
+    assert_eq!(entry.reader().entry().filename().as_str().unwrap(), "large.bin");
+
+    let mut buffer = vec![0; 64 * 1024];
+    let mut read = 0;
+    loop {
+        let bytes = entry.reader_mut().read(&mut buffer).await.unwrap();
+        if bytes == 0 {
+            break;
+        }
+        assert!(buffer[..bytes].iter().all(|&byte| byte == b'a'));
+        read += bytes as u64;
+    }
+    assert_eq!(read, ENTRY_SIZE);
+    zip = entry.done().await.unwrap();
+
+    let mut entry = zip.next_with_entry().await.unwrap().unwrap();
+    let mut data = String::new();
+    entry.reader_mut().read_to_string(&mut data).await.unwrap();
+    assert_eq!(data, "Some trailing data.");
+    assert!(entry.done().await.unwrap().next_with_entry().await.unwrap().is_none());
+}
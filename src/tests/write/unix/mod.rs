@@ -0,0 +1,37 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::attribute::AttributeCompatibility;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn unix_mode_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("run.sh".into(), Compression::Stored)
+        .attribute_compatibility(AttributeCompatibility::Dos)
+        .unix_mode(0o755);
+    writer.write_entry_whole(entry, b"#!/bin/sh").await.unwrap();
+
+    let entry = ZipEntryBuilder::new("bin/".into(), Compression::Stored).unix_mode(0o700);
+    writer.write_entry_whole(entry, &[]).await.unwrap();
+
+    let entry = ZipEntryBuilder::new("link".into(), Compression::Stored).unix_mode(0o120777);
+    writer.write_entry_whole(entry, b"run.sh").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    // The Unix host is recorded within "version made by", so the mode is read back from the external attribute.
+    assert_eq!(reader[0].attribute_compatibility(), AttributeCompatibility::Unix);
+    assert_eq!(reader[0].unix_mode(), Some(0o100755));
+    assert_eq!(reader[0].unix_permissions(), Some(0o100755));
+    assert!(!reader[0].is_dir());
+
+    assert_eq!(reader[1].unix_mode(), Some(0o040700));
+    assert!(reader[1].is_dir());
+
+    assert_eq!(reader[2].unix_mode(), Some(0o120777));
+    assert!(reader[2].is_symlink());
+}
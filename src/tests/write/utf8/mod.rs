@@ -0,0 +1,63 @@
+// Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, StringEncoding, ZipEntryBuilder, ZipString};
+
+use futures_util::io::AsyncWriteExt;
+
+const EMOJI_FILENAME: &str = "snapshots/🦀 crab 🎉.txt";
+
+#[tokio::test]
+async fn utf8_name_whole_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(EMOJI_FILENAME.into(), Compression::Stored);
+    writer.write_entry_whole(builder, b"crab").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert!(entry.is_utf8_name());
+    assert_eq!(entry.filename().as_str().unwrap(), EMOJI_FILENAME);
+    assert_eq!(entry.filename_bytes(), EMOJI_FILENAME.as_bytes());
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"crab");
+}
+
+#[tokio::test]
+async fn utf8_name_stream_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(EMOJI_FILENAME.into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(builder).await.unwrap();
+    entry_writer.write_all(b"crab").await.unwrap();
+    entry_writer.close().await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    assert!(reader.file().entries()[0].is_utf8_name());
+    assert_eq!(reader.file().entries()[0].filename().as_str().unwrap(), EMOJI_FILENAME);
+
+    // The flag must also be set within the local file header for readers which don't consult the central directory.
+    assert_eq!(u16::from_le_bytes([data[6], data[7]]) & 0x0800, 0x0800);
+}
+
+#[tokio::test]
+async fn utf8_name_override_test() {
+    let raw_filename = ZipString::new(EMOJI_FILENAME.as_bytes().to_vec(), StringEncoding::Raw);
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new(EMOJI_FILENAME.into(), Compression::Stored).utf8_name(false);
+    writer.write_entry_whole(builder, b"unflagged").await.unwrap();
+    let builder = ZipEntryBuilder::new(raw_filename, Compression::Stored).utf8_name(true);
+    writer.write_entry_whole(builder, b"flagged").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entries = reader.file().entries();
+
+    assert!(!entries[0].is_utf8_name());
+    assert_eq!(entries[0].filename_bytes(), EMOJI_FILENAME.as_bytes());
+    assert!(entries[1].is_utf8_name());
+    assert_eq!(entries[1].filename().as_str().unwrap(), EMOJI_FILENAME);
+}
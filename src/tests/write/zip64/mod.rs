@@ -48,6 +48,39 @@ async fn test_write_zip64_file() {
     assert_eq!(buffer.as_slice(), &[0, 0, 0, 0]);
 }
 
+/// Test that a stream-written entry records its compressed and uncompressed sizes in the right fields, both with and
+/// without zip64 forced off.
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn test_write_stream_entry_sizes() {
+    init_logger();
+
+    let data = vec![b'a'; 64 * 1024];
+
+    for no_zip64 in [false, true] {
+        let mut buffer = Vec::new();
+        let mut writer = ZipFileWriter::new(&mut buffer);
+        if no_zip64 {
+            writer = writer.force_no_zip64();
+        }
+        let entry = ZipEntryBuilder::new("file".to_string().into(), Compression::Deflate);
+        let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+        entry_writer.write_all(&data).await.unwrap();
+        entry_writer.close().await.unwrap();
+        writer.close().await.unwrap();
+
+        let cursor = std::io::Cursor::new(buffer);
+        let mut zip = zip::read::ZipArchive::new(cursor).unwrap();
+        let mut file = zip.by_name("file").unwrap();
+        assert_eq!(file.size(), data.len() as u64);
+        assert!(file.compressed_size() < file.size());
+
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+        assert_eq!(buffer, data);
+    }
+}
+
 /// Test writing a large zip64 file. This test will use upwards of 4GB of memory.
 #[tokio::test]
 async fn test_write_large_zip64_file() {
@@ -241,3 +274,135 @@ async fn test_force_no_zip64_errors_with_too_large_file_stream() {
 
     assert!(matches!(result, Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile))));
 }
+
+/// An in-memory sparse buffer, where large writes are assumed to only contain zeros so that their data isn't stored.
+#[derive(Default)]
+struct SparseBuffer {
+    chunks: Vec<(u64, Vec<u8>)>,
+    length: u64,
+    position: u64,
+}
+
+impl SparseBuffer {
+    const STUB_THRESHOLD: usize = 64 * 1024;
+}
+
+impl futures_util::io::AsyncWrite for SparseBuffer {
+    fn poll_write(
+        mut self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        if buf.len() < Self::STUB_THRESHOLD {
+            let offset = self.length;
+            self.chunks.push((offset, buf.to_vec()));
+        }
+
+        self.length += buf.len() as u64;
+        std::task::Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+impl futures_util::io::AsyncRead for SparseBuffer {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+        buf: &mut [u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        let mut read = 0;
+
+        // Fill the buffer in full (where possible), as readers may expect a single read to do so.
+        while read < buf.len() && self.position < self.length {
+            let position = self.position;
+            let index = self.chunks.partition_point(|(offset, _)| *offset <= position);
+            let remaining = &mut buf[read..];
+
+            let length = match index.checked_sub(1).map(|index| &self.chunks[index]) {
+                Some((offset, data)) if position < offset + data.len() as u64 => {
+                    let data = &data[(position - offset) as usize..];
+                    let length = data.len().min(remaining.len());
+                    remaining[..length].copy_from_slice(&data[..length]);
+                    length
+                }
+                _ => {
+                    let next = self.chunks.get(index).map(|(offset, _)| *offset).unwrap_or(self.length);
+                    let length = ((next - position) as usize).min(remaining.len());
+                    remaining[..length].fill(0);
+                    length
+                }
+            };
+
+            self.position += length as u64;
+            read += length;
+        }
+
+        std::task::Poll::Ready(Ok(read))
+    }
+}
+
+impl futures_util::io::AsyncSeek for SparseBuffer {
+    fn poll_seek(
+        mut self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+        pos: std::io::SeekFrom,
+    ) -> std::task::Poll<std::io::Result<u64>> {
+        self.position = match pos {
+            std::io::SeekFrom::Start(offset) => offset,
+            std::io::SeekFrom::Current(offset) => self.position.saturating_add_signed(offset),
+            std::io::SeekFrom::End(offset) => self.length.saturating_add_signed(offset),
+        };
+        std::task::Poll::Ready(Ok(self.position))
+    }
+}
+
+/// Test writing entries whose local file headers are located beyond ~4 GiB, and reading them with async-zip.
+#[tokio::test]
+async fn test_write_zip64_large_offsets_self_read() {
+    init_logger();
+
+    let mut writer = ZipFileWriter::new(SparseBuffer::default());
+
+    let entry = ZipEntryBuilder::new("large".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    for _ in 0..NUM_BATCHES {
+        entry_writer.write_all(&[0; BATCH_SIZE]).await.unwrap();
+    }
+    entry_writer.close().await.unwrap();
+
+    let entry = ZipEntryBuilder::new("whole.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"whole").await.unwrap();
+
+    let entry = ZipEntryBuilder::new("stream.txt".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"stream").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let mut buffer = writer.close().await.unwrap();
+    buffer.position = 0;
+
+    let mut reader = crate::base::read::seek::ZipFileReader::new(buffer).await.unwrap();
+    let entries = reader.file().entries();
+
+    assert!(reader.file().zip64);
+    assert_eq!(entries[0].uncompressed_size(), BATCHED_FILE_SIZE as u64);
+    assert_eq!(entries[0].compressed_size(), BATCHED_FILE_SIZE as u64);
+    assert!(entries[1].header_offset() > NON_ZIP64_MAX_SIZE as u64);
+    assert!(entries[2].header_offset() > entries[1].header_offset());
+
+    assert_eq!(reader.read_entry(1).await.unwrap(), b"whole");
+    assert_eq!(reader.read_entry(2).await.unwrap(), b"stream");
+}
@@ -5,7 +5,17 @@
 //!
 //! Concurrency is achieved as a result of:
 //! - Wrapping the provided path within an [`Arc`] to allow shared ownership.
-//! - Constructing a new [`File`] from the path when reading.
+//! - Constructing a new [`File`] from the path when reading (or reusing an idle one from a pool, if configured via
+//!   [`ZipFileReader::with_pool_size()`]).
+//!
+//! ### Concurrent Access
+//! Files are opened in a shared read mode, so the ZIP file can be opened by any number of readers at once (and by
+//! other processes, incl. ones which hold it open for writing). On Windows, this avoids the sharing violations which
+//! would otherwise occur.
+//!
+//! However, the central directory is only read when a reader is constructed (or via [`ZipFileReader::refresh()`]).
+//! If the file is modified afterwards, entry readers may read inconsistent data; this usually surfaces as a header
+//! signature or CRC32 error, but isn't guaranteed to, so the file shouldn't be modified whilst it's being read.
 //!
 //! ### Usage
 //! Unlike the [`seek`] module, we no longer hold a mutable reference to any inner reader which in turn, allows the
@@ -66,24 +76,195 @@
 //!     Ok(data)
 //! }
 //! ```
+//!
+//! ### Sequential Example
+//! ```no_run
+//! # use async_zip::tokio::read::fs::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use futures_util::io::AsyncReadExt;
+//! # use futures_util::StreamExt;
+//! #
+//! async fn run() -> Result<()> {
+//!     let reader = ZipFileReader::new("./foo.zip").await?;
+//!     let mut entries = Box::pin(reader.into_stream());
+//!
+//!     while let Some(result) = entries.next().await {
+//!         let (entry, mut entry_reader) = result?;
+//!         let mut data = Vec::new();
+//!         entry_reader.read_to_end(&mut data).await?;
+//!
+//!         // Use data within current scope.
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
 
 #[cfg(doc)]
 use crate::base::read::seek;
 
-use crate::base::read::io::entry::{WithEntry, WithoutEntry, ZipEntryReader};
+use crate::base::read::io::entry::{ExpansionLimit, WithEntry, WithoutEntry, ZipEntryReader};
+use crate::base::read::{ReadOptions, DEFAULT_BUFFER_CAPACITY};
+use crate::entry::{StoredZipEntry, ZipEntry};
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 
+use std::collections::VecDeque;
+use std::io::SeekFrom;
+use std::ops::Index;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
 
-use futures_util::io::BufReader;
+use futures_util::io::{AsyncReadExt, AsyncWriteExt, BufReader, Take};
+use futures_util::stream::{Stream, StreamExt};
 use tokio::fs::File;
-use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_util::compat::{Compat, FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
 
+/// A buffered [`tokio`] reader over the decompressed data of an entry (see [`ZipFileReader::entry_buf_reader()`]).
+pub type EntryBufReader<'a> = tokio::io::BufReader<Compat<ZipEntryReader<'a, Compat<PooledFile>, WithEntry<'a>>>>;
+
+#[derive(Clone)]
 struct Inner {
     path: PathBuf,
     file: ZipFile,
+    permits: Option<Arc<Semaphore>>,
+    buffer_capacity: usize,
+    pool: Arc<FilePool>,
+    cache: Option<Arc<EntryCache>>,
+    expansion_limit: Option<ExpansionLimit>,
+    options: ReadOptions,
+}
+
+/// A pool of open file handles which are reused by entry readers, rather than each opening its own.
+struct FilePool {
+    handles: Mutex<Vec<File>>,
+    size: usize,
+    /// The total number of file handles opened via this pool.
+    opened: AtomicUsize,
+}
+
+impl FilePool {
+    fn new(size: usize) -> Arc<Self> {
+        Arc::new(FilePool { handles: Mutex::new(Vec::new()), size, opened: AtomicUsize::new(0) })
+    }
+
+    /// Checks out an idle file handle from the pool, opening a new one if none are idle.
+    async fn checkout(self: &Arc<Self>, path: &Path) -> Result<PooledFile> {
+        let idle = self.handles.lock().unwrap().pop();
+        let file = match idle {
+            Some(file) => file,
+            None => {
+                let file = open(path).await?;
+                self.opened.fetch_add(1, Ordering::Relaxed);
+                file
+            }
+        };
+
+        Ok(PooledFile { file: Some(file), pool: self.clone() })
+    }
+}
+
+/// A cache of the decompressed data of small entries, which evicts the least recently read entries to stay within its
+/// byte budget (see [`ZipFileReader::with_cache()`]).
+struct EntryCache {
+    state: Mutex<CacheState>,
+    budget: usize,
+    threshold: usize,
+}
+
+#[derive(Default)]
+struct CacheState {
+    /// The index and data of each cached entry, ordered from least to most recently read.
+    entries: VecDeque<(usize, Vec<u8>)>,
+    /// The total length of the cached data in bytes.
+    size: usize,
+}
+
+impl EntryCache {
+    fn new(budget: usize, threshold: usize) -> Arc<Self> {
+        Arc::new(EntryCache { state: Mutex::new(CacheState::default()), budget, threshold })
+    }
+
+    /// Returns a copy of an entry's cached data (if present), marking it as the most recently read.
+    fn get(&self, index: usize) -> Option<Vec<u8>> {
+        let mut state = self.state.lock().unwrap();
+        let position = state.entries.iter().position(|(cached, _)| *cached == index)?;
+        let entry = state.entries.remove(position)?;
+        let data = entry.1.clone();
+
+        state.entries.push_back(entry);
+        Some(data)
+    }
+
+    /// Caches an entry's data if it's within the threshold, evicting the least recently read entries as needed.
+    fn insert(&self, index: usize, data: &[u8]) {
+        if data.len() > self.threshold || data.len() > self.budget {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+
+        // A concurrent read of the same entry may have already cached it.
+        if state.entries.iter().any(|(cached, _)| *cached == index) {
+            return;
+        }
+
+        while state.size + data.len() > self.budget {
+            let Some((_, evicted)) = state.entries.pop_front() else { break };
+            state.size -= evicted.len();
+        }
+
+        state.size += data.len();
+        state.entries.push_back((index, data.to_vec()));
+    }
+}
+
+/// A file handle checked out from a [`ZipFileReader`]'s pool (see [`ZipFileReader::with_pool_size()`]).
+///
+/// The handle is returned to the pool when dropped, if the pool isn't already full.
+pub struct PooledFile {
+    file: Option<File>,
+    pool: Arc<FilePool>,
+}
+
+impl PooledFile {
+    fn file(self: Pin<&mut Self>) -> Pin<&mut File> {
+        // The handle is only taken when dropped.
+        Pin::new(self.get_mut().file.as_mut().unwrap())
+    }
+}
+
+impl Drop for PooledFile {
+    fn drop(&mut self) {
+        let mut handles = self.pool.handles.lock().unwrap();
+        if handles.len() < self.pool.size {
+            handles.extend(self.file.take());
+        }
+    }
+}
+
+impl tokio::io::AsyncRead for PooledFile {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        c: &mut Context<'_>,
+        b: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        self.file().poll_read(c, b)
+    }
+}
+
+impl tokio::io::AsyncSeek for PooledFile {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+        self.file().start_seek(position)
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        self.file().poll_complete(c)
+    }
 }
 
 /// A concurrent ZIP reader which acts over a file system path.
@@ -94,12 +275,39 @@ pub struct ZipFileReader {
 
 impl ZipFileReader {
     /// Constructs a new ZIP reader from a file system path.
+    ///
+    /// Only the central directory is read, in a single pass. Each entry's local file header is read (and validated)
+    /// when its data is first accessed, so opening a ZIP file holding many entries doesn't require IO per entry.
     pub async fn new<P>(path: P) -> Result<ZipFileReader>
     where
         P: AsRef<Path>,
     {
-        let file = crate::base::read::file(File::open(&path).await?.compat()).await?;
-        Ok(ZipFileReader::from_raw_parts(path, file))
+        ZipFileReader::new_with_options(path, ReadOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a file system path, locating and parsing its central directory in accordance
+    /// with the provided options.
+    ///
+    /// The options are also used by any later [`ZipFileReader::refresh()`].
+    pub async fn new_with_options<P>(path: P, options: ReadOptions) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        let file = crate::base::read::file(open(path.as_ref()).await?.compat(), options).await?;
+        let mut reader = ZipFileReader::from_raw_parts(path, file);
+        Arc::make_mut(&mut reader.inner).options = options;
+        Ok(reader)
+    }
+
+    /// Constructs a new ZIP reader from a file system path, tolerating non-fatal inconsistencies within its central
+    /// directory.
+    ///
+    /// See [`seek::ZipFileReader::new_lenient()`] for more information.
+    pub async fn new_lenient<P>(path: P) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        ZipFileReader::new_with_options(path, ReadOptions { lenient: true, ..Default::default() }).await
     }
 
     /// Constructs a ZIP reader from a file system path and ZIP file information derived from that path.
@@ -109,7 +317,75 @@ impl ZipFileReader {
     where
         P: AsRef<Path>,
     {
-        ZipFileReader { inner: Arc::new(Inner { path: path.as_ref().to_owned(), file }) }
+        ZipFileReader {
+            inner: Arc::new(Inner {
+                path: path.as_ref().to_owned(),
+                file,
+                permits: None,
+                buffer_capacity: DEFAULT_BUFFER_CAPACITY,
+                pool: FilePool::new(0),
+                cache: None,
+                expansion_limit: None,
+                options: ReadOptions::default(),
+            }),
+        }
+    }
+
+    /// Limits the number of entry readers that may be open at any one time (unlimited by default).
+    ///
+    /// Each entry reader holds its own file handle, so opening many concurrently may exhaust the OS file descriptor
+    /// limit. Once `max_open` entry readers are open, requesting another waits until an existing one is dropped. Clones
+    /// of this reader share the limit. A `max_open` of zero removes the limit, rather than preventing any entry reader
+    /// from being opened.
+    pub fn with_max_open(mut self, max_open: usize) -> Self {
+        Arc::make_mut(&mut self.inner).permits = match max_open {
+            0 => None,
+            max_open => Some(Arc::new(Semaphore::new(max_open))),
+        };
+        self
+    }
+
+    /// Sets the capacity of the buffer which entry readers use when reading from the file (defaults to 8 KiB).
+    ///
+    /// Small sequential reads from an entry reader are batched into reads of up to this size from the file, reducing
+    /// the number of underlying syscalls.
+    pub fn with_buffer_capacity(mut self, capacity: usize) -> Self {
+        Arc::make_mut(&mut self.inner).buffer_capacity = capacity;
+        self
+    }
+
+    /// Sets the number of idle file handles which are kept open for reuse by entry readers (defaults to zero).
+    ///
+    /// By default, each entry reader opens its own file handle which is closed when the reader is dropped. With a pool,
+    /// a dropped reader's handle is instead kept open (up to the provided number) and reused by the next entry reader,
+    /// which seeks it to the entry's data. This reduces the overhead of opening files when serving many reads from the
+    /// same archive, at the cost of holding the file open for as long as this reader (or a clone of it) exists.
+    pub fn with_pool_size(mut self, size: usize) -> Self {
+        Arc::make_mut(&mut self.inner).pool = FilePool::new(size);
+        self
+    }
+
+    /// Caches the decompressed data of entries read via [`ZipFileReader::read_entry()`], up to `budget` bytes in total.
+    ///
+    /// Only entries whose data is at most `threshold` bytes are cached, with larger entries bypassing the cache. Once
+    /// the budget would be exceeded, the least recently read entries are evicted. Reading a cached entry again neither
+    /// opens the file nor decompresses the entry's data, which suits servers repeatedly serving the same small entries.
+    ///
+    /// Clones of this reader share the cache, which is cleared by [`ZipFileReader::refresh()`]. No cache is used by
+    /// default.
+    pub fn with_cache(mut self, budget: usize, threshold: usize) -> Self {
+        Arc::make_mut(&mut self.inner).cache = Some(EntryCache::new(budget, threshold));
+        self
+    }
+
+    /// Limits the ratio of decompressed bytes to compressed bytes for entry readers opened by this reader, guarding
+    /// against ZIP bombs (see [`ZipEntryReader::with_expansion_limit()`]).
+    ///
+    /// The limit is only enforced once an entry reader has produced more than `threshold` bytes. Reads which exceed it
+    /// fail with [`ZipError::SuspiciousExpansion`]. No limit is enforced by default.
+    pub fn with_expansion_limit(mut self, ratio: u64, threshold: u64) -> Self {
+        Arc::make_mut(&mut self.inner).expansion_limit = Some(ExpansionLimit { ratio, threshold });
+        self
     }
 
     /// Returns this ZIP file's information.
@@ -117,6 +393,55 @@ impl ZipFileReader {
         &self.inner.file
     }
 
+    /// Re-reads the central directory from the file system path, replacing this ZIP file's information.
+    ///
+    /// This allows entries appended to a growing file to be picked up without reconstructing the reader. If the
+    /// central directory can't be read (eg. the file was truncated or is mid-write), an error is returned and the
+    /// existing information is left intact. Clones of this reader aren't affected.
+    pub async fn refresh(&mut self) -> Result<()> {
+        let file = crate::base::read::file(open(&self.inner.path).await?.compat(), self.inner.options).await?;
+        let inner = Arc::make_mut(&mut self.inner);
+
+        // Any pooled handles (or cached data) may refer to a file which has since been replaced at the path.
+        inner.pool = FilePool::new(inner.pool.size);
+        inner.cache = inner.cache.as_ref().map(|cache| EntryCache::new(cache.budget, cache.threshold));
+        inner.file = file;
+        Ok(())
+    }
+
+    crate::file::zip_file_accessors!();
+
+    /// Returns the offset in bytes to where the data of an entry starts, if the provided index is valid.
+    ///
+    /// The offset is determined from the entry's local file header (rather than the central directory), which is read
+    /// on the first call and cached thereafter.
+    pub async fn data_offset(&self, index: usize) -> Result<u64> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let _permit = self.acquire_permit().await;
+        stored_entry.data_offset(&mut self.open_file().await?.compat()).await
+    }
+
+    /// Returns the offsets in bytes to where the compressed data of an entry starts and ends (exclusive), if the
+    /// provided index is valid.
+    ///
+    /// The start is determined as with [`ZipFileReader::data_offset()`], and the end from the compressed size recorded
+    /// within the central directory.
+    pub async fn compressed_data_range(&self, index: usize) -> Result<(u64, u64)> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let _permit = self.acquire_permit().await;
+        stored_entry.compressed_data_range(&mut self.open_file().await?.compat()).await
+    }
+
+    /// Returns the offsets in bytes to where the data descriptor following an entry's data starts and ends (exclusive),
+    /// if the provided index is valid and the entry was written with one.
+    ///
+    /// The end of the descriptor is where the next local file header (or the central directory) is expected to start.
+    pub async fn data_descriptor_range(&self, index: usize) -> Result<Option<(u64, u64)>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let _permit = self.acquire_permit().await;
+        stored_entry.data_descriptor_range(&mut self.open_file().await?.compat()).await
+    }
+
     /// Returns the file system path provided to the reader during construction.
     pub fn path(&self) -> &Path {
         &self.inner.path
@@ -126,32 +451,509 @@ impl ZipFileReader {
     pub async fn reader_without_entry(
         &self,
         index: usize,
-    ) -> Result<ZipEntryReader<'static, Compat<File>, WithoutEntry>> {
+    ) -> Result<ZipEntryReader<'static, Compat<PooledFile>, WithoutEntry>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let mut fs_file = BufReader::new(File::open(&self.inner.path).await?.compat());
+        self.entry_reader(stored_entry).await
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn reader_with_entry(
+        &self,
+        index: usize,
+    ) -> Result<ZipEntryReader<'_, Compat<PooledFile>, WithEntry<'_>>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        Ok(self.entry_reader(stored_entry).await?.into_with_entry(stored_entry))
+    }
+
+    /// Opens a new entry reader over a pooled file handle which has been sought to the entry's data, once a permit to do
+    /// so has been acquired.
+    async fn entry_reader(
+        &self,
+        stored_entry: &StoredZipEntry,
+    ) -> Result<ZipEntryReader<'static, Compat<PooledFile>, WithoutEntry>> {
+        let permit = self.acquire_permit().await;
+        let mut fs_file = BufReader::with_capacity(self.inner.buffer_capacity, self.open_file().await?.compat());
 
         stored_entry.seek_to_data_offset(&mut fs_file).await?;
 
-        Ok(ZipEntryReader::new_with_owned(
+        let reader = ZipEntryReader::new_with_owned(
             fs_file,
             stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+            stored_entry.data_size(),
+            stored_entry.is_empty(),
+        )
+        .with_uncompressed_size(stored_entry.entry.uncompressed_size())
+        .with_expansion_limit_opt(self.inner.expansion_limit);
+
+        Ok(reader.with_permit(permit))
     }
 
-    /// Returns a new entry reader if the provided index is valid.
-    pub async fn reader_with_entry(&self, index: usize) -> Result<ZipEntryReader<'_, Compat<File>, WithEntry<'_>>> {
+    /// Returns a new entry reader for the provided entry, if it belongs to this ZIP file.
+    ///
+    /// This allows an entry found via [`ZipFile::entry()`] to be read without threading its index back through. See
+    /// [`ZipFile::index_of()`] for how the entry is matched; [`ZipError::ForeignEntry`] is returned if it doesn't
+    /// belong to this ZIP file.
+    pub async fn reader_for(
+        &self,
+        entry: &StoredZipEntry,
+    ) -> Result<ZipEntryReader<'_, Compat<PooledFile>, WithEntry<'_>>> {
+        let index = self.file().index_of(entry).ok_or(ZipError::ForeignEntry)?;
+        self.reader_with_entry(index).await
+    }
+
+    /// Returns a buffered [`tokio`] reader over the decompressed data of an entry, if the provided index is valid.
+    ///
+    /// This allows the entry's data to be consumed via [`tokio::io::AsyncBufReadExt`] (eg. line by line). The buffer
+    /// shares the capacity set via [`ZipFileReader::with_buffer_capacity()`].
+    pub async fn entry_buf_reader(&self, index: usize) -> Result<EntryBufReader<'_>> {
+        let reader = self.reader_with_entry(index).await?;
+        Ok(tokio::io::BufReader::with_capacity(self.inner.buffer_capacity, reader.compat()))
+    }
+
+    /// Returns a new entry reader which decrypts the entry's data with the provided password, if the provided index is
+    /// valid.
+    ///
+    /// If the entry isn't encrypted, the password is ignored. [`ZipError::WrongPassword`] is returned if the password
+    /// fails the check against the entry's encryption header.
+    pub async fn reader_with_password(
+        &self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'_, Compat<PooledFile>, WithEntry<'_>>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let mut fs_file = BufReader::new(File::open(&self.inner.path).await?.compat());
+        let permit = self.acquire_permit().await;
+        let mut fs_file = BufReader::with_capacity(self.inner.buffer_capacity, self.open_file().await?.compat());
 
         stored_entry.seek_to_data_offset(&mut fs_file).await?;
 
-        let reader = ZipEntryReader::new_with_owned(
-            fs_file,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        );
+        let reader = ZipEntryReader::new_with_owned_password(fs_file, stored_entry, password)
+            .await?
+            .with_expansion_limit_opt(self.inner.expansion_limit);
+        Ok(reader.with_permit(permit).into_with_entry(stored_entry))
+    }
+
+    /// Returns a reader over the raw data of an entry (ie. still compressed and, if applicable, encrypted), if the
+    /// provided index is valid.
+    ///
+    /// The reader yields exactly the entry's compressed size in bytes. Alongside the entry's compression method, CRC32
+    /// value, and sizes, this allows an entry to be copied into another ZIP file without decompressing it.
+    ///
+    /// Raw readers aren't counted towards the limit provided to [`ZipFileReader::with_max_open()`].
+    pub async fn raw_reader(&self, index: usize) -> Result<Take<BufReader<Compat<PooledFile>>>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut reader = BufReader::with_capacity(self.inner.buffer_capacity, self.open_file().await?.compat());
+
+        stored_entry.seek_to_data_offset(&mut reader).await?;
+
+        Ok(reader.take(stored_entry.compressed_size()))
+    }
+
+    /// Reads the entire decompressed contents of an entry, if the provided index is valid.
+    ///
+    /// The entry's CRC32 value is verified once all of its data has been read. If a cache is used (see
+    /// [`ZipFileReader::with_cache()`]), the entry's data is returned from it where possible.
+    pub async fn read_entry(&self, index: usize) -> Result<Vec<u8>> {
+        if let Some(data) = self.inner.cache.as_ref().and_then(|cache| cache.get(index)) {
+            return Ok(data);
+        }
+
+        let mut data = Vec::new();
+        self.reader_with_entry(index).await?.read_to_end_checked(&mut data).await?;
+
+        if let Some(cache) = &self.inner.cache {
+            cache.insert(index, &data);
+        }
+        Ok(data)
+    }
+
+    /// Computes the SHA-256 digest of the decompressed contents of an entry, if the provided index is valid.
+    ///
+    /// See [`seek::ZipFileReader::entry_sha256()`] for more information.
+    #[cfg(feature = "sha256")]
+    pub async fn entry_sha256(&self, index: usize) -> Result<[u8; 32]> {
+        self.reader_with_entry(index).await?.sha256_checked().await
+    }
+
+    /// Reads the entire decompressed contents of an entry, if the provided index is valid and it doesn't exceed the
+    /// provided limit (in bytes).
+    ///
+    /// This guards against entries whose data decompresses to far more than is expected (eg. ZIP bombs), as
+    /// decompression stops once the limit has been exceeded, returning [`ZipError::EntryTooLarge`]. The same error is
+    /// returned upfront if the entry's declared uncompressed size exceeds the limit.
+    pub async fn read_entry_limited(&self, index: usize, limit: u64) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.reader_with_entry(index).await?.read_to_end_limited(&mut data, limit).await?;
+        Ok(data)
+    }
+
+    /// Reads up to the first `length` decompressed bytes of an entry, if the provided index is valid.
+    ///
+    /// Fewer bytes are returned if the entry holds less data. As the entry isn't read in full, its CRC32 value isn't
+    /// verified.
+    pub async fn read_entry_prefix(&self, index: usize, length: usize) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.reader_without_entry(index).await?.take(length as u64).read_to_end(&mut data).await?;
+        Ok(data)
+    }
+
+    /// Copies the entire decompressed contents of an entry into the provided writer, if the provided index is valid.
+    ///
+    /// The number of bytes written is returned, and the entry's CRC32 value is verified once all of its data has been
+    /// read.
+    ///
+    /// As this reader is [`tokio`]-specific, a [`tokio`] writer is accepted so that it pairs with [`File::create()`].
+    pub async fn copy_entry_to<W: tokio::io::AsyncWrite + Unpin>(&self, index: usize, writer: W) -> Result<u64> {
+        self.reader_with_entry(index).await?.copy_to_checked(writer.compat_write()).await
+    }
+
+    /// Reads the entire decompressed contents of the first entry with the provided filename, if one exists.
+    ///
+    /// See [`ZipFile::entry()`] for how filenames are matched.
+    pub async fn read_entry_by_name(&self, filename: impl AsRef<[u8]>) -> Result<Vec<u8>> {
+        let (index, _) = self.file().entry(filename).ok_or(ZipError::EntryNotFound)?;
+        self.read_entry(index).await
+    }
+
+    /// Reads every entry to completion (without buffering their data), verifying each entry's CRC32 value.
+    ///
+    /// The first failure is returned as [`ZipError::EntryValidationFailed`], which names the failing entry. Encrypted
+    /// entries can't be validated without a password (see [`ZipFileReader::validate_with_password()`]).
+    pub async fn validate(&self) -> Result<()> {
+        self.validate_entries(None).await
+    }
+
+    /// Reads every entry to completion (without buffering their data), decrypting any encrypted entries with the
+    /// provided password, and verifying each entry's CRC32 value (or authentication code for AE-2 entries).
+    ///
+    /// The first failure is returned as [`ZipError::EntryValidationFailed`], which names the failing entry.
+    pub async fn validate_with_password(&self, password: &[u8]) -> Result<()> {
+        self.validate_entries(Some(password)).await
+    }
+
+    async fn validate_entries(&self, password: Option<&[u8]>) -> Result<()> {
+        for index in 0..self.file().entries().len() {
+            let reader = match (password, self.file().entries()[index].is_encrypted()) {
+                (Some(password), _) => self.reader_with_password(index, password).await,
+                (None, true) => Err(ZipError::FeatureNotSupported("validating encrypted entries without a password")),
+                (None, false) => self.reader_with_entry(index).await,
+            };
+
+            let result = match reader {
+                Ok(mut reader) => reader.consume_checked().await,
+                Err(err) => Err(err),
+            };
+
+            result.map_err(|err| crate::base::read::validation_error(self.file(), index, err))?;
+        }
+
+        Ok(())
+    }
+
+    /// Extracts every entry into the provided directory, creating it and any parent directories as needed.
+    ///
+    /// On Unix, entries which represent symbolic links are recreated as such and the permissions of each file are set
+    /// from its Unix mode (if one was stored). Elsewhere, symbolic links are extracted as regular files containing the
+    /// link's target. With the `chrono` feature enabled, each file's modification time is also set from its entry's
+    /// (see [`ZipEntry::last_modified()`]).
+    ///
+    /// [`ZipError::UnsafePath`] is returned if an entry's filename is absolute or would otherwise resolve to a path
+    /// outside of the directory (eg. via `..`), if a symbolic link's target could do the same, or if an entry's path
+    /// passes through an existing symbolic link (eg. one extracted from an earlier entry). Entries extracted prior to
+    /// that entry are left in place.
+    pub async fn extract_to<P>(&self, dir: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.extract_to_with_progress(dir, |_, _, _| {}).await
+    }
+
+    /// Extracts every entry into the provided directory, reporting progress to the provided callback.
+    ///
+    /// The callback is provided with the entry being extracted, the number of bytes written so far across all entries,
+    /// and the total number of bytes to be written (the sum of each entry's uncompressed size). It's invoked after each
+    /// chunk of an entry's data has been written, and once more when the entry has been fully extracted.
+    ///
+    /// See [`ZipFileReader::extract_to()`] for how entries are extracted.
+    pub async fn extract_to_with_progress<P, F>(&self, dir: P, mut progress: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&ZipEntry, u64, u64),
+    {
+        let total = self.file().total_uncompressed_size();
+        let mut written = 0;
+        let mut buffer = vec![0; 8192];
+
+        for (index, entry) in self.file().entries().iter().enumerate() {
+            let relative = entry.sanitized_path()?;
+            let path = dir.as_ref().join(&relative);
+            check_no_symlinks(dir.as_ref(), &relative).await?;
+
+            if entry.is_dir() {
+                tokio::fs::create_dir_all(&path).await?;
+                progress(entry, written, total);
+                continue;
+            }
+
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+
+            if entry.is_symlink() {
+                written += self.extract_symlink(index, &relative, &path).await?;
+                progress(entry, written, total);
+                continue;
+            }
+
+            self.extract_file(index, &path, &mut buffer, |read| {
+                written += read;
+                progress(entry, written, total);
+            })
+            .await?;
+
+            progress(entry, written, total);
+        }
+
+        Ok(())
+    }
+
+    /// Extracts every entry into the provided directory, decompressing up to `concurrency` entries at once.
+    ///
+    /// Each entry is extracted within its own spawned task (which opens its own file handle), so decompression is
+    /// spread across the runtime's worker threads and overlaps with IO. Directories are created upfront, and the
+    /// number of open file handles remains bounded by `concurrency` (and by [`ZipFileReader::with_max_open()`], if
+    /// used). Symbolic links are only extracted once every regular file has been, one at a time, so that no file is
+    /// written through a link extracted alongside it. Entries are otherwise extracted as with
+    /// [`ZipFileReader::extract_to()`].
+    ///
+    /// If extracting a file fails, the error of the first failing file (in index order) is returned once all tasks
+    /// have finished, and no symbolic links are extracted.
+    pub async fn extract_all_parallel<P>(&self, dir: P, concurrency: usize) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let mut files = Vec::new();
+        let mut symlinks = Vec::new();
+
+        // Validate every path and create any directories before spawning tasks, so that they don't race to do so.
+        for (index, entry) in self.file().entries().iter().enumerate() {
+            let relative = entry.sanitized_path()?;
+            let path = dir.as_ref().join(&relative);
+            check_no_symlinks(dir.as_ref(), &relative).await?;
+
+            if entry.is_dir() {
+                tokio::fs::create_dir_all(&path).await?;
+                continue;
+            }
+
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+
+            match entry.is_symlink() {
+                true => symlinks.push((index, relative, path)),
+                false => files.push((index, path)),
+            }
+        }
+
+        let tasks = futures_util::stream::iter(files).map(|(index, path)| {
+            let reader = self.clone();
+            tokio::spawn(async move { reader.extract_file(index, &path, &mut vec![0; 8192], |_| {}).await })
+        });
+
+        let mut results = tasks.buffered(concurrency.max(1));
+        let mut first_error = None;
+
+        while let Some(result) = results.next().await {
+            if let Err(err) = result.map_err(|err| ZipError::UpstreamReadError(err.into())).and_then(|result| result) {
+                first_error.get_or_insert(err);
+            }
+        }
+
+        if let Some(err) = first_error {
+            return Err(err);
+        }
+
+        // Each link is checked again as it's extracted, as an earlier link may now be along its path.
+        for (index, relative, path) in symlinks {
+            check_no_symlinks(dir.as_ref(), &relative).await?;
+            self.extract_symlink(index, &relative, &path).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Extracts a single entry to the provided path, creating any parent directories as needed, and returns the number
+    /// of bytes written.
+    ///
+    /// As with [`ZipFileReader::extract_to()`], the file's permissions are set from the entry's Unix mode (if one was
+    /// stored) on Unix and its modification time from the entry's with the `chrono` feature enabled, and entries which
+    /// represent symbolic links are recreated as a link to their stored target (elsewhere, as regular files containing
+    /// it). Directory entries are created as directories.
+    ///
+    /// The provided path is used as-is rather than being derived from the entry's filename, though the entry's
+    /// filename must still be safe and [`ZipError::UnsafePath`] is returned if a symbolic link's target would
+    /// resolve outside of the directory the entry would otherwise be extracted into.
+    ///
+    /// [`ZipError::UnsafePath`] is also returned if the provided path is an existing symbolic link or, when it ends
+    /// with the entry's filename (as when extracting entries one at a time into a common directory), if any of the
+    /// directories it passes through within that filename are (eg. a link extracted by an earlier call).
+    pub async fn extract_entry_to<P>(&self, index: usize, dest: P) -> Result<u64>
+    where
+        P: AsRef<Path>,
+    {
+        let entry = self.file().entries().get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let relative = entry.sanitized_path()?;
+        let path = dest.as_ref();
+
+        // Only the components within the entry's filename are checked, as the directory being extracted into may itself
+        // legitimately be (or pass through) a link.
+        let (dir, within) = match path.ancestors().nth(relative.components().count()) {
+            Some(dir) if path.ends_with(&relative) => (dir, relative.as_path()),
+            _ => (path.parent().unwrap_or(Path::new("")), Path::new(path.file_name().unwrap_or_default())),
+        };
+        check_no_symlinks(dir, within).await?;
+
+        if entry.is_dir() {
+            tokio::fs::create_dir_all(path).await?;
+            return Ok(0);
+        }
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        if entry.is_symlink() {
+            return self.extract_symlink(index, &relative, path).await;
+        }
+
+        let mut written = 0;
+        self.extract_file(index, path, &mut vec![0; 8192], |read| written += read).await?;
+
+        Ok(written)
+    }
+
+    /// Extracts a symbolic link entry to the provided path, returning the length of its target in bytes.
+    async fn extract_symlink(&self, index: usize, relative: &Path, path: &Path) -> Result<u64> {
+        let mut reader = self.reader_with_entry(index).await?;
+        let mut target = String::new();
+        let read = reader.read_to_string_checked(&mut target).await?;
+
+        let target = crate::utils::sanitize_symlink_target(relative, &target)?;
+        create_symlink(&target, path).await?;
+
+        Ok(read as u64)
+    }
+
+    /// Extracts a regular file entry to the provided path, invoking the callback with the length of each chunk written.
+    ///
+    /// The file's modification time (with the `chrono` feature enabled) and permissions are then set from the entry.
+    async fn extract_file<F>(&self, index: usize, path: &Path, buffer: &mut [u8], mut on_write: F) -> Result<()>
+    where
+        F: FnMut(u64),
+    {
+        let mut reader = self.reader_with_entry(index).await?;
+        let mut fs_file = File::create(path).await?.compat_write();
+
+        loop {
+            let read = reader.read(buffer).await?;
+            if read == 0 {
+                break;
+            }
+
+            fs_file.write_all(&buffer[..read]).await?;
+            on_write(read as u64);
+        }
+
+        fs_file.close().await?;
+        reader.verify().await?;
+
+        let fs_file = fs_file.into_inner().into_std().await;
+        let reader = self.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            crate::utils::apply_file_metadata(&fs_file, &reader.file().entries()[index])
+        });
+        result.await.map_err(|err| ZipError::UpstreamReadError(err.into()))?
+    }
+
+    /// Consumes this reader and returns a stream of every entry alongside a new entry reader, in index order.
+    ///
+    /// Rather than each entry reader opening its own [`File`], a single file handle is reused by each in turn: once an
+    /// entry reader is dropped, its handle is returned to this reader and sought to the next entry's data. As long as
+    /// each entry reader is dropped before the next item is polled, only one file handle is open at a time, avoiding
+    /// exhausting the OS file descriptor limit when extracting an entire archive. Should an entry reader still be held,
+    /// the next is instead given its own file handle, as entry readers never share a cursor.
+    ///
+    /// An error opening one entry is yielded as that entry's item and doesn't terminate the stream.
+    pub fn into_stream(
+        mut self,
+    ) -> impl Stream<Item = Result<(ZipEntry, ZipEntryReader<'static, Compat<PooledFile>, WithoutEntry>)>> {
+        // A returned handle is only kept for reuse if the pool has room for it.
+        if self.inner.pool.size == 0 {
+            Arc::make_mut(&mut self.inner).pool = FilePool::new(1);
+        }
+
+        futures_util::stream::unfold((self, 0), |(reader, index)| async move {
+            let entry = reader.inner.file.entries.get(index)?.entry.clone();
+            let result = reader.reader_without_entry(index).await.map(|entry_reader| (entry, entry_reader));
+
+            Some((result, (reader, index + 1)))
+        })
+    }
 
-        Ok(reader.into_with_entry(stored_entry))
+    /// Checks out a file handle from the pool, opening a new one if none are idle.
+    async fn open_file(&self) -> Result<PooledFile> {
+        self.inner.pool.checkout(&self.inner.path).await
     }
+
+    /// Returns the total number of file handles opened by entry readers.
+    #[cfg(test)]
+    pub(crate) fn files_opened(&self) -> usize {
+        self.inner.pool.opened.load(Ordering::Relaxed)
+    }
+
+    /// Waits for a permit to open a new entry reader, if the number of open entry readers is limited.
+    async fn acquire_permit(&self) -> Option<OwnedSemaphorePermit> {
+        match &self.inner.permits {
+            // The semaphore is never closed, so acquiring a permit can't fail.
+            Some(permits) => permits.clone().acquire_owned().await.ok(),
+            None => None,
+        }
+    }
+}
+
+impl Index<usize> for ZipFileReader {
+    type Output = StoredZipEntry;
+
+    /// Returns the entry at the provided index.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds. See [`ZipFileReader::get()`] for a non-panicking alternative.
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.inner.file.entries[index]
+    }
+}
+
+/// Checks that no existing file along the entry's path within the directory is a symbolic link, without blocking the
+/// runtime (see [`crate::utils::check_no_symlinks()`]).
+async fn check_no_symlinks(dir: &Path, relative: &Path) -> Result<()> {
+    let (dir, relative) = (dir.to_owned(), relative.to_owned());
+    let result = tokio::task::spawn_blocking(move || crate::utils::check_no_symlinks(&dir, &relative)).await;
+    result.map_err(|err| ZipError::UpstreamReadError(err.into()))?
+}
+
+/// Creates a symbolic link at the provided path, or a regular file containing the link's target on non-Unix targets.
+async fn create_symlink(target: &Path, link: &Path) -> Result<()> {
+    #[cfg(unix)]
+    tokio::fs::symlink(target, link).await?;
+    #[cfg(not(unix))]
+    tokio::fs::write(link, target.to_string_lossy().as_bytes()).await?;
+
+    Ok(())
+}
+
+/// Opens the file at the provided path in a shared read mode, attaching the path to any error so that it can be
+/// identified.
+async fn open(path: &Path) -> Result<File> {
+    let options = tokio::fs::OpenOptions::from(crate::utils::shared_read_options());
+    options.open(path).await.map_err(|source| ZipError::FileOpenError { path: path.to_owned(), source })
 }
@@ -4,6 +4,9 @@
 use crate::error::{Result, ZipError};
 use futures_util::io::{AsyncRead, AsyncReadExt};
 
+#[cfg(any(feature = "sync", feature = "tokio-fs"))]
+use std::path::{Component, Path, PathBuf};
+
 // Assert that the next four-byte signature read by a reader which impls AsyncRead matches the expected signature.
 pub(crate) async fn assert_signature<R: AsyncRead + Unpin>(reader: &mut R, expected: u32) -> Result<()> {
     let signature = {
@@ -16,3 +19,102 @@ pub(crate) async fn assert_signature<R: AsyncRead + Unpin>(reader: &mut R, expec
         actual => Err(ZipError::UnexpectedHeaderError(actual, expected)),
     }
 }
+
+// Validate a symbolic link's target against the (sanitised) path of the link, rejecting any target which lexically
+// resolves outside of the extraction directory.
+//
+// Parent directory components are only accepted at the start of the target (where they're resolved against the link's
+// own directory), as a parent component following another link could otherwise step outside of the directory.
+//
+// As this validation is purely lexical, it assumes that the link's own directory doesn't pass through another link. A
+// chain such as `d/l -> ..` followed by `d/l/m -> ..` would otherwise resolve outside of the directory, so extraction
+// must also reject entries whose path passes through an existing link (see `check_no_symlinks()`).
+#[cfg(any(feature = "sync", feature = "tokio-fs"))]
+pub(crate) fn sanitize_symlink_target(link: &Path, target: &str) -> Result<PathBuf> {
+    let unsafe_path = || ZipError::UnsafePath(target.to_owned());
+    let mut resolved = link.parent().map(Path::to_path_buf).unwrap_or_default();
+    let mut leading = true;
+
+    for component in Path::new(target).components() {
+        match component {
+            Component::ParentDir if leading => {
+                if !resolved.pop() {
+                    return Err(unsafe_path());
+                }
+            }
+            Component::Normal(_) => leading = false,
+            Component::CurDir => (),
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return Err(unsafe_path()),
+        }
+    }
+
+    Ok(PathBuf::from(target))
+}
+
+// Check that no existing file along the path of a (sanitised) entry within the extraction directory is a symbolic link,
+// including the entry's own path.
+//
+// Writing through a link which was extracted earlier (or already existed) could otherwise resolve outside of the
+// directory, as link targets are only validated lexically. This must be checked before any parent directories are
+// created, as doing so would also follow such a link.
+#[cfg(any(feature = "sync", feature = "tokio-fs"))]
+pub(crate) fn check_no_symlinks(dir: &Path, relative: &Path) -> Result<()> {
+    let mut path = dir.to_path_buf();
+
+    for component in relative.components() {
+        path.push(component);
+
+        match std::fs::symlink_metadata(&path) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                return Err(ZipError::UnsafePath(relative.display().to_string()));
+            }
+            Ok(_) => (),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => break,
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Ok(())
+}
+
+// Apply the metadata of a regular file entry to the file it was extracted to: its modification time (with the `chrono`
+// feature enabled) and, on Unix, its permissions from its Unix mode (if one was stored).
+//
+// The modification time is set first, as the permissions may deny writing to the file.
+#[cfg(any(feature = "sync", feature = "tokio-fs"))]
+#[cfg_attr(not(any(feature = "chrono", unix)), allow(unused_variables))]
+pub(crate) fn apply_file_metadata(file: &std::fs::File, entry: &crate::ZipEntry) -> Result<()> {
+    #[cfg(feature = "chrono")]
+    if let Some(modified) = entry.last_modified() {
+        file.set_modified(modified.into())?;
+    }
+
+    #[cfg(unix)]
+    if let Some(mode) = entry.unix_mode() {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(mode & 0o7777))?;
+    }
+
+    Ok(())
+}
+
+// Construct options which open a file for reading whilst allowing other handles (incl. those held by other processes)
+// to read, write, or delete the file concurrently.
+//
+// This is already the case when opening a file on Unix, but Windows otherwise only shares read access, failing with a
+// sharing violation if another handle to the file holds write access.
+#[cfg(any(feature = "sync", feature = "tokio-fs"))]
+pub(crate) fn shared_read_options() -> std::fs::OpenOptions {
+    let mut options = std::fs::OpenOptions::new();
+    options.read(true);
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::OpenOptionsExt;
+
+        // FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE
+        options.share_mode(0x1 | 0x2 | 0x4);
+    }
+
+    options
+}
@@ -1,8 +1,12 @@
 // Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+// Each integration test crate only uses a subset of these helpers.
+#![allow(dead_code)]
+
 use async_zip::base::read::mem;
 use async_zip::base::read::seek;
+use async_zip::base::read::stream;
 use async_zip::base::write::ZipFileWriter;
 use async_zip::Compression;
 use async_zip::ZipEntryBuilder;
@@ -95,3 +99,42 @@ pub async fn check_decompress_mem(zip_data: Vec<u8>) {
         assert_eq!(output, expected, "for {fname}, expect zip data to match file data");
     }
 }
+
+pub async fn check_decompress_seek_mem<R>(reader: R)
+where
+    R: futures_util::io::AsyncRead + futures_util::io::AsyncSeek + Unpin,
+{
+    let mut zip = seek::ZipFileReader::new(reader).await.unwrap();
+    let zip_entries: Vec<_> = zip.file().entries().to_vec();
+    assert_eq!(zip_entries.len(), FILE_LIST.len());
+    for (idx, entry) in zip_entries.into_iter().enumerate() {
+        // TODO: resolve unwrap usage
+        let fname = entry.filename().as_str().unwrap();
+        let mut output = String::new();
+        let mut reader = zip.reader_with_entry(idx).await.unwrap();
+        let _ = reader.read_to_string_checked(&mut output).await.unwrap();
+        let fs_file = format!("{FOLDER_PREFIX}/{fname}");
+        let expected = tokio::fs::read_to_string(fs_file).await.unwrap();
+        assert_eq!(output, expected, "for {fname}, expect zip data to match file data");
+    }
+}
+
+pub async fn check_decompress_stream(fname: &str) {
+    let file = File::open(fname).await.unwrap();
+    let mut zip = stream::ZipFileReader::new(file.compat());
+    while let Some(mut reader) = zip.next_with_entry().await.unwrap() {
+        // TODO: resolve unwrap usage
+        if reader.reader().entry().dir().unwrap() {
+            zip = reader.skip().await.unwrap();
+            continue;
+        }
+        // TODO: resolve unwrap usage
+        let fname = reader.reader().entry().filename().as_str().unwrap().to_owned();
+        let mut output = String::new();
+        let _ = reader.reader_mut().read_to_string_checked(&mut output).await.unwrap();
+        let fs_file = format!("{FOLDER_PREFIX}/{fname}");
+        let expected = tokio::fs::read_to_string(fs_file).await.unwrap();
+        assert_eq!(output, expected, "for {fname}, expect zip data to match file data");
+        zip = reader.done().await.unwrap();
+    }
+}
@@ -19,8 +19,133 @@ async fn zip_decompress_in_out() {
     common::check_decompress_mem(zip_data).await
 }
 
+#[cfg(feature = "bzip2")]
+#[tokio::test]
+async fn zip_bz_in_out() {
+    let zip_data = common::compress_to_mem(Compression::Bz).await;
+    common::check_decompress_mem(zip_data).await
+}
+
+#[cfg(feature = "lzma")]
+#[tokio::test]
+async fn zip_lzma_in_out() {
+    let zip_data = common::compress_to_mem(Compression::Lzma).await;
+    common::check_decompress_mem(zip_data).await
+}
+
+#[cfg(feature = "lzma")]
+#[tokio::test]
+async fn zip_lzma_whole_in_out() {
+    use async_zip::base::{read::mem, write::ZipFileWriter};
+    use async_zip::ZipEntryBuilder;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Lzma), b"foo bar").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = mem::ZipFileReader::new(data).await.unwrap();
+    // The stream is terminated by an end-of-stream marker, which must be signalled via bit 1 of the flags.
+    assert_eq!(reader.file().entries()[0].flags() & 0x2, 0x2);
+    assert_eq!(reader.read_entry(0).await.unwrap(), b"foo bar");
+}
+
 #[tokio::test]
 async fn zip_store_in_out() {
     let zip_data = common::compress_to_mem(Compression::Stored).await;
     common::check_decompress_mem(zip_data).await
 }
+
+#[tokio::test]
+async fn zip_store_in_out_seek_cursor() {
+    let zip_data = common::compress_to_mem(Compression::Stored).await;
+    common::check_decompress_seek_mem(futures_util::io::Cursor::new(zip_data)).await
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn zip_store_in_out_seek_tokio_cursor() {
+    use tokio_util::compat::TokioAsyncReadCompatExt;
+
+    let zip_data = common::compress_to_mem(Compression::Stored).await;
+    common::check_decompress_seek_mem(std::io::Cursor::new(zip_data).compat()).await
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn zip_stream_mixed_in_out() {
+    use async_zip::base::read::seek::ZipFileReader;
+    use async_zip::base::write::ZipFileWriter;
+    use async_zip::ZipEntryBuilder;
+    use futures_util::io::{AsyncWriteExt, Cursor};
+
+    let entries: &[(&str, Compression, &[u8])] = &[
+        ("stored.txt", Compression::Stored, b"Some stored data."),
+        ("deflated.txt", Compression::Deflate, &[b'a'; 4096]),
+    ];
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for (filename, compression, data) in entries {
+        let mut entry_writer =
+            writer.write_entry_stream(ZipEntryBuilder::new((*filename).into(), *compression)).await.unwrap();
+        entry_writer.write_all(data).await.unwrap();
+        entry_writer.close().await.unwrap();
+    }
+
+    let mut reader = ZipFileReader::new(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+    assert_eq!(reader.file().entries().len(), entries.len());
+
+    for (index, (filename, compression, data)) in entries.iter().enumerate() {
+        let entry = &reader.file().entries()[index];
+        assert_eq!(entry.filename().as_str().unwrap(), *filename);
+        assert_eq!(entry.compression(), *compression);
+        assert_eq!(entry.uncompressed_size(), data.len() as u64);
+
+        let mut output = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut output).await.unwrap();
+        assert_eq!(output, *data);
+    }
+
+    let deflated = &reader.file().entries()[1];
+    assert!(deflated.compressed_size() < deflated.uncompressed_size());
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn zip_merge_raw_in_out() {
+    use async_zip::base::read::mem::ZipFileReader;
+    use async_zip::base::write::ZipFileWriter;
+    use async_zip::{ZipEntry, ZipEntryBuilder};
+    use futures_util::io::AsyncReadExt;
+
+    let first = common::compress_to_mem(Compression::Deflate).await;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let builder = ZipEntryBuilder::new("extra/stored.txt".into(), Compression::Stored);
+    writer.write_entry_whole(builder, b"Some stored data.").await.unwrap();
+    let second = writer.close().await.unwrap();
+
+    // Copy every entry from both archives into a new one, without decompressing their data.
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let mut expected = Vec::new();
+
+    for data in [first, second] {
+        let reader = ZipFileReader::new(data).await.unwrap();
+
+        for (index, entry) in reader.file().entries().iter().enumerate() {
+            let mut raw = Vec::new();
+            reader.raw_reader(index).await.unwrap().read_to_end(&mut raw).await.unwrap();
+            writer.write_entry_raw(ZipEntry::clone(entry), &raw).await.unwrap();
+
+            expected.push((entry.filename().as_str().unwrap().to_owned(), reader.read_entry(index).await.unwrap()));
+        }
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().entries().len(), expected.len());
+    assert_eq!(reader.file().entries()[0].compression(), Compression::Deflate);
+
+    for (index, (filename, data)) in expected.iter().enumerate() {
+        assert_eq!(reader.file().entries()[index].filename().as_str().unwrap(), filename);
+        assert_eq!(&reader.read_entry(index).await.unwrap(), data);
+    }
+}
@@ -3,9 +3,33 @@
 
 mod common;
 
+#[cfg(feature = "zstd")]
 const ZSTD_ZIP_FILE: &str = "tests/test_inputs/sample_data.zstd.zip";
+#[cfg(feature = "deflate")]
 const DEFLATE_ZIP_FILE: &str = "tests/test_inputs/sample_data.deflate.zip";
+#[cfg(feature = "lzma")]
+const LZMA_ZIP_FILE: &str = "tests/test_inputs/sample_data.lzma.zip";
 const STORE_ZIP_FILE: &str = "tests/test_inputs/sample_data.store.zip";
+#[cfg(feature = "deflate64")]
+const DEFLATE64_ZIP_FILE: &str = "tests/test_inputs/deflate64.zip";
+const CUSTOM_EXTRA_ZIP_FILE: &str = "tests/test_inputs/custom_extra.store.zip";
+const DIRECTORIES_ZIP_FILE: &str = "tests/test_inputs/directories.store.zip";
+const DOS_ATTRIBUTES_ZIP_FILE: &str = "tests/test_inputs/dos_attributes.store.zip";
+#[cfg(feature = "chrono")]
+const EXTENDED_TIMESTAMPS_ZIP_FILE: &str = "tests/test_inputs/extended_timestamps.store.zip";
+const EXTRA_MISMATCH_ZIP_FILE: &str = "tests/test_inputs/extra_mismatch.store.zip";
+const HOST_SYSTEMS_ZIP_FILE: &str = "tests/test_inputs/host_systems.store.zip";
+#[cfg(feature = "chrono")]
+const NTFS_TIMES_ZIP_FILE: &str = "tests/test_inputs/ntfs_times.store.zip";
+const UNIX_MODES_ZIP_FILE: &str = "tests/test_inputs/unix_modes.store.zip";
+#[cfg(feature = "deflate")]
+const ZIPCRYPTO_DEFLATE_ZIP_FILE: &str = "tests/test_inputs/zipcrypto.deflate.zip";
+const ZIPCRYPTO_STORE_ZIP_FILE: &str = "tests/test_inputs/zipcrypto.store.zip";
+const ZIPCRYPTO_PASSWORD: &[u8] = b"hunter2";
+#[cfg(all(feature = "aes", feature = "deflate"))]
+const AES128_DEFLATE_ZIP_FILE: &str = "tests/test_inputs/aes128.deflate.zip";
+const AES256_STORE_ZIP_FILE: &str = "tests/test_inputs/aes256.store.zip";
+const AES_PASSWORD: &[u8] = b"hunter2";
 
 #[cfg(feature = "zstd")]
 #[tokio::test]
@@ -13,12 +37,155 @@ async fn decompress_zstd_zip_seek() {
     common::check_decompress_seek(ZSTD_ZIP_FILE).await
 }
 
+#[cfg(feature = "lzma")]
+#[tokio::test]
+async fn decompress_lzma_zip_seek() {
+    common::check_decompress_seek(LZMA_ZIP_FILE).await
+}
+
 #[cfg(feature = "deflate")]
 #[tokio::test]
 async fn decompress_deflate_zip_seek() {
     common::check_decompress_seek(DEFLATE_ZIP_FILE).await
 }
 
+#[tokio::test]
+async fn decompress_store_metadata() {
+    let data = tokio::fs::read(STORE_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    let metadata = zip.metadata();
+
+    // As stored within the EOCDR.
+    assert_eq!(metadata.disk_number(), 0);
+    assert_eq!(metadata.entries_on_disk(), 7);
+    assert_eq!(metadata.total_entries(), 7);
+    assert_eq!(metadata.directory_size(), 504);
+    assert_eq!(metadata.directory_offset(), 1398);
+    assert_eq!(metadata.total_entries(), zip.len() as u64);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn decompress_deflate_sizes() {
+    let data = tokio::fs::read(DEFLATE_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+
+    // As listed by `unzip -v`.
+    let sizes: Vec<_> = zip
+        .file()
+        .entries()
+        .iter()
+        .map(|entry| (entry.filename().as_str().unwrap(), entry.uncompressed_size(), entry.compressed_size()))
+        .collect();
+    assert_eq!(
+        sizes,
+        [
+            ("sample_data/", 0, 2),
+            ("sample_data/numeric/", 0, 2),
+            ("sample_data/numeric/forward.txt", 87, 53),
+            ("sample_data/numeric/reverse.txt", 87, 55),
+            ("sample_data/alpha/", 0, 2),
+            ("sample_data/alpha/front_to_back.txt", 416, 87),
+            ("sample_data/alpha/back_to_front.txt", 416, 87),
+        ]
+    );
+
+    assert_eq!(zip.file().total_uncompressed_size(), 1006);
+    assert_eq!(zip.file().total_compressed_size(), 288);
+
+    assert_eq!(zip.file().entries()[0].compression_ratio(), None);
+    assert_eq!(zip.file().entries()[2].compression_ratio(), Some(53.0 / 87.0));
+}
+
+/// The base implementation only relies on `futures-io`'s traits, so doesn't require a tokio runtime.
+#[test]
+fn decompress_store_zip_futures_io() {
+    use futures::io::{AllowStdIo, AsyncReadExt, Cursor};
+
+    futures::executor::block_on(async {
+        let file = AllowStdIo::new(std::fs::File::open(STORE_ZIP_FILE).unwrap());
+        let mut zip = async_zip::base::read::seek::ZipFileReader::new(file).await.unwrap();
+
+        let index = zip.file().entries().iter().position(|entry| !entry.dir().unwrap()).unwrap();
+        let fname = zip.file().entries()[index].filename().as_str().unwrap().to_owned();
+
+        let mut output = String::new();
+        zip.reader_with_entry(index).await.unwrap().read_to_string_checked(&mut output).await.unwrap();
+        assert_eq!(output, std::fs::read_to_string(format!("tests/test_inputs/{fname}")).unwrap());
+
+        let data = std::fs::read(STORE_ZIP_FILE).unwrap();
+        let mut zip = async_zip::base::read::stream::ZipFileReader::new(Cursor::new(data));
+        let mut files = 0;
+
+        while let Some(mut reader) = zip.next_with_entry().await.unwrap() {
+            let fname = reader.reader().entry().filename().as_str().unwrap().to_owned();
+            let mut output = Vec::new();
+            reader.reader_mut().read_to_end(&mut output).await.unwrap();
+
+            if !reader.reader().entry().dir().unwrap() {
+                assert_eq!(output, std::fs::read(format!("tests/test_inputs/{fname}")).unwrap());
+                files += 1;
+            }
+
+            zip = reader.done().await.unwrap();
+        }
+
+        assert_eq!(files, 4);
+    });
+}
+
+/// Self-extracting archives prepend an executable stub, so the offsets stored within the ZIP file are relative to the
+/// end of that stub.
+#[tokio::test]
+async fn decompress_prepended_stub() {
+    let mut data = vec![0x90; 1024];
+    data.extend(common::compress_to_mem(async_zip::Compression::Stored).await);
+
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(zip.file().entries()[0].header_offset(), 1024);
+
+    common::check_decompress_mem(data.clone()).await;
+    common::check_decompress_seek_mem(futures::io::Cursor::new(data)).await;
+}
+
+#[tokio::test]
+async fn decompress_prepended_stub_zip64() {
+    use async_zip::{Compression, ZipEntryBuilder};
+
+    let mut writer = async_zip::base::write::ZipFileWriter::new(Vec::new()).force_zip64();
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"bar").await.unwrap();
+
+    let mut data = vec![0x90; 1024];
+    data.extend(writer.close().await.unwrap());
+
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    assert!(zip.file().zip64());
+    assert_eq!(zip.read_entry(0).await.unwrap(), b"bar");
+}
+
+#[tokio::test]
+async fn decompress_entry_etag() {
+    let zip = async_zip::base::read::mem::ZipFileReader::new(std::fs::read(STORE_ZIP_FILE).unwrap()).await.unwrap();
+    let index =
+        zip.file().entries().iter().position(|entry| entry.filename().as_str().unwrap().ends_with("front_to_back.txt"));
+
+    assert_eq!(zip.entry_etag(index.unwrap()).unwrap(), "fb053e32-1a0");
+    assert_ne!(zip.entry_etag(index.unwrap() + 1).unwrap(), zip.entry_etag(index.unwrap()).unwrap());
+    assert!(matches!(zip.entry_etag(usize::MAX), Err(async_zip::error::ZipError::EntryIndexOutOfBounds)));
+}
+
+#[cfg(feature = "sha256")]
+#[tokio::test]
+async fn decompress_entry_sha256() {
+    let zip = async_zip::base::read::mem::ZipFileReader::new(std::fs::read(STORE_ZIP_FILE).unwrap()).await.unwrap();
+    let index =
+        zip.file().entries().iter().position(|entry| entry.filename().as_str().unwrap().ends_with("front_to_back.txt"));
+
+    let digest = zip.entry_sha256(index.unwrap()).await.unwrap();
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    assert_eq!(hex, "c8b1bf855f0dbba17f1f1b8bbcc1b026558bf4dbca1d4a4788faf1a9ba90a703");
+}
+
 #[tokio::test]
 async fn check_empty_zip_seek() {
     let mut data: Vec<u8> = Vec::new();
@@ -31,6 +198,29 @@ async fn decompress_store_zip_seek() {
     common::check_decompress_seek(STORE_ZIP_FILE).await
 }
 
+#[cfg(feature = "zstd")]
+#[tokio::test]
+async fn decompress_zstd_zip_stream() {
+    common::check_decompress_stream(ZSTD_ZIP_FILE).await
+}
+
+#[cfg(feature = "lzma")]
+#[tokio::test]
+async fn decompress_lzma_zip_stream() {
+    common::check_decompress_stream(LZMA_ZIP_FILE).await
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn decompress_deflate_zip_stream() {
+    common::check_decompress_stream(DEFLATE_ZIP_FILE).await
+}
+
+#[tokio::test]
+async fn decompress_store_zip_stream() {
+    common::check_decompress_stream(STORE_ZIP_FILE).await
+}
+
 #[cfg(feature = "zstd")]
 #[tokio::test]
 async fn decompress_zstd_zip_mem() {
@@ -38,6 +228,13 @@ async fn decompress_zstd_zip_mem() {
     common::check_decompress_mem(content).await
 }
 
+#[cfg(feature = "lzma")]
+#[tokio::test]
+async fn decompress_lzma_zip_mem() {
+    let content = tokio::fs::read(LZMA_ZIP_FILE).await.unwrap();
+    common::check_decompress_mem(content).await
+}
+
 #[cfg(feature = "deflate")]
 #[tokio::test]
 async fn decompress_deflate_zip_mem() {
@@ -70,3 +267,1123 @@ async fn decompress_deflate_zip_fs() {
 async fn decompress_store_zip_fs() {
     common::check_decompress_fs(STORE_ZIP_FILE).await
 }
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_fs_open_error_path() {
+    let path = std::env::temp_dir().join(format!("async_zip_missing_{}.zip", std::process::id()));
+
+    let err = async_zip::tokio::read::fs::ZipFileReader::new(&path).await.err().unwrap();
+    assert!(matches!(&err, async_zip::error::ZipError::FileOpenError { path: actual, .. } if *actual == path));
+    assert!(err.to_string().contains(&*path.to_string_lossy()));
+
+    // Entry readers open their own file handles, so the path is also reported if the file disappears after opening.
+    tokio::fs::copy(STORE_ZIP_FILE, &path).await.unwrap();
+    let zip = async_zip::tokio::read::fs::ZipFileReader::new(&path).await.unwrap();
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    let err = zip.reader_with_entry(0).await.err().unwrap();
+    assert!(matches!(err, async_zip::error::ZipError::FileOpenError { path: actual, .. } if actual == path));
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_fs_shared_read() {
+    let path = std::env::temp_dir().join(format!("async_zip_shared_{}.zip", std::process::id()));
+    tokio::fs::copy(STORE_ZIP_FILE, &path).await.unwrap();
+
+    // Another handle holds the file open for writing whilst two readers open and read it simultaneously.
+    let writer = tokio::fs::OpenOptions::new().read(true).write(true).open(&path).await.unwrap();
+    let (first, second) = tokio::join!(
+        async_zip::tokio::read::fs::ZipFileReader::new(&path),
+        async_zip::tokio::read::fs::ZipFileReader::new(&path)
+    );
+    let (first, second) = (first.unwrap(), second.unwrap());
+    let (first_entry, second_entry) = tokio::join!(first.read_entry(0), second.read_entry(0));
+    assert_eq!(first_entry.unwrap(), second_entry.unwrap());
+
+    drop(writer);
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn decompress_unix_modes() {
+    let data = tokio::fs::read(UNIX_MODES_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+
+    let modes: Vec<_> =
+        zip.file().entries().iter().map(|entry| (entry.filename().as_str().unwrap(), entry.unix_mode())).collect();
+    assert_eq!(modes, [("run.sh", Some(0o100755)), ("data.txt", Some(0o100644)), ("link", Some(0o120777))]);
+}
+
+#[tokio::test]
+async fn decompress_host_systems() {
+    use async_zip::AttributeCompatibility;
+
+    let data = tokio::fs::read(HOST_SYSTEMS_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+
+    let hosts: Vec<_> = zip.file().entries().iter().map(|entry| entry.attribute_compatibility()).collect();
+    assert_eq!(hosts, [AttributeCompatibility::Dos, AttributeCompatibility::Unix, AttributeCompatibility::Ntfs]);
+
+    let modes: Vec<_> = zip.file().entries().iter().map(|entry| entry.unix_mode()).collect();
+    assert_eq!(modes, [None, Some(0o100644), None]);
+    assert!(zip.file().entries().iter().all(|entry| entry.version_needed() == 20));
+}
+
+#[tokio::test]
+async fn decompress_host_system_round_trip() {
+    use async_zip::{AttributeCompatibility, Compression, ZipEntryBuilder};
+
+    let mut writer = async_zip::base::write::ZipFileWriter::new(Vec::new());
+    for compatibility in [AttributeCompatibility::Dos, AttributeCompatibility::Other(30)] {
+        let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).attribute_compatibility(compatibility);
+        writer.write_entry_whole(entry, b"foo").await.unwrap();
+    }
+
+    let zip = async_zip::base::read::mem::ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let hosts: Vec<_> = zip.file().entries().iter().map(|entry| entry.attribute_compatibility()).collect();
+    assert_eq!(hosts, [AttributeCompatibility::Dos, AttributeCompatibility::Other(30)]);
+    assert_eq!(zip.file().entries()[0].version_needed(), 10);
+}
+
+#[tokio::test]
+async fn decompress_directories() {
+    let data = tokio::fs::read(DIRECTORIES_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+
+    // Directories are marked by a trailing slash, the MS-DOS directory attribute, or the Unix file type bits.
+    let dirs: Vec<_> =
+        zip.file().entries().iter().map(|entry| (entry.filename().as_str().unwrap(), entry.is_dir())).collect();
+    assert_eq!(dirs, [("dir/", true), ("dir/file.txt", false), ("dos_dir", true), ("unix_dir", true)]);
+    assert_eq!(zip.read_entry(1).await.unwrap(), b"foo\n");
+}
+
+#[tokio::test]
+async fn decompress_dos_attributes() {
+    let data = tokio::fs::read(DOS_ATTRIBUTES_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+
+    // As listed by `zipinfo`.
+    let attributes: Vec<_> = zip
+        .file()
+        .entries()
+        .iter()
+        .map(|entry| (entry.filename().as_str().unwrap(), entry.dos_attributes(), entry.is_text()))
+        .collect();
+    assert_eq!(attributes, [("readonly.txt", 0x21, false), ("hidden.bin", 0x06, false), ("text.txt", 0x20, true)]);
+
+    assert!(zip.file().entries()[0].dos_attributes() & 0x01 != 0);
+    assert!(!zip.file().entries()[0].is_dir());
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+async fn decompress_ntfs_times() {
+    use chrono::{TimeZone, Utc};
+
+    let data = tokio::fs::read(NTFS_TIMES_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+
+    let times = zip.file().entries()[0].ntfs_times().unwrap();
+    assert_eq!(times.modified, Utc.timestamp_opt(1678806567, 123_456_700).unwrap());
+    assert_eq!(times.accessed, Utc.timestamp_opt(1678806600, 987_654_300).unwrap());
+    assert_eq!(times.created, Utc.timestamp_opt(1678800000, 500_000_000).unwrap());
+
+    // The NTFS modification time is preferred over the (2-second resolution) MS-DOS one.
+    assert_eq!(zip.file().entries()[0].last_modified(), Some(times.modified));
+    assert_eq!(zip.file().entries()[0].last_modification_date().second(), 26);
+
+    assert_eq!(zip.file().entries()[1].ntfs_times(), None);
+    assert_eq!(zip.file().entries()[1].last_modified(), Some(Utc.with_ymd_and_hms(2023, 3, 14, 15, 9, 26).unwrap()));
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+async fn decompress_extended_timestamps() {
+    use chrono::{TimeZone, Utc};
+    use futures_util::io::Cursor;
+
+    let modified = Utc.timestamp_opt(1600000001, 0).unwrap();
+    let accessed = Utc.timestamp_opt(1600000100, 0).unwrap();
+
+    let data = tokio::fs::read(EXTENDED_TIMESTAMPS_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+
+    // Written with `zip -X`, so no extended timestamp extra field is present.
+    assert_eq!(zip.file().entries()[0].extended_timestamps(), None);
+
+    // The central directory's copy of the field only holds the modification time, which is preferred over the
+    // (2-second resolution) MS-DOS one.
+    let times = zip.file().entries()[1].extended_timestamps().unwrap();
+    assert_eq!(times, async_zip::ExtendedTimestamps { modified: Some(modified), accessed: None, created: None });
+    assert_eq!(zip.file().entries()[1].last_modified(), Some(modified));
+    assert_eq!(zip.file().entries()[1].last_modification_date().second(), 42);
+
+    // The local file header's copy also holds the access time.
+    let mut zip = async_zip::base::read::stream::ZipFileReader::new(Cursor::new(data));
+    zip = zip.next_with_entry().await.unwrap().unwrap().skip().await.unwrap();
+    let entry = zip.next_with_entry().await.unwrap().unwrap();
+
+    let times = entry.reader().entry().extended_timestamps().unwrap();
+    assert_eq!(
+        times,
+        async_zip::ExtendedTimestamps { modified: Some(modified), accessed: Some(accessed), created: None }
+    );
+}
+
+#[tokio::test]
+async fn decompress_custom_extra_field() {
+    let data = tokio::fs::read(CUSTOM_EXTRA_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+
+    let entry = &zip.file().entries()[0];
+    let fields: Vec<_> = entry.raw_extra_fields().collect();
+    assert_eq!(fields, [(0xCAFE, b"build=1234".as_slice()), (0x5455, &[0x01, 0x26, 0x8E, 0x10, 0x64])]);
+    assert_eq!(entry.extra_field_raw().len(), 4 + 10 + 4 + 5);
+    assert_eq!(&entry.extra_field_raw()[..4], &[0xFE, 0xCA, 10, 0]);
+
+    assert!(zip.file().entries()[1].extra_field_raw().is_empty());
+    assert_eq!(zip.file().entries()[1].raw_extra_fields().count(), 0);
+}
+
+#[tokio::test]
+async fn decompress_symlink() {
+    let data = tokio::fs::read(UNIX_MODES_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+
+    let symlinks: Vec<_> = zip.file().entries().iter().map(|entry| entry.is_symlink()).collect();
+    assert_eq!(symlinks, [false, false, true]);
+
+    let mut target = String::new();
+    zip.reader_with_entry(2).await.unwrap().read_to_string_checked(&mut target).await.unwrap();
+    assert_eq!(target, "data.txt");
+}
+
+#[cfg(feature = "deflate64")]
+#[tokio::test]
+async fn decompress_deflate64() {
+    // The entry's second half is a single match against its first half, using a length and distance (40,000 bytes)
+    // which are only representable by Deflate64.
+    let data = tokio::fs::read(DEFLATE64_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    assert_eq!(zip.file().entries()[0].compression(), async_zip::Compression::Deflate64);
+
+    let output = zip.read_entry(0).await.unwrap();
+    assert_eq!(output.len(), 80_008);
+    assert_eq!(output[..40_000], output[40_000..80_000]);
+    assert_eq!(&output[80_000..], b"the end\n");
+}
+
+#[tokio::test]
+async fn decompress_extra_field_mismatch() {
+    // The local extra field of this entry is four bytes longer than its central directory counterpart.
+    let data = tokio::fs::read(EXTRA_MISMATCH_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+
+    assert_eq!(zip.read_entry(0).await.unwrap(), b"hello local header\n");
+}
+
+#[tokio::test]
+async fn decompress_header_offset() {
+    let data = tokio::fs::read(STORE_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+
+    // Each offset points at the signature of the entry's local file header.
+    for stored in zip.file().entries() {
+        let offset = stored.header_offset() as usize;
+        assert_eq!(data[offset..offset + 4], 0x04034b50u32.to_le_bytes());
+    }
+}
+
+#[tokio::test]
+async fn decompress_extra_field_mismatch_data_offset() {
+    let data = tokio::fs::read(EXTRA_MISMATCH_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+
+    // The local extra field is four bytes longer than the one within the central directory.
+    let stored = &zip.file().entries()[0];
+    let header_offset = stored.header_offset() as usize;
+    let filename_length = stored.filename().as_bytes().len();
+    let local_extra_length = u16::from_le_bytes([data[header_offset + 28], data[header_offset + 29]]) as usize;
+    assert_eq!(local_extra_length, stored.extra_field_raw().len() + 4);
+
+    let offset = zip.data_offset(0).await.unwrap() as usize;
+    assert_eq!(offset, header_offset + 30 + filename_length + local_extra_length);
+    assert_eq!(&data[offset..offset + 19], b"hello local header\n");
+
+    // Subsequent calls return the cached offset.
+    assert_eq!(zip.data_offset(0).await.unwrap() as usize, offset);
+    assert_eq!(zip.read_entry(0).await.unwrap(), b"hello local header\n");
+}
+
+#[tokio::test]
+async fn decompress_invalid_local_header() {
+    let mut data = tokio::fs::read(EXTRA_MISMATCH_ZIP_FILE).await.unwrap();
+    data[0] = 0;
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+
+    let result = zip.read_entry(0).await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::UnexpectedHeaderError(0x04034b00, 0x04034b50))));
+}
+
+#[tokio::test]
+async fn decompress_entries_outlive_reader() {
+    use async_zip::{StoredZipEntry, ZipEntry};
+
+    let data = tokio::fs::read(STORE_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    let manifest: Vec<ZipEntry> = zip.file().entries().iter().map(|entry| ZipEntry::clone(entry)).collect();
+    let stored: Vec<StoredZipEntry> = zip.file().entries().to_vec();
+    drop(zip);
+
+    assert!(!manifest.is_empty());
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    for (index, entry) in manifest.iter().enumerate() {
+        assert_eq!(entry, &*zip.file().entries()[index]);
+        assert_eq!(stored[index], zip.file().entries()[index]);
+        assert_eq!(entry.filename(), zip.file().entries()[index].filename());
+    }
+
+    assert_ne!(manifest[0], manifest[1]);
+    assert!(format!("{:?}", manifest[0]).contains("filename"));
+}
+
+#[tokio::test]
+async fn decompress_read_entry() {
+    use futures_util::io::AsyncReadExt;
+
+    let data = tokio::fs::read(STORE_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    let mut seek_zip = async_zip::base::read::seek::ZipFileReader::new(futures::io::Cursor::new(data)).await.unwrap();
+
+    for (index, entry) in zip.file().entries().iter().enumerate() {
+        let mut expected = Vec::new();
+        zip.reader_with_entry(index).await.unwrap().read_to_end(&mut expected).await.unwrap();
+
+        assert_eq!(zip.read_entry(index).await.unwrap(), expected);
+        assert_eq!(zip.read_entry_by_name(entry.filename_bytes()).await.unwrap(), expected);
+        assert_eq!(seek_zip.read_entry(index).await.unwrap(), expected);
+    }
+
+    let entries = zip.file().entries().len();
+    assert!(matches!(zip.read_entry(entries).await, Err(async_zip::error::ZipError::EntryIndexOutOfBounds)));
+    assert!(matches!(zip.read_entry_by_name("missing.txt").await, Err(async_zip::error::ZipError::EntryNotFound)));
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_fs_entry_buf_reader_lines() {
+    use tokio::io::AsyncBufReadExt;
+
+    let zip = async_zip::tokio::read::fs::ZipFileReader::new(STORE_ZIP_FILE).await.unwrap();
+    let index =
+        zip.file().entries().iter().position(|entry| entry.filename().as_str().unwrap().ends_with("front_to_back.txt"));
+
+    let mut lines = zip.entry_buf_reader(index.unwrap()).await.unwrap().lines();
+    let mut actual = Vec::new();
+    while let Some(line) = lines.next_line().await.unwrap() {
+        actual.push(line);
+    }
+
+    let expected = tokio::fs::read_to_string("tests/test_inputs/sample_data/alpha/front_to_back.txt").await.unwrap();
+    assert!(actual.len() > 1);
+    assert_eq!(actual, expected.lines().collect::<Vec<_>>());
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_store_zip_fs_owned() {
+    // The path only lives as long as this function, so the reader must own its copy of it.
+    async fn open() -> async_zip::tokio::read::fs::ZipFileReader {
+        let path = std::path::PathBuf::from(STORE_ZIP_FILE);
+        async_zip::tokio::read::fs::ZipFileReader::new(path).await.unwrap()
+    }
+
+    let reader = open().await;
+    let handle = tokio::spawn(async move { reader.read_entry_by_name("sample_data/numeric/forward.txt").await });
+
+    let expected = tokio::fs::read("tests/test_inputs/sample_data/numeric/forward.txt").await.unwrap();
+    assert_eq!(handle.await.unwrap().unwrap(), expected);
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_store_zip_fs_max_open() {
+    use async_zip::tokio::read::fs::ZipFileReader;
+    use std::time::Duration;
+
+    let reader = ZipFileReader::new(STORE_ZIP_FILE).await.unwrap().with_max_open(2);
+    let entries = reader.file().entries().len();
+
+    let handles: Vec<_> = (0..32)
+        .map(|task| {
+            let reader = reader.clone();
+            tokio::spawn(async move { reader.read_entry(task % entries).await })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.await.unwrap().unwrap();
+    }
+
+    // Once the limit is reached, opening another entry reader waits until an existing one is dropped.
+    let first = reader.reader_without_entry(0).await.unwrap();
+    let _second = reader.reader_without_entry(1).await.unwrap();
+    assert!(tokio::time::timeout(Duration::from_millis(50), reader.reader_without_entry(2)).await.is_err());
+
+    drop(first);
+    tokio::time::timeout(Duration::from_secs(5), reader.reader_without_entry(2)).await.unwrap().unwrap();
+
+    // A limit of zero removes the limit.
+    let reader = reader.with_max_open(0);
+    let readers = futures_util::future::try_join_all((0..4).map(|index| reader.reader_without_entry(index % entries)));
+    tokio::time::timeout(Duration::from_secs(5), readers).await.unwrap().unwrap();
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn decompress_store_zip_sync_extract() {
+    let output_dir = std::env::temp_dir().join(format!("async_zip_sync_extract_{}", std::process::id()));
+    let mut reader = async_zip::sync::ZipFileReader::new(STORE_ZIP_FILE).unwrap();
+    reader.extract_to(&output_dir).unwrap();
+
+    for filename in ["alpha/back_to_front.txt", "alpha/front_to_back.txt", "numeric/forward.txt", "numeric/reverse.txt"]
+    {
+        let expected = std::fs::read(format!("tests/test_inputs/sample_data/{filename}")).unwrap();
+        assert_eq!(std::fs::read(output_dir.join("sample_data").join(filename)).unwrap(), expected);
+    }
+
+    let expected = std::fs::read("tests/test_inputs/sample_data/numeric/forward.txt").unwrap();
+    assert_eq!(reader.read_entry_by_name("sample_data/numeric/forward.txt").unwrap(), expected);
+    reader.validate().unwrap();
+
+    std::fs::remove_dir_all(output_dir).unwrap();
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn decompress_sync_open_error_path() {
+    let result = async_zip::sync::ZipFileReader::new("tests/test_inputs/missing.zip");
+    let err = result.err().unwrap();
+    assert!(err.to_string().contains("tests/test_inputs/missing.zip"));
+}
+
+#[cfg(feature = "sync")]
+#[tokio::test]
+async fn decompress_store_zip_sync_extract_progress() {
+    use async_zip::{Compression, ZipEntryBuilder};
+
+    let mut writer = async_zip::base::write::ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("dir/".into(), Compression::Stored), &[]).await.unwrap();
+    writer
+        .write_entry_whole(ZipEntryBuilder::new("dir/large.bin".into(), Compression::Stored), &[7; 20_000])
+        .await
+        .unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("small.txt".into(), Compression::Stored), b"foo").await.unwrap();
+
+    let base_dir = std::env::temp_dir().join(format!("async_zip_sync_extract_progress_{}", std::process::id()));
+    let archive = base_dir.join("progress.zip");
+    std::fs::create_dir_all(&base_dir).unwrap();
+    std::fs::write(&archive, writer.close().await.unwrap()).unwrap();
+
+    let mut calls = Vec::new();
+    let mut reader = async_zip::sync::ZipFileReader::new(&archive).unwrap();
+    reader
+        .extract_to_with_progress(base_dir.join("output"), |entry, written, total| {
+            calls.push((entry.filename().as_str().unwrap().to_owned(), written, total));
+        })
+        .unwrap();
+
+    // Progress is reported as each chunk of an entry's data is written, rather than only once the entry is extracted.
+    assert!(calls.iter().all(|(_, _, total)| *total == 20_003));
+    assert!(calls.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    assert!(calls.iter().filter(|(filename, _, _)| filename == "dir/large.bin").count() > 2);
+    assert_eq!(calls.first().unwrap(), &("dir/".to_owned(), 0, 20_003));
+    assert_eq!(calls.last().unwrap(), &("small.txt".to_owned(), 20_003, 20_003));
+    assert_eq!(std::fs::read(base_dir.join("output").join("dir/large.bin")).unwrap(), [7; 20_000]);
+
+    std::fs::remove_dir_all(base_dir).unwrap();
+}
+
+#[cfg(feature = "sync")]
+#[tokio::test]
+async fn decompress_sync_extract_unsafe_path() {
+    use async_zip::{Compression, ZipEntryBuilder};
+
+    let mut writer = async_zip::base::write::ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("../escaped.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let base_dir = std::env::temp_dir().join(format!("async_zip_sync_unsafe_{}", std::process::id()));
+    let archive = base_dir.join("unsafe.zip");
+    std::fs::create_dir_all(&base_dir).unwrap();
+    std::fs::write(&archive, writer.close().await.unwrap()).unwrap();
+
+    let result = async_zip::sync::ZipFileReader::new(&archive).unwrap().extract_to(base_dir.join("output"));
+    assert!(matches!(result, Err(async_zip::error::ZipError::UnsafePath(path)) if path == "../escaped.txt"));
+    assert!(!base_dir.join("escaped.txt").exists());
+
+    std::fs::remove_dir_all(base_dir).unwrap();
+}
+
+#[cfg(all(feature = "sync", unix))]
+#[tokio::test]
+async fn decompress_sync_extract_symlink_chain() {
+    let base_dir = std::env::temp_dir().join(format!("async_zip_sync_extract_chain_{}", std::process::id()));
+    let archive = base_dir.join("chain.zip");
+    std::fs::create_dir_all(&base_dir).unwrap();
+    std::fs::write(&archive, symlink_chain_zip().await).unwrap();
+
+    let result = async_zip::sync::ZipFileReader::new(&archive).unwrap().extract_to(base_dir.join("target"));
+    assert!(matches!(result, Err(async_zip::error::ZipError::UnsafePath(path)) if path == "d/l/m"));
+    assert!(!base_dir.join("target").join("m").exists());
+    assert!(!base_dir.join("escaped.txt").exists());
+
+    std::fs::remove_dir_all(base_dir).unwrap();
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_fs_extract() {
+    let output_dir = std::env::temp_dir().join(format!("async_zip_fs_extract_{}", std::process::id()));
+    let reader = async_zip::tokio::read::fs::ZipFileReader::new(STORE_ZIP_FILE).await.unwrap();
+    reader.extract_to(&output_dir).await.unwrap();
+
+    for filename in ["alpha/back_to_front.txt", "alpha/front_to_back.txt", "numeric/forward.txt", "numeric/reverse.txt"]
+    {
+        let expected = tokio::fs::read(format!("tests/test_inputs/sample_data/{filename}")).await.unwrap();
+        assert_eq!(tokio::fs::read(output_dir.join("sample_data").join(filename)).await.unwrap(), expected);
+    }
+
+    tokio::fs::remove_dir_all(output_dir).await.unwrap();
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_fs_extract_progress() {
+    use async_zip::{Compression, ZipEntryBuilder};
+
+    let mut writer = async_zip::base::write::ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("dir/".into(), Compression::Stored), &[]).await.unwrap();
+    writer
+        .write_entry_whole(ZipEntryBuilder::new("dir/large.bin".into(), Compression::Stored), &[7; 20_000])
+        .await
+        .unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("small.txt".into(), Compression::Stored), b"foo").await.unwrap();
+
+    let base_dir = std::env::temp_dir().join(format!("async_zip_fs_extract_progress_{}", std::process::id()));
+    let archive = base_dir.join("progress.zip");
+    tokio::fs::create_dir_all(&base_dir).await.unwrap();
+    tokio::fs::write(&archive, writer.close().await.unwrap()).await.unwrap();
+
+    let mut calls = Vec::new();
+    let reader = async_zip::tokio::read::fs::ZipFileReader::new(&archive).await.unwrap();
+    reader
+        .extract_to_with_progress(base_dir.join("output"), |entry, written, total| {
+            calls.push((entry.filename().as_str().unwrap().to_owned(), written, total));
+        })
+        .await
+        .unwrap();
+
+    assert!(calls.iter().all(|(_, _, total)| *total == 20_003));
+    assert!(calls.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    assert!(calls.iter().filter(|(filename, _, _)| filename == "dir/large.bin").count() > 2);
+    assert_eq!(calls.first().unwrap(), &("dir/".to_owned(), 0, 20_003));
+    assert_eq!(calls.last().unwrap(), &("small.txt".to_owned(), 20_003, 20_003));
+
+    tokio::fs::remove_dir_all(base_dir).await.unwrap();
+}
+
+#[cfg(all(feature = "tokio-fs", unix))]
+#[tokio::test]
+async fn decompress_fs_extract_unix() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let output_dir = std::env::temp_dir().join(format!("async_zip_fs_extract_unix_{}", std::process::id()));
+    let reader = async_zip::tokio::read::fs::ZipFileReader::new(UNIX_MODES_ZIP_FILE).await.unwrap();
+    reader.extract_to(&output_dir).await.unwrap();
+
+    let mode = |filename| std::fs::metadata(output_dir.join(filename)).unwrap().permissions().mode() & 0o7777;
+    assert_eq!(mode("run.sh"), 0o755);
+    assert_eq!(mode("data.txt"), 0o644);
+    assert_eq!(std::fs::read_link(output_dir.join("link")).unwrap(), std::path::Path::new("data.txt"));
+
+    tokio::fs::remove_dir_all(output_dir).await.unwrap();
+}
+
+#[cfg(all(feature = "tokio-fs", feature = "chrono", unix))]
+#[tokio::test]
+async fn decompress_fs_extract_entry_to() {
+    use async_zip::{Compression, ZipEntryBuilder};
+    use chrono::{TimeZone, Utc};
+    use std::os::unix::fs::PermissionsExt;
+
+    let modified = Utc.with_ymd_and_hms(2021, 6, 1, 12, 30, 10).unwrap();
+    let entry = ZipEntryBuilder::new("dir/data.txt".into(), Compression::Stored).unix_permissions(0o640);
+    let mut writer = async_zip::base::write::ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(entry.last_modified(modified), b"hello world").await.unwrap();
+
+    let base_dir = std::env::temp_dir().join(format!("async_zip_fs_extract_entry_to_{}", std::process::id()));
+    let archive = base_dir.join("entry.zip");
+    tokio::fs::create_dir_all(&base_dir).await.unwrap();
+    tokio::fs::write(&archive, writer.close().await.unwrap()).await.unwrap();
+
+    // The destination is used as-is rather than being derived from the entry's filename.
+    let dest = base_dir.join("output/nested/renamed.txt");
+    let reader = async_zip::tokio::read::fs::ZipFileReader::new(&archive).await.unwrap();
+    assert_eq!(reader.extract_entry_to(0, &dest).await.unwrap(), 11);
+
+    // Extracting every entry applies the same metadata to each file.
+    reader.extract_to(base_dir.join("all")).await.unwrap();
+    reader.extract_all_parallel(base_dir.join("parallel"), 2).await.unwrap();
+    #[allow(unused_mut)]
+    let mut extracted = vec![dest, base_dir.join("all/dir/data.txt"), base_dir.join("parallel/dir/data.txt")];
+    #[cfg(feature = "sync")]
+    {
+        async_zip::sync::ZipFileReader::new(&archive).unwrap().extract_to(base_dir.join("sync")).unwrap();
+        extracted.push(base_dir.join("sync/dir/data.txt"));
+    }
+
+    for path in extracted {
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"hello world");
+        assert_eq!(metadata.permissions().mode() & 0o7777, 0o640, "for {}", path.display());
+        assert_eq!(metadata.modified().unwrap(), std::time::SystemTime::from(modified), "for {}", path.display());
+    }
+
+    tokio::fs::remove_dir_all(base_dir).await.unwrap();
+}
+
+#[cfg(all(feature = "tokio-fs", unix))]
+#[tokio::test]
+async fn decompress_fs_extract_entry_to_symlink_chain() {
+    let base_dir = std::env::temp_dir().join(format!("async_zip_fs_extract_entry_to_chain_{}", std::process::id()));
+    tokio::fs::create_dir_all(&base_dir).await.unwrap();
+
+    let archive = base_dir.join("chain.zip");
+    tokio::fs::write(&archive, symlink_chain_zip().await).await.unwrap();
+
+    // Links extracted by earlier calls aren't written through by later ones.
+    let target = base_dir.join("target");
+    let reader = async_zip::tokio::read::fs::ZipFileReader::new(&archive).await.unwrap();
+    for (index, entry) in reader.file().entries().iter().enumerate() {
+        let dest = target.join(entry.filename().as_str().unwrap());
+        let result = reader.extract_entry_to(index, dest).await;
+        assert_eq!(matches!(result, Err(async_zip::error::ZipError::UnsafePath(_))), index >= 2, "for index {index}");
+    }
+
+    // Nor is an existing link at the destination itself.
+    let result = reader.extract_entry_to(3, target.join("d/l")).await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::UnsafePath(_))));
+
+    assert!(!target.join("m").exists());
+    assert!(!base_dir.join("escaped.txt").exists());
+    assert!(!base_dir.join("l").exists());
+
+    tokio::fs::remove_dir_all(base_dir).await.unwrap();
+}
+
+/// Returns every path beneath the provided directory, alongside the file's contents or the symbolic link's target.
+#[cfg(feature = "tokio-fs")]
+fn snapshot_dir(dir: &std::path::Path) -> std::collections::BTreeMap<std::path::PathBuf, Option<Vec<u8>>> {
+    let mut snapshot = std::collections::BTreeMap::new();
+    let mut pending = vec![dir.to_owned()];
+
+    while let Some(current) = pending.pop() {
+        for entry in std::fs::read_dir(current).unwrap() {
+            let path = entry.unwrap().path();
+            let file_type = std::fs::symlink_metadata(&path).unwrap().file_type();
+            let contents = match (file_type.is_dir(), file_type.is_symlink()) {
+                (true, _) => None,
+                (false, true) => Some(std::fs::read_link(&path).unwrap().into_os_string().into_encoded_bytes()),
+                (false, false) => Some(std::fs::read(&path).unwrap()),
+            };
+
+            if file_type.is_dir() {
+                pending.push(path.clone());
+            }
+            snapshot.insert(path.strip_prefix(dir).unwrap().to_owned(), contents);
+        }
+    }
+
+    snapshot
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test(flavor = "multi_thread")]
+async fn decompress_fs_extract_parallel() {
+    #[allow(unused_mut)]
+    let mut fixtures = vec![STORE_ZIP_FILE, UNIX_MODES_ZIP_FILE, DIRECTORIES_ZIP_FILE];
+    #[cfg(feature = "deflate")]
+    fixtures.push(DEFLATE_ZIP_FILE);
+
+    for (index, fixture) in fixtures.into_iter().enumerate() {
+        let base_dir =
+            std::env::temp_dir().join(format!("async_zip_fs_extract_parallel_{}_{index}", std::process::id()));
+        let reader = async_zip::tokio::read::fs::ZipFileReader::new(fixture).await.unwrap();
+
+        reader.extract_to(base_dir.join("sequential")).await.unwrap();
+        for concurrency in [0, 1, 4] {
+            let output_dir = base_dir.join(format!("parallel_{concurrency}"));
+            reader.extract_all_parallel(&output_dir, concurrency).await.unwrap();
+
+            let expected = snapshot_dir(&base_dir.join("sequential"));
+            assert!(!expected.is_empty(), "for {fixture}");
+            assert_eq!(snapshot_dir(&output_dir), expected, "for {fixture} with concurrency {concurrency}");
+        }
+
+        tokio::fs::remove_dir_all(base_dir).await.unwrap();
+    }
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_fs_extract_parallel_unsafe_path() {
+    use async_zip::{Compression, ZipEntryBuilder};
+
+    let mut writer = async_zip::base::write::ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("safe.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("../escaped.txt".into(), Compression::Stored), b"bar").await.unwrap();
+
+    let base_dir = std::env::temp_dir().join(format!("async_zip_fs_extract_parallel_unsafe_{}", std::process::id()));
+    let archive = base_dir.join("unsafe.zip");
+    tokio::fs::create_dir_all(&base_dir).await.unwrap();
+    tokio::fs::write(&archive, writer.close().await.unwrap()).await.unwrap();
+
+    // Every path is validated before any entry is extracted.
+    let reader = async_zip::tokio::read::fs::ZipFileReader::new(&archive).await.unwrap();
+    let result = reader.extract_all_parallel(base_dir.join("output"), 4).await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::UnsafePath(path)) if path == "../escaped.txt"));
+    assert!(!base_dir.join("output").join("safe.txt").exists());
+
+    tokio::fs::remove_dir_all(base_dir).await.unwrap();
+}
+
+#[cfg(all(feature = "tokio-fs", unix))]
+#[tokio::test(flavor = "multi_thread")]
+async fn decompress_fs_extract_parallel_symlink_chain() {
+    let base_dir = std::env::temp_dir().join(format!("async_zip_fs_extract_parallel_chain_{}", std::process::id()));
+    tokio::fs::create_dir_all(&base_dir).await.unwrap();
+
+    let archive = base_dir.join("chain.zip");
+    tokio::fs::write(&archive, symlink_chain_zip().await).await.unwrap();
+
+    // The file is extracted before either link, so its parent directories are created as such and the first link then
+    // fails to be created over them.
+    let target = base_dir.join("target");
+    let reader = async_zip::tokio::read::fs::ZipFileReader::new(&archive).await.unwrap();
+    assert!(reader.extract_all_parallel(&target, 4).await.is_err());
+    assert!(std::fs::symlink_metadata(target.join("d/l")).unwrap().is_dir());
+    assert_eq!(std::fs::read(target.join("d/l/m/escaped.txt")).unwrap(), b"foo");
+    assert!(!base_dir.join("escaped.txt").exists());
+
+    // A link left over from an earlier extraction isn't written through either.
+    let target = base_dir.join("existing");
+    tokio::fs::create_dir_all(target.join("d")).await.unwrap();
+    std::os::unix::fs::symlink("..", target.join("d/l")).unwrap();
+    let result = reader.extract_all_parallel(&target, 4).await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::UnsafePath(path)) if path == "d/l"));
+    assert!(!base_dir.join("m").exists());
+
+    tokio::fs::remove_dir_all(base_dir).await.unwrap();
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_fs_extract_unsafe_paths() {
+    use async_zip::{Compression, ZipEntryBuilder};
+
+    let base_dir = std::env::temp_dir().join(format!("async_zip_fs_extract_unsafe_{}", std::process::id()));
+    tokio::fs::create_dir_all(&base_dir).await.unwrap();
+
+    // A symbolic link whose target escapes the directory is refused, as writing through it later would do the same.
+    let unsafe_entries =
+        [("../../etc/passwd", None, "foo"), ("/etc/passwd", None, "foo"), ("link", Some(0o120777), "../x")];
+
+    for (index, (filename, mode, data)) in unsafe_entries.into_iter().enumerate() {
+        let mut writer = async_zip::base::write::ZipFileWriter::new(Vec::new());
+        let mut entry = ZipEntryBuilder::new(filename.into(), Compression::Stored);
+        if let Some(mode) = mode {
+            entry = entry.unix_permissions(mode);
+        }
+        writer.write_entry_whole(entry, data.as_bytes()).await.unwrap();
+
+        let archive = base_dir.join(format!("unsafe_{index}.zip"));
+        tokio::fs::write(&archive, writer.close().await.unwrap()).await.unwrap();
+
+        let reader = async_zip::tokio::read::fs::ZipFileReader::new(&archive).await.unwrap();
+        let result = reader.extract_to(base_dir.join("output")).await;
+        assert!(matches!(result, Err(async_zip::error::ZipError::UnsafePath(_))), "for {filename}");
+    }
+
+    assert!(!base_dir.join("output").join("link").exists());
+    tokio::fs::remove_dir_all(base_dir).await.unwrap();
+}
+
+/// Builds an archive whose chained symbolic links are each lexically safe, but resolve outside of the extraction
+/// directory once both have been extracted: `d/l -> ..` followed by `d/l/m -> ..`.
+#[cfg(all(any(feature = "tokio-fs", feature = "sync"), unix))]
+async fn symlink_chain_zip() -> Vec<u8> {
+    use async_zip::{Compression, ZipEntryBuilder};
+
+    let mut writer = async_zip::base::write::ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("d/".into(), Compression::Stored), &[]).await.unwrap();
+    for link in ["d/l", "d/l/m"] {
+        let entry = ZipEntryBuilder::new(link.into(), Compression::Stored).unix_permissions(0o120777);
+        writer.write_entry_whole(entry, b"..").await.unwrap();
+    }
+    writer
+        .write_entry_whole(ZipEntryBuilder::new("d/l/m/escaped.txt".into(), Compression::Stored), b"foo")
+        .await
+        .unwrap();
+    writer.close().await.unwrap()
+}
+
+#[cfg(all(feature = "tokio-fs", unix))]
+#[tokio::test]
+async fn decompress_fs_extract_symlink_chain() {
+    let base_dir = std::env::temp_dir().join(format!("async_zip_fs_extract_chain_{}", std::process::id()));
+    tokio::fs::create_dir_all(&base_dir).await.unwrap();
+
+    let archive = base_dir.join("chain.zip");
+    tokio::fs::write(&archive, symlink_chain_zip().await).await.unwrap();
+
+    // The second link passes through the first, so is refused before it (or anything beneath it) is extracted.
+    let reader = async_zip::tokio::read::fs::ZipFileReader::new(&archive).await.unwrap();
+    let result = reader.extract_to(base_dir.join("target")).await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::UnsafePath(path)) if path == "d/l/m"));
+    assert!(!base_dir.join("target").join("m").exists());
+    assert!(!base_dir.join("escaped.txt").exists());
+
+    tokio::fs::remove_dir_all(base_dir).await.unwrap();
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_store_zip_fs_stream() {
+    use futures_util::{io::AsyncReadExt, StreamExt};
+
+    let output_dir = std::env::temp_dir().join(format!("async_zip_fs_stream_{}", std::process::id()));
+    let reader = async_zip::tokio::read::fs::ZipFileReader::new(STORE_ZIP_FILE).await.unwrap();
+    let mut entries = Box::pin(reader.into_stream());
+    let mut extracted = Vec::new();
+
+    while let Some(result) = entries.next().await {
+        let (entry, mut entry_reader) = result.unwrap();
+        let path = output_dir.join(entry.filename().as_str().unwrap());
+
+        if entry.dir().unwrap() {
+            tokio::fs::create_dir_all(&path).await.unwrap();
+            continue;
+        }
+
+        let mut data = Vec::new();
+        entry_reader.read_to_end(&mut data).await.unwrap();
+        assert_eq!(entry_reader.compute_hash(), entry.crc32());
+
+        tokio::fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+        tokio::fs::write(&path, data).await.unwrap();
+        extracted.push(entry.filename().as_str().unwrap().to_owned());
+    }
+
+    assert_eq!(extracted.len(), 4);
+
+    for fname in extracted {
+        let actual = tokio::fs::read(output_dir.join(&fname)).await.unwrap();
+        let expected = tokio::fs::read(format!("tests/test_inputs/{fname}")).await.unwrap();
+        assert_eq!(actual, expected, "for {fname}, expect extracted data to match file data");
+    }
+
+    tokio::fs::remove_dir_all(&output_dir).await.unwrap();
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_store_zip_fs_stream_held() {
+    use futures_util::{io::AsyncReadExt, StreamExt};
+
+    // Entry readers which are held at once are given their own file handles, so don't share a cursor. The entries are
+    // larger than the readers' buffers, so that each is read from the file more than once.
+    let mut writer = async_zip::base::write::ZipFileWriter::new(Vec::new());
+    for (filename, byte) in [("a.bin", b'a'), ("b.bin", b'b')] {
+        let entry = async_zip::ZipEntryBuilder::new(filename.into(), async_zip::Compression::Stored);
+        writer.write_entry_whole(entry, &[byte; 64 * 1024]).await.unwrap();
+    }
+    let path = std::env::temp_dir().join(format!("async_zip_fs_stream_held_{}.zip", std::process::id()));
+    tokio::fs::write(&path, writer.close().await.unwrap()).await.unwrap();
+
+    let reader = async_zip::tokio::read::fs::ZipFileReader::new(&path).await.unwrap();
+    let mut entries = Box::pin(reader.into_stream());
+    let (_, mut first) = entries.next().await.unwrap().unwrap();
+    let (_, mut second) = entries.next().await.unwrap().unwrap();
+
+    let mut buffer = [0; 4096];
+    for _ in 0..16 {
+        first.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(buffer, [b'a'; 4096]);
+        second.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(buffer, [b'b'; 4096]);
+    }
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+/// Reads the single encrypted entry of an encrypted fixture with the given password.
+async fn read_encrypted(fname: &str, password: &[u8]) -> async_zip::error::Result<String> {
+    read_encrypted_data(tokio::fs::read(fname).await.unwrap(), password).await
+}
+
+async fn read_encrypted_data(data: Vec<u8>, password: &[u8]) -> async_zip::error::Result<String> {
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    assert!(zip.file().entries()[0].encrypted());
+
+    let mut output = String::new();
+    zip.reader_with_password(0, password).await?.read_to_string_checked(&mut output).await?;
+    Ok(output)
+}
+
+fn encrypted_expected() -> String {
+    (0..64).map(|line| format!("{line}: The quick brown fox jumps over the lazy dog.\n")).collect()
+}
+
+#[tokio::test]
+async fn decompress_zipcrypto_store() {
+    assert_eq!(read_encrypted(ZIPCRYPTO_STORE_ZIP_FILE, ZIPCRYPTO_PASSWORD).await.unwrap(), encrypted_expected());
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn decompress_zipcrypto_deflate() {
+    assert_eq!(read_encrypted(ZIPCRYPTO_DEFLATE_ZIP_FILE, ZIPCRYPTO_PASSWORD).await.unwrap(), encrypted_expected());
+}
+
+#[tokio::test]
+async fn decompress_zipcrypto_wrong_password() {
+    let result = read_encrypted(ZIPCRYPTO_STORE_ZIP_FILE, b"hunter3").await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::WrongPassword)));
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn decompress_aes256_store() {
+    assert_eq!(read_encrypted(AES256_STORE_ZIP_FILE, AES_PASSWORD).await.unwrap(), encrypted_expected());
+}
+
+#[tokio::test]
+async fn decompress_encrypted_entries() {
+    for path in [ZIPCRYPTO_STORE_ZIP_FILE, AES256_STORE_ZIP_FILE, STORE_ZIP_FILE] {
+        let data = tokio::fs::read(path).await.unwrap();
+        let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+        let expected: &[usize] = if path == STORE_ZIP_FILE { &[] } else { &[0] };
+        assert_eq!(zip.encrypted_entries(), expected, "for {path}");
+    }
+}
+
+#[cfg(all(feature = "aes", feature = "deflate"))]
+#[tokio::test]
+async fn decompress_aes128_deflate() {
+    let data = tokio::fs::read(AES128_DEFLATE_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(zip.file().entries()[0].compression(), async_zip::Compression::Deflate);
+
+    assert_eq!(read_encrypted_data(data, AES_PASSWORD).await.unwrap(), encrypted_expected());
+}
+
+/// The encrypted Deflate fixtures alongside their passwords.
+#[cfg(feature = "deflate")]
+fn encrypted_deflate_fixtures() -> Vec<(&'static str, &'static [u8])> {
+    #[allow(unused_mut)]
+    let mut fixtures = vec![(ZIPCRYPTO_DEFLATE_ZIP_FILE, ZIPCRYPTO_PASSWORD)];
+    #[cfg(feature = "aes")]
+    fixtures.push((AES128_DEFLATE_ZIP_FILE, AES_PASSWORD));
+    fixtures
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn decompress_encrypted_deflate_seek() {
+    // The encryption header must be consumed before the compressed stream, and any AES authentication code excluded
+    // from it, for the entry's data to be inflated and verified.
+    for (path, password) in encrypted_deflate_fixtures() {
+        let data = tokio::fs::read(path).await.unwrap();
+        let mut zip = async_zip::base::read::seek::ZipFileReader::new(futures::io::Cursor::new(data)).await.unwrap();
+
+        let mut output = String::new();
+        let mut reader = zip.reader_with_password(0, password).await.unwrap();
+        reader.read_to_string_checked(&mut output).await.unwrap();
+        assert_eq!(output, encrypted_expected(), "for {path}");
+    }
+}
+
+#[cfg(feature = "deflate")]
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_encrypted_deflate_fs() {
+    for (path, password) in encrypted_deflate_fixtures() {
+        let zip = async_zip::tokio::read::fs::ZipFileReader::new(path).await.unwrap();
+
+        let mut output = String::new();
+        let mut reader = zip.reader_with_password(0, password).await.unwrap();
+        reader.read_to_string_checked(&mut output).await.unwrap();
+        assert_eq!(output, encrypted_expected(), "for {path}");
+    }
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn decompress_aes_compression_method() {
+    let data = tokio::fs::read(AES256_STORE_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    let reader = zip.reader_with_password(0, AES_PASSWORD).await.unwrap();
+    assert_eq!(reader.compression_method(), async_zip::Compression::Stored);
+
+    #[cfg(feature = "deflate")]
+    {
+        let data = tokio::fs::read(AES128_DEFLATE_ZIP_FILE).await.unwrap();
+        let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+        let reader = zip.reader_with_password(0, AES_PASSWORD).await.unwrap();
+        assert_eq!(reader.compression_method(), async_zip::Compression::Deflate);
+    }
+}
+
+#[tokio::test]
+async fn decompress_validate() {
+    use async_zip::error::ZipError;
+    use async_zip::{Compression, ZipEntryBuilder};
+
+    let mut writer = async_zip::base::write::ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("first.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("second.txt".into(), Compression::Stored), b"bar").await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    zip.validate().await.unwrap();
+
+    let mut seek_zip = async_zip::base::read::seek::ZipFileReader::new(futures::io::Cursor::new(&data)).await.unwrap();
+    seek_zip.validate().await.unwrap();
+
+    // Flip a byte of the second entry's data.
+    let offset = data.windows(3).position(|window| window == b"bar").unwrap();
+    data[offset] ^= 0xFF;
+
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    match zip.validate().await {
+        Err(ZipError::EntryValidationFailed { index, filename, source }) => {
+            assert_eq!((index, filename.as_str()), (1, "second.txt"));
+            assert!(matches!(*source, ZipError::CRC32CheckError { .. }));
+        }
+        result => panic!("expected a validation failure, got {result:?}"),
+    }
+}
+
+#[cfg(feature = "tokio-fs")]
+#[tokio::test]
+async fn decompress_validate_fs() {
+    let zip = async_zip::tokio::read::fs::ZipFileReader::new(STORE_ZIP_FILE).await.unwrap();
+    zip.validate().await.unwrap();
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn decompress_validate_aes() {
+    use async_zip::error::ZipError;
+
+    let mut data = tokio::fs::read(AES256_STORE_ZIP_FILE).await.unwrap();
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    zip.validate_with_password(AES_PASSWORD).await.unwrap();
+
+    let result = zip.validate().await;
+    assert!(matches!(result, Err(ZipError::EntryValidationFailed { index: 0, .. })));
+
+    // Corrupt the first byte of encrypted data (see `decompress_aes_corrupted()`), failing the authentication code.
+    data[30 + "secret.txt".len() + 11 + 16 + 2] ^= 0xFF;
+
+    let zip = async_zip::base::read::mem::ZipFileReader::new(data).await.unwrap();
+    match zip.validate_with_password(AES_PASSWORD).await {
+        Err(ZipError::EntryValidationFailed { filename, source, .. }) => {
+            assert_eq!(filename, "secret.txt");
+            assert!(matches!(*source, ZipError::UpstreamReadError(_)));
+        }
+        result => panic!("expected a validation failure, got {result:?}"),
+    }
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn decompress_aes_wrong_password() {
+    let result = read_encrypted(AES256_STORE_ZIP_FILE, b"hunter3").await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::WrongPassword)));
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn decompress_aes_corrupted() {
+    let mut data = tokio::fs::read(AES256_STORE_ZIP_FILE).await.unwrap();
+
+    // Corrupt the first byte of encrypted data, which follows the local file header (30 bytes), the filename, the AES
+    // extra field (11 bytes), the salt (16 bytes), and the password verification value (2 bytes).
+    data[30 + "secret.txt".len() + 11 + 16 + 2] ^= 0xFF;
+
+    let result = read_encrypted_data(data, AES_PASSWORD).await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::UpstreamReadError(_))));
+}
+
+#[cfg(not(feature = "aes"))]
+#[tokio::test]
+async fn decompress_aes_not_supported() {
+    let result = read_encrypted(AES256_STORE_ZIP_FILE, AES_PASSWORD).await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::FeatureNotSupported(_))));
+}
+
+/// Reads the single encrypted entry of an encrypted fixture with the streaming reader, optionally with a password.
+async fn read_encrypted_stream(fname: &str, password: Option<&[u8]>) -> async_zip::error::Result<String> {
+    let data = tokio::fs::read(fname).await.unwrap();
+    let mut zip = async_zip::base::read::stream::ZipFileReader::new(futures::io::Cursor::new(data));
+    if let Some(password) = password {
+        zip = zip.with_password(password);
+    }
+
+    let mut reader = zip.next_with_entry().await?.unwrap();
+    let mut output = String::new();
+    reader.reader_mut().read_to_string_checked(&mut output).await?;
+
+    // Any data descriptor must have been consumed for the central directory to be reached.
+    assert!(reader.done().await?.next_with_entry().await?.is_none());
+    Ok(output)
+}
+
+#[tokio::test]
+async fn decompress_zipcrypto_store_stream() {
+    let output = read_encrypted_stream(ZIPCRYPTO_STORE_ZIP_FILE, Some(ZIPCRYPTO_PASSWORD)).await.unwrap();
+    assert_eq!(output, encrypted_expected());
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn decompress_zipcrypto_deflate_stream() {
+    let output = read_encrypted_stream(ZIPCRYPTO_DEFLATE_ZIP_FILE, Some(ZIPCRYPTO_PASSWORD)).await.unwrap();
+    assert_eq!(output, encrypted_expected());
+}
+
+#[tokio::test]
+async fn decompress_zipcrypto_stream_password_required() {
+    let result = read_encrypted_stream(ZIPCRYPTO_STORE_ZIP_FILE, None).await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::PasswordRequired(name)) if name == "secret.txt"));
+}
+
+#[tokio::test]
+async fn decompress_zipcrypto_stream_wrong_password() {
+    let result = read_encrypted_stream(ZIPCRYPTO_STORE_ZIP_FILE, Some(b"hunter3")).await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::WrongPassword)));
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn decompress_aes256_store_stream() {
+    let output = read_encrypted_stream(AES256_STORE_ZIP_FILE, Some(AES_PASSWORD)).await.unwrap();
+    assert_eq!(output, encrypted_expected());
+
+    let result = read_encrypted_stream(AES256_STORE_ZIP_FILE, None).await;
+    assert!(matches!(result, Err(async_zip::error::ZipError::PasswordRequired(_))));
+}